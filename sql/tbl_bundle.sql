@@ -0,0 +1,23 @@
+-- Partes dos combos. Um produto com partes é um combo, vendido pelo seu próprio preço.
+CREATE TABLE IF NOT EXISTS tbl_bundle_slot (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER NOT NULL REFERENCES tbl_product (id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    quantity INTEGER NOT NULL DEFAULT 1 CHECK (quantity > 0),
+    position INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (product_id, name)
+);
+
+-- Produtos que podem ocupar cada parte. Com um único produto a parte é fixa.
+CREATE TABLE IF NOT EXISTS tbl_bundle_slot_product (
+    slot_id INTEGER NOT NULL REFERENCES tbl_bundle_slot (id) ON DELETE CASCADE,
+    product_id INTEGER NOT NULL REFERENCES tbl_product (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (slot_id, product_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bundle_slot_product ON tbl_bundle_slot_product (product_id);
+
+-- Componentes dos combos em cada item, em JSON:
+--   [{"slot_id": 1, "product_id": 7, "slot": "Bebida", "name": "Suco de laranja", "quantity": 1}]
+ALTER TABLE tbl_cart_item ADD COLUMN IF NOT EXISTS components TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1,7 @@
+-- Código do produto (SKU) usado para identificar os produtos na importação do catálogo.
+-- Os produtos existentes recebem um código a partir do id e podem ser renomeados pela API.
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS sku VARCHAR(64);
+UPDATE tbl_product SET sku = 'prod-' || id WHERE sku IS NULL;
+
+-- Um produto excluído não impede que o seu código seja usado por um novo produto
+CREATE UNIQUE INDEX IF NOT EXISTS idx_product_sku ON tbl_product (sku) WHERE deleted_at IS NULL;
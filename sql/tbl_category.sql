@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS tbl_category (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    description VARCHAR(255) NOT NULL DEFAULT '',
+    icon VARCHAR(255) NOT NULL DEFAULT '',
+    position INTEGER NOT NULL DEFAULT 0,
+    available BOOLEAN NOT NULL DEFAULT true
+);
+
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS category_id INTEGER REFERENCES tbl_category (id) ON DELETE SET NULL;
+CREATE INDEX IF NOT EXISTS idx_product_category ON tbl_product (category_id);
+
+ALTER TABLE tbl_promotion ADD COLUMN IF NOT EXISTS category_id INTEGER REFERENCES tbl_category (id) ON DELETE CASCADE;
@@ -0,0 +1,21 @@
+CREATE TABLE IF NOT EXISTS tbl_courier (
+    idtbl_user INTEGER PRIMARY KEY REFERENCES tbl_user (id) ON DELETE CASCADE,
+    active BOOLEAN NOT NULL DEFAULT true,
+    available BOOLEAN NOT NULL DEFAULT true,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    location_updated_at TIMESTAMP WITH TIME ZONE,
+    last_assigned_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE TABLE IF NOT EXISTS tbl_delivery (
+    id_purchase INTEGER PRIMARY KEY REFERENCES tbl_order (id_purchase) ON DELETE CASCADE,
+    id_courier INTEGER NOT NULL REFERENCES tbl_courier (idtbl_user),
+    assigned_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    confirmation_code CHAR(4) NOT NULL,
+    delivered_at TIMESTAMP WITH TIME ZONE,
+    recipient_name VARCHAR(100)
+);
+
+CREATE INDEX IF NOT EXISTS idx_delivery_courier_open
+    ON tbl_delivery (id_courier) WHERE delivered_at IS NULL;
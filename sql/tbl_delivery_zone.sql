@@ -0,0 +1,22 @@
+-- area guarda a geometria da zona em JSON:
+--   {"type": "radius", "min_km": 0, "max_km": 3}
+--   {"type": "polygon", "points": [[lat, lon], [lat, lon], [lat, lon]]}
+CREATE TABLE IF NOT EXISTS tbl_delivery_zone (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL,
+    area TEXT NOT NULL,
+    fee NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    min_order NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    eta_minutes INTEGER NOT NULL,
+    active BOOLEAN NOT NULL DEFAULT true
+);
+
+-- Valores do pedido calculados pelo servidor
+CREATE TABLE IF NOT EXISTS tbl_order (
+    id_purchase INTEGER PRIMARY KEY,
+    idtbl_user INTEGER NOT NULL,
+    id_delivery_zone INTEGER REFERENCES tbl_delivery_zone (id) ON DELETE SET NULL,
+    subtotal NUMERIC(12, 2) NOT NULL,
+    delivery_fee NUMERIC(12, 2) NOT NULL,
+    total NUMERIC(12, 2) NOT NULL
+);
@@ -0,0 +1,48 @@
+CREATE TABLE IF NOT EXISTS tbl_loyalty_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    earn_rate DOUBLE PRECISION NOT NULL,
+    burn_rate DOUBLE PRECISION NOT NULL,
+    expiry_days INTEGER NOT NULL,
+    min_redeem_points INTEGER NOT NULL
+);
+
+-- 1 ponto por real gasto, cada ponto vale R$ 0,05, validade de 180 dias
+INSERT INTO tbl_loyalty_settings (id, earn_rate, burn_rate, expiry_days, min_redeem_points)
+VALUES (1, 1, 0.05, 180, 100)
+ON CONFLICT (id) DO NOTHING;
+
+-- Transações de pontos. Lotes do tipo 'earn' guardam em 'remaining' o saldo ainda não resgatado ou expirado.
+CREATE TABLE IF NOT EXISTS tbl_loyalty_transaction (
+    id SERIAL PRIMARY KEY,
+    idtbl_user INTEGER NOT NULL REFERENCES tbl_user (id) ON DELETE CASCADE,
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('earn', 'redeem', 'expire', 'refund')),
+    points INTEGER NOT NULL CHECK (points > 0),
+    id_purchase INTEGER,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE,
+    remaining INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_loyalty_earn_purchase
+    ON tbl_loyalty_transaction (id_purchase) WHERE kind = 'earn';
+
+-- Estorno dos pontos resgatados em pedidos cancelados. Lotes 'refund' também guardam o saldo em 'remaining'.
+ALTER TABLE tbl_loyalty_transaction DROP CONSTRAINT IF EXISTS tbl_loyalty_transaction_kind_check;
+ALTER TABLE tbl_loyalty_transaction ADD CONSTRAINT tbl_loyalty_transaction_kind_check
+    CHECK (kind IN ('earn', 'redeem', 'expire', 'refund'));
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_loyalty_refund_purchase
+    ON tbl_loyalty_transaction (id_purchase) WHERE kind = 'refund';
+
+-- Lançamentos em partidas dobradas: os lançamentos de cada transação somam zero
+CREATE TABLE IF NOT EXISTS tbl_loyalty_entry (
+    id SERIAL PRIMARY KEY,
+    id_transaction INTEGER NOT NULL REFERENCES tbl_loyalty_transaction (id) ON DELETE CASCADE,
+    account VARCHAR(30) NOT NULL,
+    amount INTEGER NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_loyalty_entry_account ON tbl_loyalty_entry (account);
+
+ALTER TABLE tbl_order ADD COLUMN IF NOT EXISTS loyalty_points INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE tbl_order ADD COLUMN IF NOT EXISTS loyalty_discount NUMERIC(12, 2) NOT NULL DEFAULT 0;
@@ -0,0 +1,27 @@
+-- Histórico de preços dos produtos. Sem chave estrangeira, para que o histórico continue disponível após a exclusão do produto.
+CREATE TABLE IF NOT EXISTS tbl_price_history (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER NOT NULL,
+    price NUMERIC(12, 2) NOT NULL,
+    idtbl_user INTEGER REFERENCES tbl_user (id) ON DELETE SET NULL,
+    changed_at TIMESTAMP NOT NULL
+);
+CREATE INDEX IF NOT EXISTS idx_price_history_product ON tbl_price_history (product_id, changed_at);
+
+-- Preço atual dos produtos cadastrados antes do histórico
+INSERT INTO tbl_price_history (product_id, price, changed_at)
+SELECT id, price, LOCALTIMESTAMP FROM tbl_product p
+WHERE NOT EXISTS (SELECT 1 FROM tbl_price_history h WHERE h.product_id = p.id);
+
+-- Alterações do cardápio com os dados, em JSON, antes e depois de cada alteração
+CREATE TABLE IF NOT EXISTS tbl_menu_audit (
+    id SERIAL PRIMARY KEY,
+    entity VARCHAR(20) NOT NULL CHECK (entity IN ('product', 'category')),
+    entity_id INTEGER NOT NULL,
+    action VARCHAR(10) NOT NULL CHECK (action IN ('create', 'update', 'delete')),
+    idtbl_user INTEGER REFERENCES tbl_user (id) ON DELETE SET NULL,
+    changed_at TIMESTAMP NOT NULL,
+    before TEXT,
+    after TEXT
+);
+CREATE INDEX IF NOT EXISTS idx_menu_audit_entity ON tbl_menu_audit (entity, entity_id, changed_at);
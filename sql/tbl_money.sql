@@ -0,0 +1,19 @@
+-- Valores monetários em NUMERIC(12, 2), lidos pelo servidor em centavos (ver 'src/models/money.rs').
+-- Converte as colunas criadas antes como DOUBLE PRECISION, arredondando para o centavo.
+ALTER TABLE tbl_product ALTER COLUMN price TYPE NUMERIC(12, 2) USING round(price::NUMERIC, 2);
+ALTER TABLE tbl_cart_item ALTER COLUMN price TYPE NUMERIC(12, 2) USING round(price::NUMERIC, 2);
+ALTER TABLE tbl_product_option ALTER COLUMN price_delta TYPE NUMERIC(12, 2) USING round(price_delta::NUMERIC, 2);
+
+ALTER TABLE tbl_delivery_zone
+    ALTER COLUMN fee TYPE NUMERIC(12, 2) USING round(fee::NUMERIC, 2),
+    ALTER COLUMN min_order TYPE NUMERIC(12, 2) USING round(min_order::NUMERIC, 2);
+
+ALTER TABLE tbl_order
+    ALTER COLUMN subtotal TYPE NUMERIC(12, 2) USING round(subtotal::NUMERIC, 2),
+    ALTER COLUMN delivery_fee TYPE NUMERIC(12, 2) USING round(delivery_fee::NUMERIC, 2),
+    ALTER COLUMN discount TYPE NUMERIC(12, 2) USING round(discount::NUMERIC, 2),
+    ALTER COLUMN loyalty_discount TYPE NUMERIC(12, 2) USING round(loyalty_discount::NUMERIC, 2),
+    ALTER COLUMN total TYPE NUMERIC(12, 2) USING round(total::NUMERIC, 2);
+
+ALTER TABLE tbl_promotion ALTER COLUMN min_order TYPE NUMERIC(12, 2) USING round(min_order::NUMERIC, 2);
+ALTER TABLE tbl_order_promotion ALTER COLUMN amount TYPE NUMERIC(12, 2) USING round(amount::NUMERIC, 2);
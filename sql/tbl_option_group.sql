@@ -0,0 +1,24 @@
+CREATE TABLE IF NOT EXISTS tbl_option_group (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER NOT NULL REFERENCES tbl_product (id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    min_select INTEGER NOT NULL DEFAULT 0,
+    max_select INTEGER NOT NULL DEFAULT 1,
+    position INTEGER NOT NULL DEFAULT 0,
+    CHECK (min_select >= 0 AND max_select >= 1 AND min_select <= max_select),
+    UNIQUE (product_id, name)
+);
+
+CREATE TABLE IF NOT EXISTS tbl_product_option (
+    id SERIAL PRIMARY KEY,
+    group_id INTEGER NOT NULL REFERENCES tbl_option_group (id) ON DELETE CASCADE,
+    name VARCHAR(50) NOT NULL,
+    price_delta NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    available BOOLEAN NOT NULL DEFAULT true,
+    position INTEGER NOT NULL DEFAULT 0,
+    UNIQUE (group_id, name)
+);
+
+-- Opções escolhidas em cada item, em JSON:
+--   [{"option_id": 3, "group": "Tamanho", "name": "G", "price_delta": 4.0}]
+ALTER TABLE tbl_cart_item ADD COLUMN IF NOT EXISTS options TEXT NOT NULL DEFAULT '[]';
@@ -0,0 +1,16 @@
+-- Alergênicos declarados (ver 'src/models/nutrition.rs') e informação nutricional por porção dos produtos
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS allergens TEXT[] NOT NULL DEFAULT '{}';
+CREATE INDEX IF NOT EXISTS idx_product_allergens ON tbl_product USING GIN (allergens);
+
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS portion_grams DOUBLE PRECISION;
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS kcal DOUBLE PRECISION;
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS protein DOUBLE PRECISION;
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS carbs DOUBLE PRECISION;
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS fat DOUBLE PRECISION;
+
+-- A informação nutricional é gravada completa ou não é gravada
+ALTER TABLE tbl_product DROP CONSTRAINT IF EXISTS tbl_product_nutrition_check;
+ALTER TABLE tbl_product ADD CONSTRAINT tbl_product_nutrition_check CHECK (
+    (portion_grams IS NULL AND kcal IS NULL AND protein IS NULL AND carbs IS NULL AND fat IS NULL)
+    OR (portion_grams IS NOT NULL AND kcal IS NOT NULL AND protein IS NOT NULL AND carbs IS NOT NULL AND fat IS NOT NULL)
+);
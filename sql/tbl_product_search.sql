@@ -0,0 +1,19 @@
+-- Busca textual de produtos em português, sem diferenciar acentos ("feijao" encontra "feijão")
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_ts_config WHERE cfgname = 'portuguese_unaccent') THEN
+        CREATE TEXT SEARCH CONFIGURATION portuguese_unaccent (COPY = portuguese);
+        ALTER TEXT SEARCH CONFIGURATION portuguese_unaccent
+            ALTER MAPPING FOR hword, hword_part, word WITH unaccent, portuguese_stem;
+    END IF;
+END
+$$;
+
+-- O nome tem peso maior que a descrição na relevância
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS search_vector TSVECTOR GENERATED ALWAYS AS (
+    setweight(to_tsvector('portuguese_unaccent', name), 'A') ||
+    setweight(to_tsvector('portuguese_unaccent', description), 'B')
+) STORED;
+CREATE INDEX IF NOT EXISTS idx_product_search ON tbl_product USING GIN (search_vector);
@@ -0,0 +1,27 @@
+-- kind guarda o tipo de desconto em JSON:
+--   {"type": "percentage", "percent": 10}
+--   {"type": "fixed", "amount": 5}
+--   {"type": "free_delivery"}
+CREATE TABLE IF NOT EXISTS tbl_promotion (
+    id SERIAL PRIMARY KEY,
+    code VARCHAR(20) UNIQUE,
+    description VARCHAR(255) NOT NULL,
+    kind TEXT NOT NULL,
+    product_id INTEGER REFERENCES tbl_product (id) ON DELETE CASCADE,
+    min_order NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    valid_from TIMESTAMP,
+    valid_until TIMESTAMP,
+    max_uses INTEGER,
+    max_uses_per_user INTEGER,
+    active BOOLEAN NOT NULL DEFAULT true
+);
+
+CREATE TABLE IF NOT EXISTS tbl_order_promotion (
+    id_purchase INTEGER NOT NULL REFERENCES tbl_order (id_purchase) ON DELETE CASCADE,
+    id_promotion INTEGER NOT NULL REFERENCES tbl_promotion (id),
+    idtbl_user INTEGER NOT NULL,
+    amount NUMERIC(12, 2) NOT NULL,
+    PRIMARY KEY (id_purchase, id_promotion)
+);
+
+ALTER TABLE tbl_order ADD COLUMN IF NOT EXISTS discount NUMERIC(12, 2) NOT NULL DEFAULT 0;
@@ -0,0 +1,28 @@
+-- Horários do cardápio. Cada linha pertence a um produto ou a uma categoria.
+-- weekday vai de 0 (domingo) a 6 (sábado); end_time <= start_time indica um intervalo que passa da meia-noite.
+-- Os horários são avaliados no fuso do restaurante (RESTAURANT_TIMEZONE, padrão America/Sao_Paulo).
+CREATE TABLE IF NOT EXISTS tbl_schedule_range (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER REFERENCES tbl_product (id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES tbl_category (id) ON DELETE CASCADE,
+    weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 0 AND 6),
+    start_time TIME NOT NULL,
+    end_time TIME NOT NULL,
+    CHECK (num_nonnulls(product_id, category_id) = 1)
+);
+
+CREATE INDEX IF NOT EXISTS idx_schedule_range_product ON tbl_schedule_range (product_id);
+CREATE INDEX IF NOT EXISTS idx_schedule_range_category ON tbl_schedule_range (category_id);
+
+-- Exceções por data, que substituem os intervalos da semana naquele dia
+CREATE TABLE IF NOT EXISTS tbl_schedule_exception (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER REFERENCES tbl_product (id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES tbl_category (id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    available BOOLEAN NOT NULL,
+    CHECK (num_nonnulls(product_id, category_id) = 1)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_schedule_exception_product ON tbl_schedule_exception (product_id, date) WHERE product_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_schedule_exception_category ON tbl_schedule_exception (category_id, date) WHERE category_id IS NOT NULL;
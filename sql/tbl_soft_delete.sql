@@ -0,0 +1,14 @@
+-- Exclusão lógica de produtos e usuários. As linhas excluídas ficam ocultas e podem ser restauradas
+-- por um administrador até serem removidas pela tarefa 'purge_deleted', após o prazo de retenção.
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+CREATE INDEX IF NOT EXISTS idx_product_deleted ON tbl_product (deleted_at) WHERE deleted_at IS NOT NULL;
+
+ALTER TABLE tbl_user ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMP;
+-- Usuários com pedidos não são removidos: os dados pessoais são apagados e a linha é mantida para os pedidos
+ALTER TABLE tbl_user ADD COLUMN IF NOT EXISTS purged_at TIMESTAMP;
+CREATE INDEX IF NOT EXISTS idx_user_deleted ON tbl_user (deleted_at) WHERE deleted_at IS NOT NULL;
+
+-- Restaurações registradas no histórico do cardápio
+ALTER TABLE tbl_menu_audit DROP CONSTRAINT IF EXISTS tbl_menu_audit_action_check;
+ALTER TABLE tbl_menu_audit ADD CONSTRAINT tbl_menu_audit_action_check
+    CHECK (action IN ('create', 'update', 'delete', 'restore'));
@@ -0,0 +1,35 @@
+-- Estoque de produtos vendidos em unidades. NULL indica que o estoque do produto não é controlado.
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS stock INTEGER NULL CHECK (stock >= 0);
+ALTER TABLE tbl_product ADD COLUMN IF NOT EXISTS low_stock_threshold INTEGER NOT NULL DEFAULT 0;
+
+CREATE TABLE IF NOT EXISTS tbl_ingredient (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(50) NOT NULL UNIQUE,
+    unit VARCHAR(10) NOT NULL,
+    stock DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (stock >= 0),
+    low_stock_threshold DOUBLE PRECISION NOT NULL DEFAULT 0
+);
+
+-- Quantidade de cada ingrediente usada em uma unidade do produto
+CREATE TABLE IF NOT EXISTS tbl_recipe (
+    product_id INTEGER NOT NULL REFERENCES tbl_product (id) ON DELETE CASCADE,
+    ingredient_id INTEGER NOT NULL REFERENCES tbl_ingredient (id) ON DELETE CASCADE,
+    amount DOUBLE PRECISION NOT NULL CHECK (amount > 0),
+    PRIMARY KEY (product_id, ingredient_id)
+);
+
+-- Histórico de alterações de estoque. O nome é copiado para o registro continuar legível após exclusões.
+CREATE TABLE IF NOT EXISTS tbl_stock_movement (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER REFERENCES tbl_product (id) ON DELETE SET NULL,
+    ingredient_id INTEGER REFERENCES tbl_ingredient (id) ON DELETE SET NULL,
+    name VARCHAR(50) NOT NULL,
+    delta DOUBLE PRECISION NOT NULL,
+    reason VARCHAR(20) NOT NULL CHECK (reason IN ('restock', 'waste', 'correction', 'order', 'cancellation')),
+    note VARCHAR(255) NOT NULL DEFAULT '',
+    id_purchase INTEGER,
+    idtbl_user INTEGER REFERENCES tbl_user (id) ON DELETE SET NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_stock_movement_purchase ON tbl_stock_movement (id_purchase);
@@ -0,0 +1,34 @@
+-- Configurações do restaurante. A pausa manual termina sozinha em resume_at, no fuso do restaurante.
+CREATE TABLE IF NOT EXISTS tbl_store_settings (
+    id INTEGER PRIMARY KEY CHECK (id = 1),
+    paused BOOLEAN NOT NULL DEFAULT false,
+    pause_reason VARCHAR(255) NOT NULL DEFAULT '',
+    resume_at TIMESTAMP
+);
+
+INSERT INTO tbl_store_settings (id) VALUES (1) ON CONFLICT (id) DO NOTHING;
+
+-- Horário de funcionamento. weekday vai de 0 (domingo) a 6 (sábado);
+-- close_time <= open_time indica um intervalo que passa da meia-noite.
+-- Sem linhas cadastradas o restaurante é considerado sempre aberto.
+CREATE TABLE IF NOT EXISTS tbl_opening_hours (
+    id SERIAL PRIMARY KEY,
+    weekday SMALLINT NOT NULL CHECK (weekday BETWEEN 0 AND 6),
+    open_time TIME NOT NULL,
+    close_time TIME NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS tbl_holiday (
+    date DATE PRIMARY KEY,
+    description VARCHAR(100) NOT NULL DEFAULT ''
+);
+
+-- Pedidos agendados: horário pedido no fuso do restaurante e regras de capacidade da cozinha
+ALTER TABLE tbl_order ADD COLUMN IF NOT EXISTS scheduled_for TIMESTAMP;
+CREATE INDEX IF NOT EXISTS idx_order_scheduled_for ON tbl_order (scheduled_for) WHERE scheduled_for IS NOT NULL;
+
+ALTER TABLE tbl_store_settings ADD COLUMN IF NOT EXISTS schedule_slot_minutes INTEGER NOT NULL DEFAULT 15;
+ALTER TABLE tbl_store_settings ADD COLUMN IF NOT EXISTS schedule_slot_capacity INTEGER NOT NULL DEFAULT 10;
+ALTER TABLE tbl_store_settings ADD COLUMN IF NOT EXISTS schedule_lead_minutes INTEGER NOT NULL DEFAULT 45;
+ALTER TABLE tbl_store_settings ADD COLUMN IF NOT EXISTS schedule_min_advance_minutes INTEGER NOT NULL DEFAULT 60;
+ALTER TABLE tbl_store_settings ADD COLUMN IF NOT EXISTS schedule_max_days_ahead INTEGER NOT NULL DEFAULT 7;
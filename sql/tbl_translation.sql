@@ -0,0 +1,14 @@
+-- Traduções do nome e da descrição do cardápio. Cada linha pertence a um produto ou a uma categoria.
+-- O conteúdo em português fica no próprio item; 'locale' é o idioma em minúsculas, como 'en' ou 'es-ar'.
+CREATE TABLE IF NOT EXISTS tbl_translation (
+    id SERIAL PRIMARY KEY,
+    product_id INTEGER REFERENCES tbl_product (id) ON DELETE CASCADE,
+    category_id INTEGER REFERENCES tbl_category (id) ON DELETE CASCADE,
+    locale VARCHAR(35) NOT NULL,
+    name TEXT NOT NULL,
+    description TEXT,
+    CHECK (num_nonnulls(product_id, category_id) = 1)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_translation_product ON tbl_translation (product_id, locale) WHERE product_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_translation_category ON tbl_translation (category_id, locale) WHERE category_id IS NOT NULL;
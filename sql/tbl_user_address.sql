@@ -0,0 +1,32 @@
+CREATE TABLE IF NOT EXISTS tbl_user_address (
+    id SERIAL PRIMARY KEY,
+    idtbl_user INTEGER NOT NULL REFERENCES tbl_user (id) ON DELETE CASCADE,
+    label VARCHAR(30) NOT NULL DEFAULT '',
+    street VARCHAR(255) NOT NULL,
+    number VARCHAR(10) NOT NULL,
+    complement VARCHAR(100) NOT NULL DEFAULT '',
+    neighborhood VARCHAR(100) NOT NULL,
+    city VARCHAR(100) NOT NULL,
+    cep CHAR(8) NOT NULL,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION,
+    is_default BOOLEAN NOT NULL DEFAULT false
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_address_default
+    ON tbl_user_address (idtbl_user) WHERE is_default;
+
+-- Cópia do endereço de entrega no momento do pedido
+CREATE TABLE IF NOT EXISTS tbl_order_address (
+    id_purchase INTEGER PRIMARY KEY,
+    idtbl_user INTEGER NOT NULL,
+    label VARCHAR(30) NOT NULL DEFAULT '',
+    street VARCHAR(255) NOT NULL,
+    number VARCHAR(10) NOT NULL,
+    complement VARCHAR(100) NOT NULL DEFAULT '',
+    neighborhood VARCHAR(100) NOT NULL,
+    city VARCHAR(100) NOT NULL,
+    cep CHAR(8) NOT NULL,
+    latitude DOUBLE PRECISION,
+    longitude DOUBLE PRECISION
+);
@@ -0,0 +1,14 @@
+CREATE TABLE IF NOT EXISTS tbl_user_payment_method (
+    id SERIAL PRIMARY KEY,
+    idtbl_user INTEGER NOT NULL REFERENCES tbl_user (id) ON DELETE CASCADE,
+    kind VARCHAR(10) NOT NULL CHECK (kind IN ('cash', 'card', 'pix')),
+    value VARCHAR(255) NOT NULL DEFAULT '',
+    label VARCHAR(50) NOT NULL DEFAULT '',
+    is_default BOOLEAN NOT NULL DEFAULT false
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_payment_method_unique
+    ON tbl_user_payment_method (idtbl_user, kind, value);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_user_payment_method_default
+    ON tbl_user_payment_method (idtbl_user) WHERE is_default;
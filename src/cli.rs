@@ -0,0 +1,90 @@
+use std::fs;
+use std::io::{self, Write};
+
+use crate::data::catalog::DbCatalog;
+use crate::models::catalog::{Catalog, CatalogFormat};
+
+const USAGE: &str = "Uso:
+  backend catalog export [--format csv|json] [--output ARQUIVO]
+  backend catalog import ARQUIVO [--format csv|json] [--apply]";
+
+// Comandos de linha de comando, executados no lugar do servidor quando o programa recebe argumentos
+pub async fn run(args: Vec<String>) -> Result<(), String> {
+    match args.first().map(|arg| arg.as_str()) {
+        Some("catalog") => catalog(&args[1..]).await,
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+// Exporta o cardápio para a saída padrão ou um arquivo, ou importa um arquivo do cardápio.
+// A importação apenas mostra as alterações, a menos que receba '--apply'.
+async fn catalog(args: &[String]) -> Result<(), String> {
+    let mut format = None;
+    let mut output = None;
+    let mut file = None;
+    let mut apply = false;
+
+    let mut options = args.iter().skip(1);
+    while let Some(option) = options.next() {
+        match option.as_str() {
+            "--format" => format = Some(CatalogFormat::parse(next_value(&mut options, option)?)?),
+            "--output" => output = Some(next_value(&mut options, option)?.to_string()),
+            "--apply" => apply = true,
+            value if !value.starts_with("--") && file.is_none() => file = Some(value.to_string()),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+
+    match args.first().map(|arg| arg.as_str()) {
+        Some("export") if file.is_none() && !apply => {
+            let format = format.unwrap_or(CatalogFormat::Json);
+            let data = DbCatalog::export()
+                .await
+                .map_err(|e| e.to_string())?
+                .write(format)?;
+            match output {
+                Some(output) => {
+                    fs::write(&output, data).map_err(|e| format!("Failed to write file: {}", e))
+                }
+                None => io::stdout()
+                    .write_all(&data)
+                    .map_err(|e| format!("Failed to write catalog: {}", e)),
+            }
+        }
+        Some("import") if output.is_none() => {
+            let file = file.ok_or_else(|| USAGE.to_string())?;
+            // Sem '--format' o formato vem da extensão do arquivo
+            let format = match format {
+                Some(format) => format,
+                None => CatalogFormat::parse(file.rsplit('.').next().unwrap_or_default())?,
+            };
+            let data = fs::read(&file).map_err(|e| format!("Failed to read file: {}", e))?;
+
+            let (catalog, errors) = Catalog::parse(format, &data)?;
+            let result = DbCatalog::import(&catalog, errors, !apply, None)
+                .await
+                .map_err(|e| e.to_string())?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&result)
+                    .map_err(|e| format!("Failed to write result: {}", e))?
+            );
+
+            match result.has_errors() {
+                true => Err(String::from("Catalog has errors, nothing was imported")),
+                false => Ok(()),
+            }
+        }
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+fn next_value<'a>(
+    options: &mut impl Iterator<Item = &'a String>,
+    option: &str,
+) -> Result<&'a str, String> {
+    options
+        .next()
+        .map(|value| value.as_str())
+        .ok_or_else(|| format!("Missing value for '{}'", option))
+}
@@ -1,37 +1,216 @@
+use crate::routes::address::{
+    create as addr_create, delete as addr_delete, read as addr_read, update as addr_update,
+};
+use crate::routes::archive::{
+    read_products as archive_read_products, read_users as archive_read_users,
+    restore_product as archive_restore_product, restore_user as archive_restore_user,
+};
 use crate::routes::auth::{check as user_check, login as user_login, logout as user_logout};
+use crate::routes::catalog::{export as catalog_export, import as catalog_import};
+use crate::routes::category::{
+    create as cat_create, delete as cat_delete, menu, read as cat_read,
+    read_all as cat_read_all, read_available as cat_read_available, update as cat_update,
+};
+use crate::routes::courier::{
+    create as courier_create, delete as courier_delete, read as courier_read,
+    read_deliveries as courier_read_deliveries, update_available as courier_update_available,
+    update_location as courier_update_location,
+};
+use crate::routes::delivery::{
+    assign as delivery_assign, auto_assign as delivery_auto_assign,
+    confirm as delivery_confirm, track as delivery_track,
+};
+use crate::routes::delivery_zone::{
+    create as zone_create, delete as zone_delete, quote as zone_quote, read as zone_read,
+    read_all as zone_read_all, update as zone_update,
+};
 use crate::routes::history::{
-    create as hist_create, read as hist_read, read_all, update as hist_update,
+    create as hist_create, read as hist_read, read_address as hist_read_address, read_all,
+    read_total as hist_read_total, update as hist_update,
+    update_legacy as hist_update_legacy,
+};
+use crate::routes::image::upload as image_upload;
+use crate::routes::loyalty::{
+    audit as loyalty_audit, read as loyalty_read, read_settings as loyalty_read_settings,
+    update_settings as loyalty_update_settings,
+};
+use crate::routes::menu_audit::{
+    read as audit_read, read_price_at as audit_read_price_at, read_prices as audit_read_prices,
+};
+use crate::routes::payment_method::{
+    create as pay_create, delete as pay_delete, read as pay_read, update as pay_update,
 };
 use crate::routes::product::{
-    create as prod_create, delete as prod_delete, read as prod_read,
-    read_in_range as prod_read_in_range, update as prod_update,
+    create as prod_create, delete as prod_delete, list as prod_list, read as prod_read,
+    read_in_range as prod_read_in_range, search as prod_search, update as prod_update,
+};
+use crate::routes::promotion::{
+    create as promo_create, delete as promo_delete, read as promo_read,
+    read_all as promo_read_all, update as promo_update,
+};
+use crate::routes::schedule::{
+    read_category as schedule_read_category, read_product as schedule_read_product,
+    update_category as schedule_update_category, update_product as schedule_update_product,
+};
+use crate::routes::stock::{
+    adjust as stock_adjust, alerts as stock_alerts, create_ingredient as stock_create_ingredient,
+    delete_ingredient as stock_delete_ingredient, read as stock_read,
+    read_ingredients as stock_read_ingredients, read_log as stock_read_log,
+    read_recipe as stock_read_recipe, update_ingredient as stock_update_ingredient,
+    update_recipe as stock_update_recipe, update_settings as stock_update_settings,
+};
+use crate::routes::store::{
+    pause as store_pause, read_hours as store_read_hours,
+    read_schedule_settings as store_read_schedule_settings, read_slots as store_read_slots,
+    resume as store_resume, status as store_status, update_hours as store_update_hours,
+    update_schedule_settings as store_update_schedule_settings,
+};
+use crate::routes::translation::{
+    read_category as translation_read_category, read_product as translation_read_product,
+    update_category as translation_update_category, update_product as translation_update_product,
 };
 use crate::routes::user::{
     create as user_create, delete as user_delete, get_admin, read as user_read, send_recovery_code,
     update as user_update, change_password,
 };
 
+use crate::models::api_error::ApiError;
+
 use actix_files::Files;
-use actix_web::web;
+use actix_web::{web, ResponseError};
 
 pub fn config_routes(cfg: &mut web::ServiceConfig) {
     cfg
+        // Erros de leitura dos dados enviados e rotas inexistentes no mesmo formato JSON das demais respostas de erro
+        .app_data(
+            web::JsonConfig::default()
+                .error_handler(|e, _| ApiError::validation(e.to_string()).into()),
+        )
+        .app_data(
+            web::QueryConfig::default()
+                .error_handler(|e, _| ApiError::validation(e.to_string()).into()),
+        )
+        .app_data(
+            web::PathConfig::default()
+                .error_handler(|e, _| ApiError::validation(e.to_string()).into()),
+        )
+        .default_service(web::to(|| async {
+            ApiError::NotFound(String::new()).error_response()
+        }))
         // Historic
         .service(hist_create)
         .service(hist_read)
         .service(hist_update)
+        .service(hist_update_legacy)
+        .service(hist_read_address)
+        .service(hist_read_total)
         .service(read_all)
         // Product
         .service(prod_create)
         .service(prod_read)
         .service(prod_read_in_range)
+        .service(prod_list)
+        .service(prod_search)
         .service(prod_update)
         .service(prod_delete)
+        // Image
+        .service(image_upload)
+        // Category
+        .service(cat_create)
+        .service(cat_read)
+        .service(cat_read_available)
+        .service(cat_read_all)
+        .service(cat_update)
+        .service(cat_delete)
+        .service(menu)
+        // Menu audit
+        .service(audit_read)
+        .service(audit_read_prices)
+        .service(audit_read_price_at)
+        // Schedule
+        .service(schedule_read_product)
+        .service(schedule_read_category)
+        .service(schedule_update_product)
+        .service(schedule_update_category)
+        // Translation
+        .service(translation_read_product)
+        .service(translation_read_category)
+        .service(translation_update_product)
+        .service(translation_update_category)
+        // Catalog
+        .service(catalog_export)
+        .service(catalog_import)
+        // Promotion
+        .service(promo_create)
+        .service(promo_read)
+        .service(promo_read_all)
+        .service(promo_update)
+        .service(promo_delete)
         // User
         .service(user_create)
         .service(user_read)
         .service(user_update)
         .service(user_delete)
+        // Archive
+        .service(archive_read_products)
+        .service(archive_restore_product)
+        .service(archive_read_users)
+        .service(archive_restore_user)
+        // Address
+        .service(addr_create)
+        .service(addr_read)
+        .service(addr_update)
+        .service(addr_delete)
+        // Delivery zone
+        .service(zone_create)
+        .service(zone_read)
+        .service(zone_read_all)
+        .service(zone_quote)
+        .service(zone_update)
+        .service(zone_delete)
+        // Courier
+        .service(courier_create)
+        .service(courier_read)
+        .service(courier_delete)
+        .service(courier_update_available)
+        .service(courier_update_location)
+        .service(courier_read_deliveries)
+        // Delivery
+        .service(delivery_assign)
+        .service(delivery_auto_assign)
+        .service(delivery_confirm)
+        .service(delivery_track)
+        // Loyalty
+        .service(loyalty_read)
+        .service(loyalty_read_settings)
+        .service(loyalty_update_settings)
+        .service(loyalty_audit)
+        // Stock
+        .service(stock_update_settings)
+        .service(stock_read)
+        .service(stock_alerts)
+        .service(stock_adjust)
+        .service(stock_read_log)
+        .service(stock_read_recipe)
+        .service(stock_update_recipe)
+        .service(stock_create_ingredient)
+        .service(stock_read_ingredients)
+        .service(stock_update_ingredient)
+        .service(stock_delete_ingredient)
+        // Store
+        .service(store_status)
+        .service(store_read_hours)
+        .service(store_update_hours)
+        .service(store_pause)
+        .service(store_resume)
+        .service(store_read_slots)
+        .service(store_read_schedule_settings)
+        .service(store_update_schedule_settings)
+        // Payment method
+        .service(pay_create)
+        .service(pay_read)
+        .service(pay_update)
+        .service(pay_delete)
         // Authenticate
         .service(user_login)
         .service(user_check)
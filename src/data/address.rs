@@ -0,0 +1,265 @@
+use tokio_postgres::{GenericClient, Row};
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::address::Address;
+
+pub struct DbAddress {}
+
+impl DbAddress {
+    pub async fn create(user_id: i32, address: Address) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        // O primeiro endereço cadastrado se torna o padrão
+        let has_addresses: bool = transaction
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM tbl_user_address WHERE idtbl_user = $1)",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+        let is_default = address.is_default() || !has_addresses;
+
+        if is_default {
+            Self::clear_default(&transaction, user_id).await?;
+        }
+
+        let query = "INSERT INTO tbl_user_address (idtbl_user, label, street, number, complement, neighborhood, city, cep, latitude, longitude, is_default)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+
+        transaction
+            .execute(
+                query,
+                &[
+                    &user_id,
+                    &address.label(),
+                    &address.street(),
+                    &address.number(),
+                    &address.complement(),
+                    &address.neighborhood(),
+                    &address.city(),
+                    &address.cep(),
+                    &address.latitude(),
+                    &address.longitude(),
+                    &is_default,
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read(user_id: i32) -> Result<Vec<Address>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, label, street, number, complement, neighborhood, city, cep, latitude, longitude, is_default FROM tbl_user_address WHERE idtbl_user = $1 ORDER BY is_default DESC, id";
+        let mut addresses = Vec::new();
+
+        for row in client
+            .query(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            addresses.push(Self::from_row(&row)?);
+        }
+
+        Ok(addresses)
+    }
+
+    // Busca o endereço escolhido pelo usuário ou, se nenhum for informado, o endereço padrão.
+    // Sem 'id' retorna o endereço padrão, ou None se o usuário não tem endereços cadastrados
+    pub async fn read_for_checkout(
+        user_id: i32,
+        id: Option<i32>,
+    ) -> Result<Option<Address>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, label, street, number, complement, neighborhood, city, cep, latitude, longitude, is_default FROM tbl_user_address
+        WHERE idtbl_user = $1 AND (id = $2 OR ($2 IS NULL AND is_default))";
+
+        match client
+            .query_opt(query, &[&user_id, &id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            Some(row) => Ok(Some(Self::from_row(&row)?)),
+            None if id.is_none() => Ok(None),
+            None => Err(DataError::Rule(
+                "Endereço de entrega não encontrado".to_string(),
+            )),
+        }
+    }
+
+    pub async fn update(id: i32, user_id: i32, address: Address) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        if address.is_default() {
+            Self::clear_default(&transaction, user_id).await?;
+        }
+
+        let query = "UPDATE tbl_user_address SET label = $3, street = $4, number = $5, complement = $6, neighborhood = $7, city = $8, cep = $9, latitude = $10, longitude = $11, is_default = $12
+        WHERE id = $1 AND idtbl_user = $2";
+
+        let rows_affected = transaction
+            .execute(
+                query,
+                &[
+                    &id,
+                    &user_id,
+                    &address.label(),
+                    &address.street(),
+                    &address.number(),
+                    &address.complement(),
+                    &address.neighborhood(),
+                    &address.city(),
+                    &address.cep(),
+                    &address.latitude(),
+                    &address.longitude(),
+                    &address.is_default(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Address not found.".to_string()));
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn delete(id: i32, user_id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "DELETE FROM tbl_user_address WHERE id = $1 AND idtbl_user = $2";
+
+        let rows_affected = client
+            .execute(query, &[&id, &user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Address not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Grava uma cópia do endereço no pedido, para que alterações futuras no cadastro não mudem pedidos antigos.
+    pub async fn create_snapshot<C: GenericClient>(
+        client: &C,
+        id_purchase: i32,
+        user_id: i32,
+        address: &Address,
+    ) -> Result<(), DataError> {
+        let query = "INSERT INTO tbl_order_address (id_purchase, idtbl_user, label, street, number, complement, neighborhood, city, cep, latitude, longitude)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+
+        client
+            .execute(
+                query,
+                &[
+                    &id_purchase,
+                    &user_id,
+                    &address.label(),
+                    &address.street(),
+                    &address.number(),
+                    &address.complement(),
+                    &address.neighborhood(),
+                    &address.city(),
+                    &address.cep(),
+                    &address.latitude(),
+                    &address.longitude(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_snapshot(id_purchase: i32) -> Result<(i32, Address), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT NULL::INTEGER AS id, idtbl_user, label, street, number, complement, neighborhood, city, cep, latitude, longitude, false AS is_default
+        FROM tbl_order_address WHERE id_purchase = $1";
+
+        let row = client
+            .query_opt(query, &[&id_purchase])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("Address not found.".to_string()))?;
+
+        // Pedidos feitos com o endereço em texto do perfil não têm CEP
+        let cep: String = row.get("cep");
+        let address = match cep.trim().is_empty() {
+            true => Address::legacy(row.get("street")),
+            false => Self::from_row(&row)?,
+        };
+
+        Ok((row.get("idtbl_user"), address))
+    }
+
+    async fn clear_default<C: GenericClient>(client: &C, user_id: i32) -> Result<(), DataError> {
+        client
+            .execute(
+                "UPDATE tbl_user_address SET is_default = false WHERE idtbl_user = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn from_row(row: &Row) -> Result<Address, DataError> {
+        Address::new(
+            row.get("id"),
+            row.get("label"),
+            row.get("street"),
+            row.get("number"),
+            row.get("complement"),
+            row.get("neighborhood"),
+            row.get("city"),
+            row.get("cep"),
+            row.get("latitude"),
+            row.get("longitude"),
+            row.get("is_default"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+}
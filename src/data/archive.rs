@@ -0,0 +1,121 @@
+use super::database::Db;
+use super::error::DataError;
+use super::schedule::DbSchedule;
+use crate::models::archive::ArchivedItem;
+
+pub struct DbArchive {}
+
+impl DbArchive {
+    // Produtos excluídos, dos mais recentes aos mais antigos
+    pub async fn read_products(retention_days: i32) -> Result<Vec<ArchivedItem>, DataError> {
+        let query = "SELECT id, name, to_char(deleted_at, 'YYYY-MM-DD HH24:MI:SS') AS deleted_at,
+        to_char(deleted_at + make_interval(days => $1), 'YYYY-MM-DD HH24:MI:SS') AS purge_at
+        FROM tbl_product WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC, id";
+
+        Self::read(query, retention_days).await
+    }
+
+    // Usuários excluídos que ainda podem ser restaurados, dos mais recentes aos mais antigos
+    pub async fn read_users(retention_days: i32) -> Result<Vec<ArchivedItem>, DataError> {
+        let query = "SELECT id, username AS name, to_char(deleted_at, 'YYYY-MM-DD HH24:MI:SS') AS deleted_at,
+        to_char(deleted_at + make_interval(days => $1), 'YYYY-MM-DD HH24:MI:SS') AS purge_at
+        FROM tbl_user WHERE deleted_at IS NOT NULL AND purged_at IS NULL ORDER BY deleted_at DESC, id";
+
+        Self::read(query, retention_days).await
+    }
+
+    async fn read(query: &str, retention_days: i32) -> Result<Vec<ArchivedItem>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Ok(client
+            .query(query, &[&retention_days])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .iter()
+            .map(|row| {
+                ArchivedItem::new(
+                    row.get("id"),
+                    row.get("name"),
+                    row.get("deleted_at"),
+                    row.get("purge_at"),
+                )
+            })
+            .collect())
+    }
+
+    // Remove definitivamente os produtos e usuários excluídos há mais de 'retention_days' dias.
+    // Usuários com pedidos ou entregas têm os dados pessoais apagados, mas a linha é mantida para que
+    // o histórico continue consistente. Retorna a quantidade de produtos e usuários removidos.
+    pub async fn purge(retention_days: i32) -> Result<u64, DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let expired = format!(
+            "deleted_at < {} - make_interval(days => $1)",
+            DbSchedule::local_now()
+        );
+
+        // Os itens dos pedidos guardam uma cópia dos dados do produto e não dependem dele
+        let products_query = format!("DELETE FROM tbl_product WHERE {}", expired);
+
+        let products = transaction
+            .execute(products_query.as_str(), &[&retention_days])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let anonymize_query = format!(
+            "UPDATE tbl_user u SET name = '', username = 'deleted-' || u.id, password = '', email = 'deleted-' || u.id,
+            phone = '', address = '', purged_at = {}
+            WHERE u.{} AND u.purged_at IS NULL
+            AND (EXISTS (SELECT 1 FROM tbl_cart_item i WHERE i.idtbl_user = u.id)
+            OR EXISTS (SELECT 1 FROM tbl_order o WHERE o.idtbl_user = u.id)
+            OR EXISTS (SELECT 1 FROM tbl_delivery d WHERE d.id_courier = u.id))
+            RETURNING u.id",
+            DbSchedule::local_now(),
+            expired
+        );
+
+        let anonymized: Vec<i32> = transaction
+            .query(anonymize_query.as_str(), &[&retention_days])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .iter()
+            .map(|row| row.get("id"))
+            .collect();
+
+        for query in [
+            "DELETE FROM tbl_user_address WHERE idtbl_user = ANY($1)",
+            "DELETE FROM tbl_user_payment_method WHERE idtbl_user = ANY($1)",
+        ] {
+            transaction
+                .execute(query, &[&anonymized])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        let users_query = format!(
+            "DELETE FROM tbl_user WHERE {} AND purged_at IS NULL",
+            expired
+        );
+
+        let users = transaction
+            .execute(users_query.as_str(), &[&retention_days])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(products + anonymized.len() as u64 + users)
+    }
+}
@@ -0,0 +1,81 @@
+use actix_web::web;
+use async_trait::async_trait;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+
+use super::error::DataError;
+
+
+// Armazenamento de arquivos enviados. As chaves são caminhos relativos, como 'uploads/abc/large.jpg'.
+#[async_trait(?Send)]
+pub trait BlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, content_type: &str) -> Result<(), DataError>;
+    async fn delete(&self, key: &str) -> Result<(), DataError>;
+    // Endereço público do arquivo
+    fn url(&self, key: &str) -> String;
+}
+
+// Grava os arquivos numa pasta local, servida pelo próprio servidor.
+pub struct LocalBlobStore {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: PathBuf, base_url: String) -> LocalBlobStore {
+        LocalBlobStore {
+            root,
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    // Usa a pasta de imagens servida em '/images', a menos que IMAGE_STORAGE_DIR e IMAGE_BASE_URL sejam definidas.
+    pub fn from_env() -> LocalBlobStore {
+        LocalBlobStore::new(
+            PathBuf::from(env::var("IMAGE_STORAGE_DIR").unwrap_or_else(|_| String::from("./src/images/"))),
+            env::var("IMAGE_BASE_URL").unwrap_or_else(|_| String::from("/images")),
+        )
+    }
+
+    // Recusa chaves que sairiam da pasta raiz
+    fn path(&self, key: &str) -> Result<PathBuf, DataError> {
+        let relative = Path::new(key);
+        if key.is_empty() || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            return Err(DataError::Db(format!("Invalid blob key '{}'", key)));
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+#[async_trait(?Send)]
+impl BlobStore for LocalBlobStore {
+    async fn put(&self, key: &str, bytes: Vec<u8>, _content_type: &str) -> Result<(), DataError> {
+        let path = self.path(key)?;
+
+        web::block(move || {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, bytes)
+        })
+        .await
+        .map_err(|e| DataError::Db(format!("Failed to store file: {}", e)))?
+        .map_err(|e| DataError::Db(format!("Failed to store file: {}", e)))
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), DataError> {
+        let path = self.path(key)?;
+
+        web::block(move || match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        })
+        .await
+        .map_err(|e| DataError::Db(format!("Failed to delete file: {}", e)))?
+        .map_err(|e| DataError::Db(format!("Failed to delete file: {}", e)))
+    }
+
+    fn url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+}
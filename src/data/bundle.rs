@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use tokio_postgres::GenericClient;
+
+use super::error::DataError;
+use super::schedule::DbSchedule;
+use super::stock::DbStock;
+use crate::models::bundle::{select_components, BundleComponent, BundleSlot};
+
+pub struct DbBundle {}
+
+impl DbBundle {
+    // Condição SQL que é verdadeira quando todas as partes do combo têm ao menos um produto disponível,
+    // em estoque e dentro do horário.
+    // Produtos que não são combos não têm partes e sempre satisfazem a condição.
+    pub fn components_available(product: &str) -> String {
+        format!(
+            "NOT EXISTS (SELECT 1 FROM tbl_bundle_slot s WHERE s.product_id = {}.id AND NOT EXISTS (
+            SELECT 1 FROM tbl_bundle_slot_product sp JOIN tbl_product c ON c.id = sp.product_id
+            WHERE sp.slot_id = s.id AND c.deleted_at IS NULL AND c.available AND {} AND {}))",
+            product,
+            DbStock::in_stock("c"),
+            DbSchedule::product_on_schedule("c")
+        )
+    }
+
+    // Busca as partes dos combos informados, agrupadas pelo id do combo.
+    pub async fn read_with<C: GenericClient>(
+        client: &C,
+        product_ids: &[i32],
+    ) -> Result<BTreeMap<i32, Vec<BundleSlot>>, DataError> {
+        let query = "SELECT s.product_id, s.id, s.name, s.quantity, ARRAY_AGG(sp.product_id ORDER BY sp.position) AS product_ids
+        FROM tbl_bundle_slot s JOIN tbl_bundle_slot_product sp ON sp.slot_id = s.id
+        WHERE s.product_id = ANY($1) GROUP BY s.product_id, s.id ORDER BY s.product_id, s.position, s.id";
+
+        let mut slots: BTreeMap<i32, Vec<BundleSlot>> = BTreeMap::new();
+
+        for row in client
+            .query(query, &[&product_ids])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let slot = BundleSlot::new(
+                row.get("id"),
+                row.get("name"),
+                row.get("quantity"),
+                row.get("product_ids"),
+            )
+            .map_err(DataError::invalid_row)?;
+            slots.entry(row.get("product_id")).or_default().push(slot);
+        }
+
+        Ok(slots)
+    }
+
+    // Substitui as partes do combo. Um combo não pode conter a si mesmo nem outros combos.
+    pub async fn replace_all<C: GenericClient>(
+        client: &C,
+        product_id: i32,
+        slots: &[BundleSlot],
+    ) -> Result<(), DataError> {
+        client
+            .execute(
+                "DELETE FROM tbl_bundle_slot WHERE product_id = $1",
+                &[&product_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if slots.is_empty() {
+            return Ok(());
+        }
+
+        let is_component: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM tbl_bundle_slot_product WHERE product_id = $1)",
+                &[&product_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+        if is_component {
+            return Err(DataError::Rule(
+                "A product used in a bundle cannot be a bundle".to_string(),
+            ));
+        }
+
+        let slot_query = "INSERT INTO tbl_bundle_slot (id, product_id, name, quantity, position)
+        VALUES (COALESCE($1, nextval('tbl_bundle_slot_id_seq')::INTEGER), $2, $3, $4, $5) RETURNING id";
+        let product_query =
+            "INSERT INTO tbl_bundle_slot_product (slot_id, product_id, position) VALUES ($1, $2, $3)";
+
+        for (position, slot) in slots.iter().enumerate() {
+            let position = position as i32;
+            let slot_id: i32 = client
+                .query_one(
+                    slot_query,
+                    &[
+                        &slot.id(),
+                        &product_id,
+                        &slot.name(),
+                        &slot.quantity(),
+                        &position,
+                    ],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+                .get("id");
+
+            for (position, component_id) in slot.product_ids().iter().enumerate() {
+                let position = position as i32;
+                Self::validate_component(client, product_id, *component_id).await?;
+
+                client
+                    .execute(product_query, &[&slot_id, component_id, &position])
+                    .await
+                    .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Confere as escolhas do cliente e completa os componentes com os nomes dos produtos.
+    pub async fn expand<C: GenericClient>(
+        client: &C,
+        slots: &[BundleSlot],
+        choices: &[BundleComponent],
+    ) -> Result<Vec<BundleComponent>, DataError> {
+        let mut components = select_components(slots, choices).map_err(DataError::Rule)?;
+
+        for component in components.iter_mut() {
+            let query = format!(
+                "SELECT name, deleted_at IS NULL AND available AND {} AND {} AS available FROM tbl_product WHERE id = $1",
+                DbStock::in_stock("tbl_product"),
+                DbSchedule::product_on_schedule("tbl_product")
+            );
+            let row = client
+                .query_one(query.as_str(), &[&component.product_id()])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+            let name: String = row.get("name");
+            let available: bool = row.get("available");
+            if !available {
+                return Err(DataError::Rule(format!("'{}' indisponível", name)));
+            }
+            component.set_name(name);
+        }
+
+        Ok(components)
+    }
+
+    async fn validate_component<C: GenericClient>(
+        client: &C,
+        product_id: i32,
+        component_id: i32,
+    ) -> Result<(), DataError> {
+        if component_id == product_id {
+            return Err(DataError::Rule(
+                "A bundle cannot contain itself".to_string(),
+            ));
+        }
+
+        let row = client
+            .query_opt(
+                "SELECT EXISTS (SELECT 1 FROM tbl_bundle_slot s WHERE s.product_id = p.id) AS is_bundle FROM tbl_product p WHERE p.id = $1 AND p.deleted_at IS NULL",
+                &[&component_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        match row {
+            Some(row) if row.get::<_, bool>("is_bundle") => Err(DataError::Rule(format!(
+                "Product {} is a bundle and cannot be part of another bundle",
+                component_id
+            ))),
+            Some(_) => Ok(()),
+            None => Err(DataError::Rule(format!(
+                "Product {} not found",
+                component_id
+            ))),
+        }
+    }
+}
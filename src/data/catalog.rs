@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use tokio_postgres::GenericClient;
+
+use super::category::DbCategory;
+use super::database::Db;
+use super::error::DataError;
+use super::product::DbProduct;
+use crate::models::{
+    catalog::{
+        category_changes, product_changes, Catalog, CatalogAction, CatalogChange, CatalogEntity,
+        CatalogError, CatalogImport, CatalogProduct,
+    },
+    category::Category,
+    product::Product,
+};
+
+// Colunas dos produtos com a disponibilidade cadastrada, e não a calculada pelo estoque e horários
+const PRODUCT_COLUMNS: &str = "id, sku, name, price, description, image, available, category_id, allergens, portion_grams, kcal, protein, carbs, fat";
+
+pub struct DbCatalog {}
+
+impl DbCatalog {
+    // Cardápio completo com as categorias e os produtos não excluídos, sem as traduções
+    pub async fn export() -> Result<Catalog, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let categories = Self::read_categories(&client).await?;
+        let category_names: BTreeMap<i32, String> = categories
+            .iter()
+            .filter_map(|category| category.id().map(|id| (id, category.name())))
+            .collect();
+
+        let mut products = Vec::new();
+        for product in Self::read_products(&client).await?.values() {
+            let category = product
+                .category_id()
+                .and_then(|id| category_names.get(&id).cloned());
+            products.push(
+                CatalogProduct::from_product(product, category).map_err(DataError::invalid_row)?,
+            );
+        }
+
+        // Os ids só valem neste banco de dados: as categorias são identificadas pelo nome
+        let categories = categories
+            .into_iter()
+            .map(|category| {
+                Category::new(
+                    None,
+                    category.name(),
+                    category.description(),
+                    category.icon(),
+                    category.position(),
+                    category.available(),
+                )
+                .map_err(DataError::invalid_row)
+            })
+            .collect::<Result<Vec<Category>, DataError>>()?;
+
+        Ok(Catalog::new(categories, products))
+    }
+
+    // Importa o catálogo em uma única transação: as categorias são encontradas pelo nome e os produtos
+    // pelo SKU, sendo atualizados quando existem e criados quando não existem. 'errors' são os erros já
+    // encontrados na leitura do arquivo. Com 'dry_run' ou com algum erro a transação é desfeita e apenas
+    // as alterações que seriam feitas são retornadas.
+    pub async fn import(
+        catalog: &Catalog,
+        mut errors: Vec<CatalogError>,
+        dry_run: bool,
+        user_id: Option<i32>,
+    ) -> Result<CatalogImport, DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let mut changes = Vec::new();
+
+        // Categorias com o mesmo nome no banco de dados: vale a mais antiga
+        let current_categories = Self::read_categories(&transaction).await?;
+        let mut category_ids: BTreeMap<String, i32> = BTreeMap::new();
+        for category in current_categories.iter() {
+            if let Some(id) = category.id() {
+                category_ids.entry(category.name()).or_insert(id);
+            }
+        }
+
+        for category in catalog.categories() {
+            let current = category_ids.get(&category.name()).and_then(|id| {
+                current_categories
+                    .iter()
+                    .find(|current| current.id() == Some(*id))
+            });
+
+            match current {
+                Some(current) => {
+                    let fields = category_changes(current, &category);
+                    let action = match fields.is_empty() {
+                        true => CatalogAction::Unchanged,
+                        false => {
+                            let id = current.id().unwrap_or_default();
+                            DbCategory::update_with(&transaction, id, &category, user_id).await?;
+                            CatalogAction::Update
+                        }
+                    };
+                    changes.push(CatalogChange::new(
+                        CatalogEntity::Category,
+                        category.name(),
+                        action,
+                        fields,
+                    ));
+                }
+                None => {
+                    let id = DbCategory::insert(&transaction, &category, user_id).await?;
+                    category_ids.insert(category.name(), id);
+                    changes.push(CatalogChange::new(
+                        CatalogEntity::Category,
+                        category.name(),
+                        CatalogAction::Create,
+                        Vec::new(),
+                    ));
+                }
+            }
+        }
+
+        let current_products = Self::read_products(&transaction).await?;
+
+        for product in catalog.products() {
+            let category_id = match product.category() {
+                Some(name) => match category_ids.get(&name) {
+                    Some(id) => Some(*id),
+                    None => {
+                        errors.push(CatalogError::new(
+                            product.row(),
+                            CatalogEntity::Product,
+                            product.sku(),
+                            format!("Category '{}' not found", name),
+                        ));
+                        continue;
+                    }
+                },
+                None => None,
+            };
+
+            let current = current_products.get(&product.sku());
+            let new_product = match product.product(category_id, current) {
+                Ok(new_product) => new_product,
+                Err(e) => {
+                    errors.push(CatalogError::new(
+                        product.row(),
+                        CatalogEntity::Product,
+                        product.sku(),
+                        e,
+                    ));
+                    continue;
+                }
+            };
+
+            match current {
+                Some(current) => {
+                    let fields = product_changes(current, &new_product);
+                    let action = match fields.is_empty() {
+                        true => CatalogAction::Unchanged,
+                        false => {
+                            let id = current.id().unwrap_or_default();
+                            DbProduct::update_with(&transaction, id, &new_product, user_id).await?;
+                            CatalogAction::Update
+                        }
+                    };
+                    changes.push(CatalogChange::new(
+                        CatalogEntity::Product,
+                        product.sku(),
+                        action,
+                        fields,
+                    ));
+                }
+                None => {
+                    DbProduct::insert(&transaction, &new_product, user_id).await?;
+                    changes.push(CatalogChange::new(
+                        CatalogEntity::Product,
+                        product.sku(),
+                        CatalogAction::Create,
+                        Vec::new(),
+                    ));
+                }
+            }
+        }
+
+        // Sem o commit a transação é desfeita ao ser descartada
+        let applied = !dry_run && errors.is_empty();
+        if applied {
+            transaction
+                .commit()
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+        }
+
+        Ok(CatalogImport::new(dry_run, applied, changes, errors))
+    }
+
+    async fn read_categories<C: GenericClient>(client: &C) -> Result<Vec<Category>, DataError> {
+        let query = "SELECT id, name, description, icon, position, available FROM tbl_category ORDER BY position, name, id";
+        let mut categories = Vec::new();
+
+        for row in client
+            .query(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            categories.push(
+                Category::new(
+                    row.get("id"),
+                    row.get("name"),
+                    row.get("description"),
+                    row.get("icon"),
+                    row.get("position"),
+                    row.get("available"),
+                )
+                .map_err(DataError::invalid_row)?,
+            );
+        }
+
+        Ok(categories)
+    }
+
+    // Produtos não excluídos pelo SKU
+    async fn read_products<C: GenericClient>(
+        client: &C,
+    ) -> Result<BTreeMap<String, Product>, DataError> {
+        let query = format!(
+            "SELECT {} FROM tbl_product WHERE deleted_at IS NULL ORDER BY sku",
+            PRODUCT_COLUMNS
+        );
+
+        let rows = client
+            .query(query.as_str(), &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(DbProduct::from_rows(client, rows)
+            .await?
+            .into_iter()
+            .map(|product| (product.sku().unwrap_or_default(), product))
+            .collect())
+    }
+}
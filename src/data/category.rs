@@ -0,0 +1,297 @@
+use std::collections::BTreeMap;
+use tokio_postgres::{GenericClient, Row};
+
+use super::database::Db;
+use super::error::DataError;
+use super::menu_audit::DbMenuAudit;
+use super::product::DbProduct;
+use super::schedule::{DbSchedule, ScheduleOwner};
+use super::translation::DbTranslation;
+use crate::models::{
+    category::{Category, Menu, MenuSection},
+    menu_audit::{MenuAction, MenuEntity},
+    product::Product,
+};
+
+pub struct DbCategory {}
+
+impl DbCategory {
+    // Retorna a categoria gravada, com o id criado
+    pub async fn create(category: Category, user_id: i32) -> Result<Category, DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let id = Self::insert(&transaction, &category, Some(user_id)).await?;
+
+        let query = "SELECT id, name, description, icon, position, available FROM tbl_category WHERE id = $1";
+        let created = Self::from_row(
+            &transaction
+                .query_one(query, &[&id])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?,
+        )?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(created)
+    }
+
+    // Grava a categoria dentro de uma transação já aberta e retorna o id criado
+    pub async fn insert<C: GenericClient>(
+        client: &C,
+        category: &Category,
+        user_id: Option<i32>,
+    ) -> Result<i32, DataError> {
+        let query = "INSERT INTO tbl_category (name, description, icon, position, available) VALUES ($1, $2, $3, $4, $5) RETURNING id";
+
+        let id: i32 = client
+            .query_one(
+                query,
+                &[
+                    &category.name(),
+                    &category.description(),
+                    &category.icon(),
+                    &category.position(),
+                    &category.available(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get("id");
+
+        let after = DbMenuAudit::snapshot(client, MenuEntity::Category, id).await?;
+        DbMenuAudit::record(
+            client,
+            MenuEntity::Category,
+            id,
+            MenuAction::Create,
+            user_id,
+            None,
+            after,
+        )
+        .await?;
+
+        Ok(id)
+    }
+
+    // 'locales' é a cadeia de idiomas das traduções; vazia retorna o conteúdo original
+    pub async fn read(id: i32, locales: &[String]) -> Result<Category, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, name, description, icon, position, available FROM tbl_category WHERE id = $1";
+
+        let row = client
+            .query_opt(query, &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("Category not found.".to_string()))?;
+
+        let mut categories = vec![Self::from_row(&row)?];
+        DbTranslation::translate_categories(&client, &mut categories, locales).await?;
+
+        categories
+            .pop()
+            .ok_or_else(|| DataError::NotFound("Category not found.".to_string()))
+    }
+
+    // Com 'only_available' lista apenas as categorias disponíveis e dentro do horário.
+    pub async fn read_all(only_available: bool, locales: &[String]) -> Result<Vec<Category>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!(
+            "SELECT id, name, description, icon, position, available FROM tbl_category
+            WHERE (available AND {}) OR NOT $1 ORDER BY position, name, id",
+            DbSchedule::on_schedule(ScheduleOwner::Category, "tbl_category.id")
+        );
+        let mut categories = Vec::new();
+
+        for row in client
+            .query(query.as_str(), &[&only_available])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            categories.push(Self::from_row(&row)?);
+        }
+
+        DbTranslation::translate_categories(&client, &mut categories, locales).await?;
+
+        Ok(categories)
+    }
+
+    pub async fn update(id: i32, category: Category, user_id: i32) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        Self::update_with(&transaction, id, &category, Some(user_id)).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Atualiza a categoria dentro de uma transação já aberta
+    pub async fn update_with<C: GenericClient>(
+        client: &C,
+        id: i32,
+        category: &Category,
+        user_id: Option<i32>,
+    ) -> Result<(), DataError> {
+        let before = match DbMenuAudit::snapshot(client, MenuEntity::Category, id).await? {
+            Some(before) => before,
+            None => return Err(DataError::NotFound("Category not found.".to_string())),
+        };
+
+        let query = "UPDATE tbl_category SET name = $2, description = $3, icon = $4, position = $5, available = $6 WHERE id = $1";
+
+        client
+            .execute(
+                query,
+                &[
+                    &id,
+                    &category.name(),
+                    &category.description(),
+                    &category.icon(),
+                    &category.position(),
+                    &category.available(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let after = DbMenuAudit::snapshot(client, MenuEntity::Category, id).await?;
+        DbMenuAudit::record(
+            client,
+            MenuEntity::Category,
+            id,
+            MenuAction::Update,
+            user_id,
+            Some(before),
+            after,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(id: i32, user_id: i32) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let before = match DbMenuAudit::snapshot(&transaction, MenuEntity::Category, id).await? {
+            Some(before) => before,
+            None => return Err(DataError::NotFound("Category not found.".to_string())),
+        };
+
+        let query = "DELETE FROM tbl_category WHERE id = $1";
+
+        transaction
+            .execute(query, &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        DbMenuAudit::record(
+            &transaction,
+            MenuEntity::Category,
+            id,
+            MenuAction::Delete,
+            Some(user_id),
+            Some(before),
+            None,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Monta o cardápio com as categorias disponíveis, na ordem definida pelos funcionários,
+    // e os produtos disponíveis de cada uma em ordem alfabética, no idioma pedido.
+    pub async fn read_menu(locales: &[String]) -> Result<Menu, DataError> {
+        let categories = Self::read_all(true, locales).await?;
+
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!(
+            "SELECT {} FROM tbl_product WHERE {} ORDER BY name, id",
+            DbProduct::columns(),
+            DbProduct::available_condition("tbl_product")
+        );
+
+        let rows = client
+            .query(query.as_str(), &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let mut by_category: BTreeMap<i32, Vec<Product>> = BTreeMap::new();
+        let mut uncategorized = Vec::new();
+
+        let mut products = DbProduct::from_rows(&client, rows).await?;
+        if !locales.is_empty() {
+            DbTranslation::translate_products(&client, &mut products, locales).await?;
+            products.sort_by_key(|product| product.name().to_lowercase());
+        }
+
+        for product in products {
+            match product.category_id() {
+                Some(category_id) => by_category.entry(category_id).or_default().push(product),
+                None => uncategorized.push(product),
+            }
+        }
+
+        let sections = categories
+            .into_iter()
+            .filter_map(|category| {
+                let products = by_category.remove(&category.id()?)?;
+                Some(MenuSection::new(category, products))
+            })
+            .collect();
+
+        Ok(Menu::new(sections, uncategorized))
+    }
+
+    fn from_row(row: &Row) -> Result<Category, DataError> {
+        Category::new(
+            row.get("id"),
+            row.get("name"),
+            row.get("description"),
+            row.get("icon"),
+            row.get("position"),
+            row.get("available"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+}
@@ -0,0 +1,141 @@
+use tokio_postgres::Row;
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::courier::{Courier, CourierLocation};
+
+pub struct DbCourier {}
+
+impl DbCourier {
+    // Cadastra um usuário existente como entregador, reativando-o se já foi cadastrado antes.
+    pub async fn create(user_id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "INSERT INTO tbl_courier (idtbl_user, active, available) VALUES ($1, true, true)
+        ON CONFLICT (idtbl_user) DO UPDATE SET active = true";
+
+        client
+            .execute(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_all() -> Result<Vec<Courier>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT c.idtbl_user, u.name, u.phone, c.available, c.latitude, c.longitude, c.location_updated_at::TEXT AS location_updated_at
+        FROM tbl_courier c JOIN tbl_user u ON u.id = c.idtbl_user WHERE c.active ORDER BY u.name";
+        let mut couriers = Vec::new();
+
+        for row in client
+            .query(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            couriers.push(Self::from_row(&row)?);
+        }
+
+        Ok(couriers)
+    }
+
+    // O entregador é desativado em vez de removido para manter o histórico de entregas.
+    pub async fn delete(user_id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_courier SET active = false, available = false WHERE idtbl_user = $1 AND active";
+
+        let rows_affected = client
+            .execute(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Courier not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn is_courier(user_id: i32) -> Result<bool, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT EXISTS (SELECT 1 FROM tbl_courier WHERE idtbl_user = $1 AND active)";
+
+        let exists: bool = client
+            .query_one(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+
+        Ok(exists)
+    }
+
+    pub async fn update_available(user_id: i32, available: bool) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_courier SET available = $2 WHERE idtbl_user = $1 AND active";
+
+        let rows_affected = client
+            .execute(query, &[&user_id, &available])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Courier not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn update_location(user_id: i32, location: CourierLocation) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_courier SET latitude = $2, longitude = $3, location_updated_at = NOW() WHERE idtbl_user = $1 AND active";
+
+        let rows_affected = client
+            .execute(
+                query,
+                &[&user_id, &location.latitude(), &location.longitude()],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Courier not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    fn from_row(row: &Row) -> Result<Courier, DataError> {
+        let latitude: Option<f64> = row.get("latitude");
+        let longitude: Option<f64> = row.get("longitude");
+        let location = match (latitude, longitude) {
+            (Some(latitude), Some(longitude)) => Some(CourierLocation::new(latitude, longitude).map_err(DataError::invalid_row)?),
+            _ => None,
+        };
+
+        Ok(Courier::new(
+            row.get("idtbl_user"),
+            row.get("name"),
+            row.get("phone"),
+            row.get("available"),
+            location,
+            row.get("location_updated_at"),
+        ))
+    }
+}
@@ -9,7 +9,7 @@ impl Db {
 
         tokio::spawn(async move {
             if let Err(e) = connection.await {
-                eprintln!("connection error: {}", e);
+                log::error!("Database connection error: {}", e);
             }
         });
 
@@ -19,6 +19,6 @@ impl Db {
 
 impl Drop for Db {
     fn drop(&mut self) {
-        println!("Disconnecting from database...");
+        log::debug!("Disconnecting from database...");
     }
 }
@@ -0,0 +1,284 @@
+use rand::Rng;
+use tokio_postgres::{Row, Transaction};
+
+use super::database::Db;
+use super::error::DataError;
+use super::loyalty::DbLoyalty;
+use crate::models::{
+    courier::CourierLocation,
+    delivery::{Delivery, DeliveryConfirmation, STATUS_DELIVERED, STATUS_OUT_FOR_DELIVERY},
+    product_cart::{STATUS_CANCELLED, STATUS_SCHEDULED},
+};
+
+pub struct DbDelivery {}
+
+impl DbDelivery {
+    // Atribui o pedido ao entregador escolhido pelo funcionário.
+    pub async fn assign(id_purchase: i32, courier_id: i32) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let query = "SELECT 1 FROM tbl_courier WHERE idtbl_user = $1 AND active FOR UPDATE";
+
+        if transaction
+            .query_opt(query, &[&courier_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .is_none()
+        {
+            return Err(DataError::NotFound("Courier not found.".to_string()));
+        }
+
+        Self::assign_with(&transaction, id_purchase, courier_id).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Atribui o pedido ao entregador disponível, sem entregas em andamento, que está há mais tempo sem receber um pedido.
+    pub async fn auto_assign(id_purchase: i32) -> Result<i32, DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let query = "SELECT c.idtbl_user FROM tbl_courier c
+        WHERE c.active AND c.available
+        AND EXISTS (SELECT 1 FROM tbl_user u WHERE u.id = c.idtbl_user AND u.deleted_at IS NULL)
+        AND NOT EXISTS (SELECT 1 FROM tbl_delivery d WHERE d.id_courier = c.idtbl_user AND d.delivered_at IS NULL)
+        ORDER BY c.last_assigned_at NULLS FIRST, c.idtbl_user
+        LIMIT 1 FOR UPDATE SKIP LOCKED";
+
+        let courier_id: i32 = match transaction
+            .query_opt(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            Some(row) => row.get("idtbl_user"),
+            None => return Err(DataError::Rule("Nenhum entregador disponível".to_string())),
+        };
+
+        Self::assign_with(&transaction, id_purchase, courier_id).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(courier_id)
+    }
+
+    pub async fn read(id_purchase: i32) -> Result<(i32, Delivery), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT o.idtbl_user AS owner_id, d.id_purchase, d.id_courier, u.name AS courier_name, d.assigned_at::TEXT AS assigned_at,
+        d.delivered_at::TEXT AS delivered_at, d.recipient_name, d.confirmation_code, c.latitude, c.longitude, c.location_updated_at::TEXT AS location_updated_at
+        FROM tbl_delivery d
+        JOIN tbl_order o ON o.id_purchase = d.id_purchase
+        JOIN tbl_courier c ON c.idtbl_user = d.id_courier
+        JOIN tbl_user u ON u.id = d.id_courier
+        WHERE d.id_purchase = $1";
+
+        let row = client
+            .query_opt(query, &[&id_purchase])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("Delivery not found.".to_string()))?;
+
+        Ok((row.get("owner_id"), Self::from_row(&row)?))
+    }
+
+    // Entregas em andamento do entregador, sem o código de confirmação.
+    pub async fn read_by_courier(courier_id: i32) -> Result<Vec<Delivery>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT d.id_purchase, d.id_courier, u.name AS courier_name, d.assigned_at::TEXT AS assigned_at,
+        d.delivered_at::TEXT AS delivered_at, d.recipient_name, d.confirmation_code, c.latitude, c.longitude, c.location_updated_at::TEXT AS location_updated_at
+        FROM tbl_delivery d
+        JOIN tbl_courier c ON c.idtbl_user = d.id_courier
+        JOIN tbl_user u ON u.id = d.id_courier
+        WHERE d.id_courier = $1 AND d.delivered_at IS NULL
+        ORDER BY d.assigned_at";
+        let mut deliveries = Vec::new();
+
+        for row in client
+            .query(query, &[&courier_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let mut delivery = Self::from_row(&row)?;
+            delivery.hide_confirmation_code();
+            deliveries.push(delivery);
+        }
+
+        Ok(deliveries)
+    }
+
+    // Confirma a entrega com o código informado pelo cliente e o nome de quem recebeu.
+    pub async fn confirm(
+        id_purchase: i32,
+        courier_id: i32,
+        confirmation: DeliveryConfirmation,
+    ) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let query = "UPDATE tbl_delivery SET delivered_at = NOW(), recipient_name = $4
+        WHERE id_purchase = $1 AND id_courier = $2 AND confirmation_code = $3 AND delivered_at IS NULL";
+
+        let rows_affected = transaction
+            .execute(
+                query,
+                &[
+                    &id_purchase,
+                    &courier_id,
+                    &confirmation.confirmation_code(),
+                    &confirmation.recipient_name(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound(
+                "Entrega não encontrada ou código de confirmação inválido".to_string(),
+            ));
+        }
+
+        // Um pedido cancelado depois da atribuição não é entregue nem gera pontos
+        let rows_affected = transaction
+            .execute(
+                "UPDATE tbl_cart_item SET status = $1 WHERE id_purchase = $2 AND status <> $3",
+                &[&STATUS_DELIVERED, &id_purchase, &STATUS_CANCELLED],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected == 0 {
+            return Err(DataError::Rule("O pedido foi cancelado".to_string()));
+        }
+
+        DbLoyalty::earn(&transaction, id_purchase).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn assign_with(
+        transaction: &Transaction<'_>,
+        id_purchase: i32,
+        courier_id: i32,
+    ) -> Result<(), DataError> {
+        // Os itens do pedido ficam travados até o fim da atribuição, para que não sejam cancelados no meio dela
+        let statuses: Vec<i32> = transaction
+            .query(
+                "SELECT status FROM tbl_cart_item WHERE id_purchase = $1 FOR UPDATE",
+                &[&id_purchase],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .iter()
+            .map(|row| row.get("status"))
+            .collect();
+        if statuses.is_empty() {
+            return Err(DataError::NotFound("Order not found.".to_string()));
+        }
+
+        // Somente pedidos liberados para a cozinha ou já em entrega podem ir para um entregador
+        if statuses.contains(&STATUS_CANCELLED) {
+            return Err(DataError::Rule("O pedido foi cancelado".to_string()));
+        }
+        if statuses.contains(&STATUS_DELIVERED) {
+            return Err(DataError::Rule("O pedido já foi entregue".to_string()));
+        }
+        if statuses.contains(&STATUS_SCHEDULED) {
+            return Err(DataError::Rule(
+                "O pedido agendado ainda não foi enviado à cozinha".to_string(),
+            ));
+        }
+
+        let confirmation_code = format!("{:04}", rand::thread_rng().gen_range(0..10000));
+
+        // Reatribuir um pedido mantém o código de confirmação já mostrado ao cliente
+        let query = "INSERT INTO tbl_delivery (id_purchase, id_courier, assigned_at, confirmation_code) VALUES ($1, $2, NOW(), $3)
+        ON CONFLICT (id_purchase) DO UPDATE SET id_courier = $2, assigned_at = NOW() WHERE tbl_delivery.delivered_at IS NULL";
+
+        let rows_affected = transaction
+            .execute(query, &[&id_purchase, &courier_id, &confirmation_code])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::Rule("O pedido já foi entregue".to_string()));
+        }
+
+        transaction
+            .execute(
+                "UPDATE tbl_courier SET last_assigned_at = NOW() WHERE idtbl_user = $1",
+                &[&courier_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        transaction
+            .execute(
+                "UPDATE tbl_cart_item SET status = $1 WHERE id_purchase = $2",
+                &[&STATUS_OUT_FOR_DELIVERY, &id_purchase],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn from_row(row: &Row) -> Result<Delivery, DataError> {
+        let latitude: Option<f64> = row.get("latitude");
+        let longitude: Option<f64> = row.get("longitude");
+        let location = match (latitude, longitude) {
+            (Some(latitude), Some(longitude)) => {
+                Some(CourierLocation::new(latitude, longitude).map_err(DataError::invalid_row)?)
+            }
+            _ => None,
+        };
+
+        Ok(Delivery::new(
+            row.get("id_purchase"),
+            row.get("id_courier"),
+            row.get("courier_name"),
+            row.get("assigned_at"),
+            row.get("delivered_at"),
+            row.get("recipient_name"),
+            row.get("confirmation_code"),
+            location,
+            row.get("location_updated_at"),
+        ))
+    }
+}
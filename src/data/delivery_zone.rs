@@ -0,0 +1,160 @@
+use tokio_postgres::Row;
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::delivery_zone::{restaurant_coordinates, DeliveryQuote, DeliveryZone, ZoneArea};
+
+pub struct DbDeliveryZone {}
+
+impl DbDeliveryZone {
+    pub async fn create(zone: DeliveryZone) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "INSERT INTO tbl_delivery_zone (name, area, fee, min_order, eta_minutes, active) VALUES ($1, $2, $3, $4, $5, $6)";
+
+        client
+            .execute(
+                query,
+                &[
+                    &zone.name(),
+                    &Self::area_to_text(&zone.area())?,
+                    &zone.fee(),
+                    &zone.min_order(),
+                    &zone.eta_minutes(),
+                    &zone.active(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_all(only_active: bool) -> Result<Vec<DeliveryZone>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, name, area, fee, min_order, eta_minutes, active FROM tbl_delivery_zone WHERE active OR NOT $1 ORDER BY fee, id";
+        let mut zones = Vec::new();
+
+        for row in client
+            .query(query, &[&only_active])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            zones.push(Self::from_row(&row)?);
+        }
+
+        Ok(zones)
+    }
+
+    pub async fn update(id: i32, zone: DeliveryZone) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_delivery_zone SET name = $2, area = $3, fee = $4, min_order = $5, eta_minutes = $6, active = $7 WHERE id = $1";
+
+        let rows_affected = client
+            .execute(
+                query,
+                &[
+                    &id,
+                    &zone.name(),
+                    &Self::area_to_text(&zone.area())?,
+                    &zone.fee(),
+                    &zone.min_order(),
+                    &zone.eta_minutes(),
+                    &zone.active(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Delivery zone not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn delete(id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "DELETE FROM tbl_delivery_zone WHERE id = $1";
+
+        let rows_affected = client
+            .execute(query, &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Delivery zone not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Encontra a zona ativa mais barata que atende as coordenadas informadas.
+    pub async fn quote(latitude: f64, longitude: f64) -> Result<DeliveryQuote, DataError> {
+        // Sem a localização do restaurante as zonas por raio são ignoradas e apenas os polígonos valem
+        let has_restaurant = restaurant_coordinates().is_ok();
+
+        for zone in Self::read_all(true).await? {
+            if !has_restaurant && matches!(zone.area(), ZoneArea::Radius { .. }) {
+                continue;
+            }
+            if zone
+                .area()
+                .contains(latitude, longitude)
+                .map_err(DataError::Db)?
+            {
+                return Ok(DeliveryQuote::new(&zone));
+            }
+        }
+
+        Err(DataError::Rule(
+            "Endereço fora da área de entrega".to_string(),
+        ))
+    }
+
+    // Sem as coordenadas do endereço não é possível saber a zona. Usa a zona ativa de maior taxa,
+    // para que pedidos com o endereço em texto do perfil nunca paguem menos que a entrega real.
+    pub async fn quote_without_coordinates() -> Result<DeliveryQuote, DataError> {
+        match Self::read_all(true)
+            .await?
+            .iter()
+            .max_by_key(|zone| zone.fee())
+        {
+            Some(zone) => Ok(DeliveryQuote::new(zone)),
+            None => Err(DataError::Rule("Nenhuma zona de entrega ativa".to_string())),
+        }
+    }
+
+    fn area_to_text(area: &ZoneArea) -> Result<String, DataError> {
+        serde_json::to_string(area)
+            .map_err(|e| DataError::Db(format!("Failed to serialize zone area: {}", e)))
+    }
+
+    fn from_row(row: &Row) -> Result<DeliveryZone, DataError> {
+        let area: String = row.get("area");
+        let area: ZoneArea = serde_json::from_str(&area)
+            .map_err(|e| DataError::Db(format!("Failed to parse zone area: {}", e)))?;
+
+        DeliveryZone::new(
+            row.get("id"),
+            row.get("name"),
+            area,
+            row.get("fee"),
+            row.get("min_order"),
+            row.get("eta_minutes"),
+            row.get("active"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+}
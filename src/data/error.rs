@@ -0,0 +1,40 @@
+use std::fmt;
+
+// Erros da camada de dados. A variante decide a resposta da API, em vez do texto da mensagem.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    // Falha do banco de dados ou do armazenamento de arquivos. A mensagem fica apenas no log.
+    Db(String),
+    // O registro pedido não existe
+    NotFound(String),
+    // O registro já existe
+    Conflict(String),
+    // Uma regra do negócio impede a operação, com uma mensagem para o usuário
+    Rule(String),
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::Db(message)
+            | DataError::NotFound(message)
+            | DataError::Conflict(message)
+            | DataError::Rule(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl DataError {
+    // Registro lido do banco que não passa na validação do modelo. É uma falha interna, não do usuário.
+    pub fn invalid_row(error: impl fmt::Display) -> DataError {
+        DataError::Db(format!("Failed to read row: {}", error))
+    }
+
+    // Identifica o item do pedido em que a regra falhou, como "Produto 'X': ...". Os outros erros não mudam.
+    pub fn in_item(self, name: &str) -> DataError {
+        match self {
+            DataError::Rule(message) => DataError::Rule(format!("Produto '{}': {}", name, message)),
+            error => error,
+        }
+    }
+}
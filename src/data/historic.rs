@@ -1,20 +1,50 @@
+use tokio_postgres::Row;
+
+use super::address::DbAddress;
+use super::bundle::DbBundle;
 use super::database::Db;
-use crate::models::product_cart::ProductCart;
+use super::error::DataError;
+use super::loyalty::DbLoyalty;
+use super::option_group::DbOptionGroup;
+use super::product::DbProduct;
+use super::promotion::DbPromotion;
+use super::schedule::DbSchedule;
+use super::stock::DbStock;
+use super::store::DbStore;
+use crate::models::{
+    address::Address,
+    bundle::BundleComponent,
+    option_group::{select_options, SelectedOption},
+    money::Money,
+    order_total::{OrderTotal, PricedItem},
+    product_cart::{ProductCart, STATUS_CANCELLED, STATUS_RECEIVED, STATUS_SCHEDULED},
+};
 
 pub struct DbHistoric {}
 
 impl DbHistoric {
-    pub async fn create(items: &[ProductCart], user_id: i32) -> Result<(), String> {
-        let client = Db::connect()
+    pub async fn create(
+        items: &[ProductCart],
+        priced_items: &[PricedItem],
+        user_id: i32,
+        address: &Address,
+        total: &OrderTotal,
+        scheduled_for: Option<&str>,
+    ) -> Result<Vec<ProductCart>, DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
 
-        let query = "INSERT INTO tbl_cart_item (name, observation, quantity, price, description, image, idtbl_user, status, date, payment_type, id_purchase)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)";
+        let query = "INSERT INTO tbl_cart_item (name, observation, quantity, price, description, image, idtbl_user, status, date, payment_type, id_purchase, options, components)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)";
         
         for item in items {
-
-            client
+            transaction
                 .execute(
                     query,
                     &[
@@ -29,58 +59,207 @@ impl DbHistoric {
                         &item.date(),
                         &item.payment_type(),
                         &item.id_purchase(),
+                        &Self::options_to_text(&item.options())?,
+                        &Self::components_to_text(&item.components())?,
                     ],
                 )
                 .await
-                .map_err(|e| format!("Failed to execute query: {}", e))?;
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
         }
 
-        Ok(())
+        if let Some(item) = items.first() {
+            DbAddress::create_snapshot(&transaction, item.id_purchase(), user_id, address).await?;
+
+            if let Some(scheduled_for) = scheduled_for {
+                DbStore::reserve_slot(&transaction, scheduled_for).await?;
+            }
+
+            let query = "INSERT INTO tbl_order (id_purchase, idtbl_user, id_delivery_zone, subtotal, delivery_fee, discount, loyalty_points, loyalty_discount, total, scheduled_for)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10::TEXT::TIMESTAMP)";
+
+            transaction
+                .execute(
+                    query,
+                    &[
+                        &item.id_purchase(),
+                        &user_id,
+                        &total.delivery_zone_id(),
+                        &total.subtotal(),
+                        &total.delivery_fee(),
+                        &total.discount(),
+                        &total.loyalty_points(),
+                        &total.loyalty_discount(),
+                        &total.total(),
+                        &scheduled_for,
+                    ],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+            DbPromotion::record_usage(&transaction, item.id_purchase(), user_id, total).await?;
+            DbLoyalty::redeem(&transaction, user_id, item.id_purchase(), total.loyalty_points())
+                .await?;
+
+            let mut demand = Vec::new();
+            for (item, priced_item) in items.iter().zip(priced_items) {
+                demand.push((priced_item.product_id(), item.quantity()));
+                for component in item.components() {
+                    demand.push((component.product_id(), component.quantity() * item.quantity()));
+                }
+            }
+            DbStock::reserve(&transaction, item.id_purchase(), user_id, &demand).await?;
+        }
+
+        // Itens gravados, com os ids criados pelo banco de dados
+        let query = "SELECT id, name, price, description, image, date, quantity, observation, status, payment_type, id_purchase, options, components FROM tbl_cart_item
+        WHERE idtbl_user = $1 AND id_purchase = $2 ORDER BY id";
+        let mut created = Vec::new();
+
+        if let Some(item) = items.first() {
+            for row in transaction
+                .query(query, &[&user_id, &item.id_purchase()])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            {
+                created.push(Self::from_row(&row)?);
+            }
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(created)
+    }
+
+    // Atualiza o preço de cada item com o preço cadastrado do produto somado às opções escolhidas.
+    // Os combos são expandidos nos seus componentes para a cozinha.
+    pub async fn price_items(items: &mut [ProductCart]) -> Result<Vec<PricedItem>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!(
+            "SELECT p.id, p.price, {} AND COALESCE(c.available, true) AS available, {} AS on_schedule, p.category_id
+            FROM tbl_product p LEFT JOIN tbl_category c ON c.id = p.category_id WHERE p.name = $1 AND p.deleted_at IS NULL ORDER BY p.id LIMIT 1",
+            DbProduct::available_condition("p"),
+            DbSchedule::product_on_schedule("p")
+        );
+        let mut priced_items = Vec::new();
+
+        for item in items.iter_mut() {
+            let row = match client
+                .query_opt(query.as_str(), &[&item.name()])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            {
+                Some(row) => row,
+                None => return Err(DataError::Rule(format!("Produto '{}' não encontrado", item.name()))),
+            };
+
+            let on_schedule: bool = row.get("on_schedule");
+            if !on_schedule {
+                return Err(DataError::Rule(format!("Produto '{}' não é servido neste horário", item.name())));
+            }
+            let available: bool = row.get("available");
+            if !available {
+                return Err(DataError::Rule(format!("Produto '{}' indisponível", item.name())));
+            }
+
+            let product_id: i32 = row.get("id");
+            let groups = DbOptionGroup::read_with(&client, &[product_id])
+                .await?
+                .remove(&product_id)
+                .unwrap_or_default();
+            let options = select_options(&groups, &item.options())
+                .map_err(|e| DataError::Rule(e).in_item(&item.name()))?;
+
+            let price: Money = row.get("price");
+            let price = price + options.iter().map(|o| o.price_delta()).sum::<Money>();
+            item.set_price(price).map_err(|_| {
+                DataError::Rule(format!("Produto '{}': preço inválido com as opções escolhidas", item.name()))
+            })?;
+            item.set_options(options);
+
+            let slots = DbBundle::read_with(&client, &[product_id])
+                .await?
+                .remove(&product_id)
+                .unwrap_or_default();
+            let components = DbBundle::expand(&client, &slots, &item.components())
+                .await
+                .map_err(|e| e.in_item(&item.name()))?;
+            item.set_components(components);
+            priced_items.push(PricedItem::new(
+                row.get("id"),
+                row.get("category_id"),
+                item.quantity(),
+                item.price(),
+            ).map_err(DataError::Rule)?);
+        }
+
+        Ok(priced_items)
     }
 
-    pub async fn read(user_id: i32) -> Result<Vec<ProductCart>, String> {
+    pub async fn read_total(id_purchase: i32) -> Result<(i32, OrderTotal), DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "SELECT id, name, price, description, image, date, quantity, observation, status, payment_type, id_purchase FROM tbl_cart_item WHERE idtbl_user = $1 ORDER BY date DESC";
+        let query = "SELECT idtbl_user, id_delivery_zone, subtotal, delivery_fee, loyalty_points, loyalty_discount FROM tbl_order WHERE id_purchase = $1";
+
+        let row = client
+            .query_opt(query, &[&id_purchase])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("Order not found.".to_string()))?;
+
+        let mut total = OrderTotal::new(
+            row.get("subtotal"),
+            row.get("delivery_fee"),
+            row.get("id_delivery_zone"),
+        );
+        for promotion in DbPromotion::read_usage(&client, id_purchase).await? {
+            total.add_promotion(promotion);
+        }
+        total.add_loyalty(row.get("loyalty_points"), row.get("loyalty_discount"));
+
+        Ok((row.get("idtbl_user"), total))
+    }
+
+    pub async fn read(user_id: i32) -> Result<Vec<ProductCart>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, name, price, description, image, date, quantity, observation, status, payment_type, id_purchase, options, components FROM tbl_cart_item WHERE idtbl_user = $1 ORDER BY date DESC";
         let mut items = Vec::new();
 
         let rows = client
             .query(query, &[&user_id])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
 
         for row in rows {
-            let item = ProductCart::new(
-                Some(row.get(0)),
-                row.get(1),
-                row.get(2),
-                row.get(3),
-                row.get(4),
-                row.get(5),
-                row.get(6),
-                row.get(7),
-                row.get(8),
-                row.get(9),
-                row.get(10),
-            )
-            .map_err(|e| format!("Failed to create ProductCart: {}", e))?;
-
-            items.push(item);
+            items.push(Self::from_row(&row)?);
         }
 
         Ok(items)
     }
 
-    pub async fn update(id_purchase: i32, new_status: i32, new_payment_type: String) -> Result<(), String> {
-        let client = Db::connect()
+    pub async fn update(id_purchase: i32, new_status: i32, new_payment_type: String) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
     
         let query = "UPDATE tbl_cart_item SET status = $1, payment_type = $2 WHERE id_purchase = $3";
     
-        client
+        transaction
             .execute(
                 query,
                 &[
@@ -90,60 +269,112 @@ impl DbHistoric {
                 ],
             )
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if new_status == STATUS_CANCELLED {
+            DbStock::restock(&transaction, id_purchase).await?;
+            DbLoyalty::refund(&transaction, id_purchase).await?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
     
         Ok(())
     }
 
+    // Envia para a cozinha os pedidos agendados cujo horário está dentro da antecedência configurada.
+    // Retorna a quantidade de itens liberados.
+    pub async fn release_scheduled() -> Result<u64, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!(
+            "UPDATE tbl_cart_item SET status = $1 WHERE status = $2 AND id_purchase IN (
+            SELECT o.id_purchase FROM tbl_order o, tbl_store_settings s
+            WHERE s.id = 1 AND o.scheduled_for - make_interval(mins => s.schedule_lead_minutes) <= {})",
+            DbSchedule::local_now()
+        );
+
+        client
+            .execute(query.as_str(), &[&STATUS_RECEIVED, &STATUS_SCHEDULED])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))
+    }
+
     /*
-    pub async fn delete(id_purchase: i32) -> Result<(), String> {
+    pub async fn delete(id_purchase: i32) -> Result<(), DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
     
         let query = "DELETE FROM tbl_cart_item WHERE id_purchase = $1";
     
         client
             .execute(query, &[&id_purchase])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
     
         Ok(())
     }
     */
 
-    pub async fn read_all() -> Result<Vec<ProductCart>, String> {
+    pub async fn read_all() -> Result<Vec<ProductCart>, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "SELECT id, name, price, description, image, date, quantity, observation, status, payment_type, id_purchase FROM tbl_cart_item ORDER BY date ASC";
+        let query = "SELECT id, name, price, description, image, date, quantity, observation, status, payment_type, id_purchase, options, components FROM tbl_cart_item ORDER BY date ASC";
         let mut items = Vec::new();
 
         let rows = client
             .query(query, &[])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
 
         for row in rows {
-            let item = ProductCart::new(
-                Some(row.get(0)),
-                row.get(1),
-                row.get(2),
-                row.get(3),
-                row.get(4),
-                row.get(5),
-                row.get(6),
-                row.get(7),
-                row.get(8),
-                row.get(9),
-                row.get(10),
-            )
-            .map_err(|e| format!("Failed to create ProductCart: {}", e))?;
-
-            items.push(item);
+            items.push(Self::from_row(&row)?);
         }
 
         Ok(items)
     }
+
+    fn from_row(row: &Row) -> Result<ProductCart, DataError> {
+        ProductCart::new(
+            Some(row.get(0)),
+            row.get(1),
+            row.get(2),
+            row.get(3),
+            row.get(4),
+            row.get(5),
+            row.get(6),
+            row.get(7),
+            row.get(8),
+            row.get(9),
+            row.get(10),
+            Self::options_from_text(row.get(11))?,
+            Self::components_from_text(row.get(12))?,
+        )
+        .map_err(|e| DataError::Db(format!("Failed to create ProductCart: {}", e)))
+    }
+
+    fn options_to_text(options: &[SelectedOption]) -> Result<String, DataError> {
+        serde_json::to_string(options).map_err(|e| DataError::Db(format!("Failed to serialize item options: {}", e)))
+    }
+
+    fn options_from_text(options: String) -> Result<Vec<SelectedOption>, DataError> {
+        serde_json::from_str(&options).map_err(|e| DataError::Db(format!("Failed to parse item options: {}", e)))
+    }
+
+    fn components_to_text(components: &[BundleComponent]) -> Result<String, DataError> {
+        serde_json::to_string(components)
+            .map_err(|e| DataError::Db(format!("Failed to serialize bundle components: {}", e)))
+    }
+
+    fn components_from_text(components: String) -> Result<Vec<BundleComponent>, DataError> {
+        serde_json::from_str(&components)
+            .map_err(|e| DataError::Db(format!("Failed to parse bundle components: {}", e)))
+    }
 }
@@ -0,0 +1,412 @@
+use tokio_postgres::GenericClient;
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::{
+    loyalty::{
+        user_account, LoyaltyAudit, LoyaltySettings, LoyaltyStatement, LoyaltyTransaction,
+        ACCOUNT_EXPIRED, ACCOUNT_ISSUED, ACCOUNT_REDEEMED,
+    },
+    money::Money,
+    order_total::OrderTotal,
+};
+
+pub struct DbLoyalty {}
+
+impl DbLoyalty {
+    pub async fn read_settings() -> Result<LoyaltySettings, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Self::read_settings_with(&client).await
+    }
+
+    pub async fn update_settings(settings: LoyaltySettings) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_loyalty_settings SET earn_rate = $1, burn_rate = $2, expiry_days = $3, min_redeem_points = $4 WHERE id = 1";
+
+        client
+            .execute(
+                query,
+                &[
+                    &settings.earn_rate(),
+                    &settings.burn_rate(),
+                    &settings.expiry_days(),
+                    &settings.min_redeem_points(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_statement(user_id: i32) -> Result<LoyaltyStatement, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let balance = Self::balance(&client, user_id).await?;
+
+        let query = "SELECT id, kind, points, id_purchase, created_at::TEXT AS created_at, expires_at::TEXT AS expires_at
+        FROM tbl_loyalty_transaction WHERE idtbl_user = $1 ORDER BY created_at DESC, id DESC";
+        let mut transactions = Vec::new();
+
+        for row in client
+            .query(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            transactions.push(LoyaltyTransaction::new(
+                row.get("id"),
+                row.get("kind"),
+                row.get("points"),
+                row.get("id_purchase"),
+                row.get("created_at"),
+                row.get("expires_at"),
+            ));
+        }
+
+        Ok(LoyaltyStatement::new(balance, transactions))
+    }
+
+    // Calcula o desconto dos pontos que o usuário quer resgatar no pedido.
+    // Se os pontos valem mais que o pedido, apenas os pontos necessários são usados.
+    pub async fn apply_redemption(
+        user_id: i32,
+        points: i32,
+        total: &mut OrderTotal,
+    ) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let settings = Self::read_settings_with(&client).await?;
+        if points < settings.min_redeem_points() || points <= 0 {
+            return Err(DataError::Rule(format!(
+                "O resgate mínimo é de {} pontos",
+                settings.min_redeem_points().max(1)
+            )));
+        }
+        if settings.burn_rate() <= 0.0 {
+            return Err(DataError::Rule("O resgate de pontos está desativado".to_string()));
+        }
+        if Self::available_points(&client, user_id).await? < points as i64 {
+            return Err(DataError::Rule("Saldo de pontos insuficiente".to_string()));
+        }
+
+        let needed = (total.total().to_decimal() / settings.burn_rate()).ceil() as i32;
+        let points = points.min(needed);
+        total.add_loyalty(points, Money::from_decimal(points as f64 * settings.burn_rate()).map_err(DataError::Rule)?);
+
+        Ok(())
+    }
+
+    // Debita os pontos resgatados, consumindo primeiro os que expiram antes.
+    pub async fn redeem<C: GenericClient>(
+        client: &C,
+        user_id: i32,
+        id_purchase: i32,
+        points: i32,
+    ) -> Result<(), DataError> {
+        if points <= 0 {
+            return Ok(());
+        }
+
+        let query = "SELECT id, remaining FROM tbl_loyalty_transaction
+        WHERE idtbl_user = $1 AND kind IN ('earn', 'refund') AND remaining > 0 AND expires_at > NOW()
+        ORDER BY expires_at, id FOR UPDATE";
+
+        let lots = client
+            .query(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let mut missing = points;
+        for lot in lots {
+            if missing == 0 {
+                break;
+            }
+            let id: i32 = lot.get("id");
+            let remaining: i32 = lot.get("remaining");
+            let used = remaining.min(missing);
+
+            client
+                .execute(
+                    "UPDATE tbl_loyalty_transaction SET remaining = remaining - $2 WHERE id = $1",
+                    &[&id, &used],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+            missing -= used;
+        }
+
+        if missing > 0 {
+            return Err(DataError::Rule("Saldo de pontos insuficiente".to_string()));
+        }
+
+        Self::post(
+            client,
+            user_id,
+            "redeem",
+            points,
+            Some(id_purchase),
+            None,
+            (&user_account(user_id), ACCOUNT_REDEEMED),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    // Credita os pontos de um pedido entregue. Chamar mais de uma vez para o mesmo pedido não tem efeito.
+    pub async fn earn<C: GenericClient>(client: &C, id_purchase: i32) -> Result<(), DataError> {
+        let order = client
+            .query_opt(
+                "SELECT idtbl_user, total FROM tbl_order WHERE id_purchase = $1 FOR UPDATE",
+                &[&id_purchase],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        // Pedidos anteriores ao cálculo de valores no servidor não geram pontos
+        let order = match order {
+            Some(order) => order,
+            None => return Ok(()),
+        };
+
+        let already_earned: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM tbl_loyalty_transaction WHERE id_purchase = $1 AND kind = 'earn')",
+                &[&id_purchase],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+        if already_earned {
+            return Ok(());
+        }
+
+        let settings = Self::read_settings_with(client).await?;
+        let total: Money = order.get("total");
+        let points = settings.points_for(total);
+        if points <= 0 {
+            return Ok(());
+        }
+
+        let user_id: i32 = order.get("idtbl_user");
+        Self::post(
+            client,
+            user_id,
+            "earn",
+            points,
+            Some(id_purchase),
+            Some(settings.expiry_days()),
+            (ACCOUNT_ISSUED, &user_account(user_id)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    // Devolve os pontos resgatados em um pedido cancelado, como um novo lote com a validade atual.
+    // Chamar mais de uma vez para o mesmo pedido não tem efeito.
+    pub async fn refund<C: GenericClient>(client: &C, id_purchase: i32) -> Result<(), DataError> {
+        let query = "SELECT r.idtbl_user, r.points FROM tbl_loyalty_transaction r
+        WHERE r.id_purchase = $1 AND r.kind = 'redeem'
+        AND NOT EXISTS (SELECT 1 FROM tbl_loyalty_transaction WHERE id_purchase = $1 AND kind = 'refund')";
+
+        let redeemed = client
+            .query_opt(query, &[&id_purchase])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        // Pedidos sem resgate de pontos não têm o que devolver
+        let redeemed = match redeemed {
+            Some(redeemed) => redeemed,
+            None => return Ok(()),
+        };
+
+        let settings = Self::read_settings_with(client).await?;
+        let user_id: i32 = redeemed.get("idtbl_user");
+        Self::post(
+            client,
+            user_id,
+            "refund",
+            redeemed.get("points"),
+            Some(id_purchase),
+            Some(settings.expiry_days()),
+            (ACCOUNT_REDEEMED, &user_account(user_id)),
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    // Expira o saldo restante dos lotes de pontos vencidos. Retorna a quantidade de lotes expirados.
+    pub async fn expire_points() -> Result<usize, DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let query = "SELECT id, idtbl_user, remaining FROM tbl_loyalty_transaction
+        WHERE kind IN ('earn', 'refund') AND remaining > 0 AND expires_at <= NOW() FOR UPDATE SKIP LOCKED";
+
+        let lots = transaction
+            .query(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        for lot in lots.iter() {
+            let id: i32 = lot.get("id");
+            let user_id: i32 = lot.get("idtbl_user");
+            let remaining: i32 = lot.get("remaining");
+
+            Self::post(
+                &transaction,
+                user_id,
+                "expire",
+                remaining,
+                None,
+                None,
+                (&user_account(user_id), ACCOUNT_EXPIRED),
+            )
+            .await?;
+
+            transaction
+                .execute(
+                    "UPDATE tbl_loyalty_transaction SET remaining = 0 WHERE id = $1",
+                    &[&id],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(lots.len())
+    }
+
+    pub async fn audit() -> Result<LoyaltyAudit, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let unbalanced: Vec<i32> = client
+            .query(
+                "SELECT id_transaction FROM tbl_loyalty_entry GROUP BY id_transaction HAVING SUM(amount) <> 0 ORDER BY id_transaction",
+                &[],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .iter()
+            .map(|row| row.get("id_transaction"))
+            .collect();
+
+        let query = "SELECT
+        COALESCE(-SUM(amount) FILTER (WHERE account = $1), 0)::BIGINT AS issued,
+        COALESCE(SUM(amount) FILTER (WHERE account = $2), 0)::BIGINT AS redeemed,
+        COALESCE(SUM(amount) FILTER (WHERE account = $3), 0)::BIGINT AS expired,
+        COALESCE(SUM(amount) FILTER (WHERE account LIKE 'user:%'), 0)::BIGINT AS outstanding
+        FROM tbl_loyalty_entry";
+
+        let row = client
+            .query_one(query, &[&ACCOUNT_ISSUED, &ACCOUNT_REDEEMED, &ACCOUNT_EXPIRED])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(LoyaltyAudit::new(
+            unbalanced,
+            row.get("issued"),
+            row.get("redeemed"),
+            row.get("expired"),
+            row.get("outstanding"),
+        ))
+    }
+
+    async fn read_settings_with<C: GenericClient>(client: &C) -> Result<LoyaltySettings, DataError> {
+        let query = "SELECT earn_rate, burn_rate, expiry_days, min_redeem_points FROM tbl_loyalty_settings WHERE id = 1";
+
+        let row = client
+            .query_one(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        LoyaltySettings::new(
+            row.get("earn_rate"),
+            row.get("burn_rate"),
+            row.get("expiry_days"),
+            row.get("min_redeem_points"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+
+    async fn balance<C: GenericClient>(client: &C, user_id: i32) -> Result<i64, DataError> {
+        let balance: i64 = client
+            .query_one(
+                "SELECT COALESCE(SUM(amount), 0)::BIGINT FROM tbl_loyalty_entry WHERE account = $1",
+                &[&user_account(user_id)],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+
+        Ok(balance)
+    }
+
+    async fn available_points<C: GenericClient>(client: &C, user_id: i32) -> Result<i64, DataError> {
+        let available: i64 = client
+            .query_one(
+                "SELECT COALESCE(SUM(remaining), 0)::BIGINT FROM tbl_loyalty_transaction WHERE idtbl_user = $1 AND kind IN ('earn', 'refund') AND expires_at > NOW()",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+
+        Ok(available)
+    }
+
+    // Registra uma transação movendo os pontos entre as contas (origem, destino).
+    async fn post<C: GenericClient>(
+        client: &C,
+        user_id: i32,
+        kind: &str,
+        points: i32,
+        id_purchase: Option<i32>,
+        expiry_days: Option<i32>,
+        (from, to): (&str, &str),
+    ) -> Result<i32, DataError> {
+        let query = "INSERT INTO tbl_loyalty_transaction (idtbl_user, kind, points, id_purchase, created_at, expires_at, remaining)
+        VALUES ($1, $2, $3, $4, NOW(), NOW() + make_interval(days => $5), CASE WHEN $2 IN ('earn', 'refund') THEN $3 ELSE 0 END)
+        RETURNING id";
+
+        let id: i32 = client
+            .query_one(query, &[&user_id, &kind, &points, &id_purchase, &expiry_days])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get("id");
+
+        let query = "INSERT INTO tbl_loyalty_entry (id_transaction, account, amount) VALUES ($1, $2, $3), ($1, $4, $5)";
+
+        client
+            .execute(query, &[&id, &from, &-points, &to, &points])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(id)
+    }
+}
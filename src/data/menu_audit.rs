@@ -0,0 +1,188 @@
+use tokio_postgres::GenericClient;
+
+use super::database::Db;
+use super::error::DataError;
+use super::schedule::DbSchedule;
+use crate::models::{
+    menu_audit::{MenuAction, MenuChange, MenuEntity, PriceChange},
+    money::Money,
+};
+
+// Maior número de alterações retornadas por consulta
+pub const AUDIT_LIMIT: i64 = 200;
+
+pub struct DbMenuAudit {}
+
+impl DbMenuAudit {
+    // Dados do item como JSON, para guardar o antes e o depois de cada alteração.
+    // Bloqueia a linha até o fim da transação. Retorna None se o item não existir ou tiver sido excluído.
+    pub async fn snapshot<C: GenericClient>(
+        client: &C,
+        entity: MenuEntity,
+        id: i32,
+    ) -> Result<Option<String>, DataError> {
+        let query = match entity {
+            MenuEntity::Product => "SELECT json_build_object('sku', sku, 'name', name, 'price', price, 'description', description, 'image', image,
+            'available', available, 'category_id', category_id, 'allergens', allergens, 'portion_grams', portion_grams,
+            'kcal', kcal, 'protein', protein, 'carbs', carbs, 'fat', fat)::TEXT FROM tbl_product WHERE id = $1 AND deleted_at IS NULL FOR UPDATE",
+            MenuEntity::Category => "SELECT json_build_object('name', name, 'description', description, 'icon', icon,
+            'position', position, 'available', available)::TEXT FROM tbl_category WHERE id = $1 FOR UPDATE",
+        };
+
+        Ok(client
+            .query_opt(query, &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .map(|row| row.get(0)))
+    }
+
+    pub async fn record<C: GenericClient>(
+        client: &C,
+        entity: MenuEntity,
+        id: i32,
+        action: MenuAction,
+        user_id: Option<i32>,
+        before: Option<String>,
+        after: Option<String>,
+    ) -> Result<(), DataError> {
+        let query = format!(
+            "INSERT INTO tbl_menu_audit (entity, entity_id, action, idtbl_user, changed_at, before, after)
+            VALUES ($1, $2, $3, $4, {}, $5, $6)",
+            DbSchedule::local_now()
+        );
+
+        client
+            .execute(
+                query.as_str(),
+                &[
+                    &entity.as_str(),
+                    &id,
+                    &action.as_str(),
+                    &user_id,
+                    &before,
+                    &after,
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Alterações mais recentes primeiro, opcionalmente de um tipo de item ou de um item
+    pub async fn read(
+        entity: Option<MenuEntity>,
+        entity_id: Option<i32>,
+    ) -> Result<Vec<MenuChange>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, entity, entity_id, action, idtbl_user, to_char(changed_at, 'YYYY-MM-DD HH24:MI:SS') AS changed_at, before, after
+        FROM tbl_menu_audit WHERE ($1::TEXT IS NULL OR entity = $1) AND ($2::INTEGER IS NULL OR entity_id = $2)
+        ORDER BY changed_at DESC, id DESC LIMIT $3";
+        let mut changes = Vec::new();
+
+        for row in client
+            .query(query, &[&entity.map(|e| e.as_str()), &entity_id, &AUDIT_LIMIT])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let entity: String = row.get("entity");
+            let action: String = row.get("action");
+            let before: Option<String> = row.get("before");
+            let after: Option<String> = row.get("after");
+
+            changes.push(MenuChange::new(
+                row.get("id"),
+                MenuEntity::parse(&entity).map_err(DataError::invalid_row)?,
+                row.get("entity_id"),
+                MenuAction::parse(&action).map_err(DataError::invalid_row)?,
+                row.get("idtbl_user"),
+                row.get("changed_at"),
+                Self::json_from_text(before)?,
+                Self::json_from_text(after)?,
+            ));
+        }
+
+        Ok(changes)
+    }
+
+    // Registra o preço do produto se ele mudou desde o último registro
+    pub async fn record_price<C: GenericClient>(
+        client: &C,
+        product_id: i32,
+        price: Money,
+        user_id: Option<i32>,
+    ) -> Result<(), DataError> {
+        let query = format!(
+            "INSERT INTO tbl_price_history (product_id, price, idtbl_user, changed_at)
+            SELECT $1, $2, $3, {} WHERE $2 IS DISTINCT FROM (
+                SELECT price FROM tbl_price_history WHERE product_id = $1 ORDER BY changed_at DESC, id DESC LIMIT 1)",
+            DbSchedule::local_now()
+        );
+
+        client
+            .execute(query.as_str(), &[&product_id, &price, &user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Preços do produto, do mais recente ao mais antigo. O histórico continua disponível após a exclusão do produto.
+    pub async fn read_prices(product_id: i32) -> Result<Vec<PriceChange>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT product_id, price, idtbl_user, to_char(changed_at, 'YYYY-MM-DD HH24:MI:SS') AS changed_at
+        FROM tbl_price_history WHERE product_id = $1 ORDER BY changed_at DESC, id DESC";
+
+        Ok(client
+            .query(query, &[&product_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .iter()
+            .map(|row| {
+                PriceChange::new(
+                    row.get("product_id"),
+                    row.get("price"),
+                    row.get("idtbl_user"),
+                    row.get("changed_at"),
+                )
+            })
+            .collect())
+    }
+
+    // Preço em vigor no momento informado (YYYY-MM-DD HH:MM:SS)
+    pub async fn read_price_at(product_id: i32, moment: &str) -> Result<PriceChange, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT product_id, price, idtbl_user, to_char(changed_at, 'YYYY-MM-DD HH24:MI:SS') AS changed_at
+        FROM tbl_price_history WHERE product_id = $1 AND changed_at <= $2::TEXT::TIMESTAMP
+        ORDER BY changed_at DESC, id DESC LIMIT 1";
+
+        match client
+            .query_opt(query, &[&product_id, &moment])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            Some(row) => Ok(PriceChange::new(
+                row.get("product_id"),
+                row.get("price"),
+                row.get("idtbl_user"),
+                row.get("changed_at"),
+            )),
+            None => Err(DataError::NotFound(format!("No price recorded for the product at {}", moment))),
+        }
+    }
+
+    fn json_from_text(text: Option<String>) -> Result<Option<serde_json::Value>, DataError> {
+        text.map(|text| serde_json::from_str(&text))
+            .transpose()
+            .map_err(|e| DataError::Db(format!("Failed to parse menu change: {}", e)))
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+use tokio_postgres::GenericClient;
+
+use crate::models::option_group::{OptionGroup, ProductOption};
+use super::error::DataError;
+
+
+pub struct DbOptionGroup {}
+
+impl DbOptionGroup {
+    // Busca os grupos de opções dos produtos informados, agrupados pelo id do produto.
+    pub async fn read_with<C: GenericClient>(
+        client: &C,
+        product_ids: &[i32],
+    ) -> Result<BTreeMap<i32, Vec<OptionGroup>>, DataError> {
+        let query = "SELECT g.product_id, g.id AS group_id, g.name AS group_name, g.min_select, g.max_select,
+        o.id AS option_id, o.name AS option_name, o.price_delta, o.available
+        FROM tbl_option_group g JOIN tbl_product_option o ON o.group_id = g.id
+        WHERE g.product_id = ANY($1) ORDER BY g.product_id, g.position, g.id, o.position, o.id";
+
+        let rows = client
+            .query(query, &[&product_ids])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let mut groups: BTreeMap<i32, Vec<OptionGroup>> = BTreeMap::new();
+        let mut i = 0;
+        while i < rows.len() {
+            let group_id: i32 = rows[i].get("group_id");
+            let mut options = Vec::new();
+            let mut j = i;
+            while j < rows.len() && rows[j].get::<_, i32>("group_id") == group_id {
+                options.push(ProductOption::new(
+                    rows[j].get("option_id"),
+                    rows[j].get("option_name"),
+                    rows[j].get("price_delta"),
+                    rows[j].get("available"),
+                ).map_err(DataError::invalid_row)?);
+                j += 1;
+            }
+
+            let group = OptionGroup::new(
+                Some(group_id),
+                rows[i].get("group_name"),
+                rows[i].get("min_select"),
+                rows[i].get("max_select"),
+                options,
+            ).map_err(DataError::invalid_row)?;
+            groups
+                .entry(rows[i].get("product_id"))
+                .or_default()
+                .push(group);
+            i = j;
+        }
+
+        Ok(groups)
+    }
+
+    // Substitui os grupos de opções do produto. Os ids informados são mantidos para que
+    // os aplicativos com o cardápio em cache continuem enviando opções válidas.
+    pub async fn replace_all<C: GenericClient>(
+        client: &C,
+        product_id: i32,
+        groups: &[OptionGroup],
+    ) -> Result<(), DataError> {
+        client
+            .execute(
+                "DELETE FROM tbl_option_group WHERE product_id = $1",
+                &[&product_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let group_query = "INSERT INTO tbl_option_group (id, product_id, name, min_select, max_select, position)
+        VALUES (COALESCE($1, nextval('tbl_option_group_id_seq')::INTEGER), $2, $3, $4, $5, $6) RETURNING id";
+        let option_query = "INSERT INTO tbl_product_option (id, group_id, name, price_delta, available, position)
+        VALUES (COALESCE($1, nextval('tbl_product_option_id_seq')::INTEGER), $2, $3, $4, $5, $6)";
+
+        for (position, group) in groups.iter().enumerate() {
+            let position = position as i32;
+            let group_id: i32 = client
+                .query_one(
+                    group_query,
+                    &[
+                        &group.id(),
+                        &product_id,
+                        &group.name(),
+                        &group.min_select(),
+                        &group.max_select(),
+                        &position,
+                    ],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+                .get("id");
+
+            for (position, option) in group.options().iter().enumerate() {
+                let position = position as i32;
+                client
+                    .execute(
+                        option_query,
+                        &[
+                            &option.id(),
+                            &group_id,
+                            &option.name(),
+                            &option.price_delta(),
+                            &option.available(),
+                            &position,
+                        ],
+                    )
+                    .await
+                    .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+use tokio_postgres::GenericClient;
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::payment_method::{PaymentMethod, PaymentMethodKind};
+
+pub struct DbPaymentMethod {}
+
+impl DbPaymentMethod {
+    pub async fn create(user_id: i32, method: PaymentMethod) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        Self::insert(&transaction, user_id, &method).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read(user_id: i32) -> Result<Vec<PaymentMethod>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Self::read_with(&client, user_id).await
+    }
+
+    pub async fn update(id: i32, user_id: i32, method: PaymentMethod) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        if method.is_default() {
+            Self::clear_default(&transaction, user_id).await?;
+        }
+
+        let query = "UPDATE tbl_user_payment_method SET kind = $3, value = $4, label = $5, is_default = $6 WHERE id = $1 AND idtbl_user = $2";
+
+        let rows_affected = transaction
+            .execute(
+                query,
+                &[
+                    &id,
+                    &user_id,
+                    &method.kind().as_str(),
+                    &method.value(),
+                    &method.label(),
+                    &method.is_default(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Payment method not found.".to_string()));
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn delete(id: i32, user_id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "DELETE FROM tbl_user_payment_method WHERE id = $1 AND idtbl_user = $2";
+
+        let rows_affected = client
+            .execute(query, &[&id, &user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Payment method not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Valida o pagamento escolhido no pedido contra os métodos salvos do usuário.
+    // Pagamento em dinheiro é sempre aceito. Cartão e PIX usam o método salvo informado em
+    // 'payment_method_id', que deve ser do usuário e do mesmo tipo; sem ele o usuário precisa ter
+    // um método salvo do tipo escolhido.
+    pub async fn validate_checkout(
+        user_id: i32,
+        payment_type: &str,
+        payment_method_id: Option<i32>,
+    ) -> Result<(), DataError> {
+        let kind = PaymentMethodKind::parse(payment_type).map_err(DataError::Rule)?;
+        if kind == PaymentMethodKind::Cash {
+            return Ok(());
+        }
+
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT kind FROM tbl_user_payment_method WHERE idtbl_user = $1 AND ($2::INT IS NULL OR id = $2) AND ($2::INT IS NOT NULL OR kind = $3) ORDER BY is_default DESC, id LIMIT 1";
+
+        let row = client
+            .query_opt(query, &[&user_id, &payment_method_id, &kind.as_str()])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        match row.map(|row| row.get::<_, String>("kind")) {
+            Some(saved) if saved == kind.as_str() => Ok(()),
+            Some(saved) => Err(DataError::Rule(format!(
+                "O método de pagamento escolhido é do tipo '{}', não '{}'",
+                saved,
+                kind.as_str()
+            ))),
+            None if payment_method_id.is_some() => Err(DataError::Rule(
+                "Método de pagamento não encontrado para o usuário".to_string(),
+            )),
+            None => Err(DataError::Rule(format!(
+                "Nenhum método de pagamento '{}' cadastrado para o usuário",
+                kind.as_str()
+            ))),
+        }
+    }
+
+    pub async fn read_with<C: GenericClient>(
+        client: &C,
+        user_id: i32,
+    ) -> Result<Vec<PaymentMethod>, DataError> {
+        let query = "SELECT id, kind, value, label, is_default FROM tbl_user_payment_method WHERE idtbl_user = $1 ORDER BY is_default DESC, id";
+        let mut methods = Vec::new();
+
+        for row in client
+            .query(query, &[&user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let kind: String = row.get("kind");
+            let method = PaymentMethod::new(
+                row.get("id"),
+                PaymentMethodKind::parse(&kind).map_err(DataError::invalid_row)?,
+                row.get("value"),
+                row.get("label"),
+                row.get("is_default"),
+            )
+            .map_err(DataError::invalid_row)?;
+
+            methods.push(method);
+        }
+
+        Ok(methods)
+    }
+
+    async fn insert<C: GenericClient>(
+        client: &C,
+        user_id: i32,
+        method: &PaymentMethod,
+    ) -> Result<(), DataError> {
+        // O primeiro método cadastrado se torna o padrão
+        let has_methods: bool = client
+            .query_one(
+                "SELECT EXISTS (SELECT 1 FROM tbl_user_payment_method WHERE idtbl_user = $1)",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+        let is_default = method.is_default() || !has_methods;
+
+        if is_default {
+            Self::clear_default(client, user_id).await?;
+        }
+
+        let query = "INSERT INTO tbl_user_payment_method (idtbl_user, kind, value, label, is_default) VALUES ($1, $2, $3, $4, $5)";
+
+        client
+            .execute(
+                query,
+                &[
+                    &user_id,
+                    &method.kind().as_str(),
+                    &method.value(),
+                    &method.label(),
+                    &is_default,
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn clear_default<C: GenericClient>(client: &C, user_id: i32) -> Result<(), DataError> {
+        client
+            .execute(
+                "UPDATE tbl_user_payment_method SET is_default = false WHERE idtbl_user = $1",
+                &[&user_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+}
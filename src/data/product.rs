@@ -1,94 +1,336 @@
+use tokio_postgres::{GenericClient, Row};
+
+use super::bundle::DbBundle;
 use super::database::Db;
-use crate::models::product::Product;
+use super::error::DataError;
+use super::menu_audit::DbMenuAudit;
+use super::option_group::DbOptionGroup;
+use super::schedule::DbSchedule;
+use super::stock::DbStock;
+use super::translation::DbTranslation;
+use crate::models::{
+    menu_audit::{MenuAction, MenuEntity},
+    nutrition::{Allergen, NutritionFacts},
+    page::Page,
+    product::Product,
+    product_listing::{ProductCursor, ProductListing, ProductSort},
+    product_search::{ProductSearch, ProductSearchResult},
+};
+
+// Configuração de busca textual em português que ignora acentos, criada em 'sql/tbl_product_search.sql'
+const SEARCH_CONFIG: &str = "portuguese_unaccent";
 
 pub struct DbProduct {}
 
 impl DbProduct {
-    pub async fn create(product: Product) -> Result<(), String> {
-        let client = Db::connect()
+    // 'user_id' identifica o funcionário no histórico de alterações do cardápio.
+    // Retorna o produto gravado, com o id e o código gerados.
+    pub async fn create(product: Product, user_id: Option<i32>) -> Result<Product, DataError> {
+        let mut client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "INSERT INTO tbl_product (name, price, description, image) VALUES ($1, $2, $3, $4) RETURNING id";
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
 
-        client
-            .execute(
+        let product_id = Self::insert(&transaction, &product, user_id).await?;
+
+        let query = format!("SELECT {} FROM tbl_product WHERE id = $1", Self::columns());
+        let row = transaction
+            .query_one(query.as_str(), &[&product_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        let created = Self::from_rows(&transaction, vec![row])
+            .await?
+            .pop()
+            .ok_or_else(|| DataError::NotFound("Product not found.".to_string()))?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(created)
+    }
+
+    // Grava o produto dentro de uma transação já aberta e retorna o id criado
+    pub async fn insert<C: GenericClient>(
+        client: &C,
+        product: &Product,
+        user_id: Option<i32>,
+    ) -> Result<i32, DataError> {
+        let query = "INSERT INTO tbl_product (sku, name, price, description, image, available, category_id, allergens, portion_grams, kcal, protein, carbs, fat)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13) RETURNING id";
+        let nutrition = product.nutrition();
+
+        let product_id: i32 = client
+            .query_one(
                 query,
                 &[
+                    &product.sku(),
                     &product.name(),
                     &product.price(),
                     &product.description(),
                     &product.image(),
+                    &product.available(),
+                    &product.category_id(),
+                    &Self::allergen_names(&product.allergens()),
+                    &nutrition.map(|n| n.portion_grams()),
+                    &nutrition.map(|n| n.kcal()),
+                    &nutrition.map(|n| n.protein()),
+                    &nutrition.map(|n| n.carbs()),
+                    &nutrition.map(|n| n.fat()),
                 ],
             )
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get("id");
 
-        Ok(())
+        // Produtos criados sem código recebem um a partir do id
+        client
+            .execute(
+                "UPDATE tbl_product SET sku = 'prod-' || id WHERE id = $1 AND sku IS NULL",
+                &[&product_id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        DbOptionGroup::replace_all(client, product_id, &product.option_groups()).await?;
+        DbBundle::replace_all(client, product_id, &product.bundle()).await?;
+        DbMenuAudit::record_price(client, product_id, product.price(), user_id).await?;
+        let after = DbMenuAudit::snapshot(client, MenuEntity::Product, product_id).await?;
+        DbMenuAudit::record(
+            client,
+            MenuEntity::Product,
+            product_id,
+            MenuAction::Create,
+            user_id,
+            None,
+            after,
+        )
+        .await?;
+
+        Ok(product_id)
     }
 
-    pub async fn read(id: i32) -> Result<Product, String> {
+    // 'locales' é a cadeia de idiomas das traduções; vazia retorna o conteúdo original
+    pub async fn read(id: i32, locales: &[String]) -> Result<Product, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query =
-            "SELECT id, name, price, description, image, available FROM tbl_product WHERE id = $1";
+        let query = format!(
+            "SELECT {} FROM tbl_product WHERE id = $1 AND deleted_at IS NULL",
+            Self::columns()
+        );
 
         let row = client
-            .query_one(query, &[&id])
+            .query_opt(query.as_str(), &[&id])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("Product not found.".to_string()))?;
 
-        Ok(Product::new(
-            row.get("id"),
-            row.get("name"),
-            row.get("price"),
-            row.get("description"),
-            row.get("image"),
-            row.get("available"),
-        )?)
+        let mut products = Self::from_rows(&client, vec![row]).await?;
+        DbTranslation::translate_products(&client, &mut products, locales).await?;
+
+        products
+            .pop()
+            .ok_or_else(|| DataError::NotFound("Product not found.".to_string()))
     }
 
-    pub async fn read_in_range(start: i64, limit: i64, available: bool) -> Result<Vec<Product>, String> {
+    pub async fn read_in_range(
+        start: i64,
+        limit: i64,
+        available: bool,
+        locales: &[String],
+    ) -> Result<Vec<Product>, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
         let available_condition = match available {
-            true => "WHERE available = true",
-            false => "",
+            true => format!("AND {}", Self::available_condition("tbl_product")),
+            false => String::new(),
         };
-        let query = format!("SELECT id, name, price, description, image, available FROM tbl_product {} ORDER BY id LIMIT $1 OFFSET $2", available_condition);
+        let query = format!(
+            "SELECT {} FROM tbl_product WHERE deleted_at IS NULL {} ORDER BY id LIMIT $1 OFFSET $2",
+            Self::columns(),
+            available_condition
+        );
 
         let rows = client
             .query(query.as_str(), &[&limit, &start])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
 
-        let mut products = Vec::new();
-        for row in rows {
-            let prod = Product::new(
-                row.get("id"),
-                row.get("name"),
-                row.get("price"),
-                row.get("description"),
-                row.get("image"),
-                row.get("available"),
-            )?;
-
-            products.push(prod);
-        }
+        let mut products = Self::from_rows(&client, rows).await?;
+        DbTranslation::translate_products(&client, &mut products, locales).await?;
 
         Ok(products)
     }
 
-    pub async fn update(id: i32, new_product: Product) -> Result<(), String> {
+    // Lista os produtos a partir do cursor, na ordem pedida. A paginação usa o último produto lido em vez
+    // de um deslocamento, por isso não repete nem pula produtos quando o cardápio é alterado entre as páginas.
+    // A ordenação por nome usa o nome original; a tradução é aplicada à página já montada.
+    pub async fn list(listing: ProductListing, locales: &[String]) -> Result<Page<Product>, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        // Empates no campo de ordenação são desfeitos pelo id
+        let (after_cursor, order_by) = match listing.sort() {
+            ProductSort::Name => ("(name, id) > ($6::TEXT, $5)", "name, id"),
+            ProductSort::Price => ("(price, id) > ($6::TEXT::NUMERIC, $5)", "price, id"),
+            ProductSort::Newest => ("id < $5", "id DESC"),
+        };
+        let query = format!(
+            "SELECT {} FROM tbl_product \
+             WHERE deleted_at IS NULL \
+             AND ($1::INTEGER IS NULL OR category_id = $1) \
+             AND ($2::NUMERIC IS NULL OR price >= $2) \
+             AND ($3::NUMERIC IS NULL OR price <= $3) \
+             AND (NOT $4 OR {}) \
+             AND ($5::INTEGER IS NULL OR {}) \
+             AND NOT (allergens && $8::TEXT[]) \
+             ORDER BY {} LIMIT $7",
+            Self::columns(),
+            Self::available_condition("tbl_product"),
+            after_cursor,
+            order_by
+        );
+
+        let cursor = listing.cursor();
+        // Um produto a mais indica se existe uma próxima página
+        let rows = client
+            .query(
+                query.as_str(),
+                &[
+                    &listing.category_id(),
+                    &listing.min_price(),
+                    &listing.max_price(),
+                    &listing.only_available(),
+                    &cursor.as_ref().map(|cursor| cursor.id()),
+                    &cursor.as_ref().map(|cursor| cursor.key()),
+                    &(listing.limit() + 1),
+                    &Self::allergen_names(&listing.exclude_allergens()),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let mut products = Self::from_rows(&client, rows).await?;
+        let next_cursor = match products.len() as i64 > listing.limit() {
+            true => {
+                products.truncate(listing.limit() as usize);
+                products.last().map(|product| {
+                    let key = match listing.sort() {
+                        ProductSort::Name => product.name(),
+                        ProductSort::Price => product.price().to_string(),
+                        ProductSort::Newest => String::new(),
+                    };
+                    ProductCursor::new(listing.sort(), product.id().unwrap_or_default(), key)
+                        .encode()
+                })
+            }
+            false => None,
+        };
+
+        DbTranslation::translate_products(&client, &mut products, locales).await?;
+
+        Ok(Page::new(products, next_cursor))
+    }
+
+    // Busca textual pelo nome e pela descrição, sem diferenciar acentos, ordenada pela relevância.
+    pub async fn search(search: ProductSearch) -> Result<Vec<ProductSearchResult>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        // O texto é escapado antes de receber as marcações, para que o resultado possa ser exibido como HTML
+        let query = format!(
+            "WITH search AS (SELECT to_tsquery('{config}', $1) AS query) \
+             SELECT {}, ts_rank_cd(tbl_product.search_vector, search.query) AS rank, \
+             ts_headline('{config}', {}, search.query, 'StartSel=<mark>, StopSel=</mark>, HighlightAll=true') AS name_highlight, \
+             ts_headline('{config}', {}, search.query, 'StartSel=<mark>, StopSel=</mark>, MaxWords=20, MinWords=8, MaxFragments=2, FragmentDelimiter=\" ... \"') AS snippet \
+             FROM tbl_product, search \
+             WHERE tbl_product.search_vector @@ search.query AND tbl_product.deleted_at IS NULL \
+             AND ($2::INTEGER IS NULL OR tbl_product.category_id = $2) \
+             AND (NOT $3 OR {}) \
+             ORDER BY rank DESC, tbl_product.name \
+             LIMIT $4 OFFSET $5",
+            Self::columns(),
+            Self::escape_html("tbl_product.name"),
+            Self::escape_html("tbl_product.description"),
+            Self::available_condition("tbl_product"),
+            config = SEARCH_CONFIG
+        );
+
+        let rows = client
+            .query(
+                query.as_str(),
+                &[
+                    &search.tsquery(),
+                    &search.category_id(),
+                    &search.only_available(),
+                    &search.limit(),
+                    &search.offset(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let matches: Vec<(f32, String, String)> = rows
+            .iter()
+            .map(|row| (row.get("rank"), row.get("name_highlight"), row.get("snippet")))
+            .collect();
+        let products = Self::from_rows(&client, rows).await?;
+
+        Ok(products
+            .into_iter()
+            .zip(matches)
+            .map(|(product, (rank, name_highlight, snippet))| {
+                ProductSearchResult::new(product, rank, name_highlight, snippet)
+            })
+            .collect())
+    }
+
+    pub async fn update(id: i32, new_product: Product, user_id: Option<i32>) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        Self::update_with(&transaction, id, &new_product, user_id).await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
 
-        let query = "UPDATE tbl_product SET name = $2, price = $3, description = $4, image = $5, available = $6 WHERE id = $1";
+    // Atualiza o produto dentro de uma transação já aberta. Sem código informado o código atual é mantido.
+    pub async fn update_with<C: GenericClient>(
+        client: &C,
+        id: i32,
+        new_product: &Product,
+        user_id: Option<i32>,
+    ) -> Result<(), DataError> {
+        let before = match DbMenuAudit::snapshot(client, MenuEntity::Product, id).await? {
+            Some(before) => before,
+            None => return Err(DataError::NotFound("Product not found.".to_string())),
+        };
+
+        let query = "UPDATE tbl_product SET name = $2, price = $3, description = $4, image = $5, available = $6, category_id = $7,
+        allergens = $8, portion_grams = $9, kcal = $10, protein = $11, carbs = $12, fat = $13, sku = COALESCE($14, sku) WHERE id = $1";
+        let nutrition = new_product.nutrition();
 
         client
             .execute(
@@ -100,26 +342,251 @@ impl DbProduct {
                     &new_product.description(),
                     &new_product.image(),
                     &new_product.available(),
+                    &new_product.category_id(),
+                    &Self::allergen_names(&new_product.allergens()),
+                    &nutrition.map(|n| n.portion_grams()),
+                    &nutrition.map(|n| n.kcal()),
+                    &nutrition.map(|n| n.protein()),
+                    &nutrition.map(|n| n.carbs()),
+                    &nutrition.map(|n| n.fat()),
+                    &new_product.sku(),
                 ],
             )
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        DbOptionGroup::replace_all(client, id, &new_product.option_groups()).await?;
+        DbBundle::replace_all(client, id, &new_product.bundle()).await?;
+        DbMenuAudit::record_price(client, id, new_product.price(), user_id).await?;
+        let after = DbMenuAudit::snapshot(client, MenuEntity::Product, id).await?;
+        DbMenuAudit::record(
+            client,
+            MenuEntity::Product,
+            id,
+            MenuAction::Update,
+            user_id,
+            Some(before),
+            after,
+        )
+        .await?;
 
         Ok(())
     }
 
-    pub async fn delete(id: i32) -> Result<(), String> {
-        let client = Db::connect()
+    pub async fn update_image(id: i32, image: String, user_id: i32) -> Result<(), DataError> {
+        let mut client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "DELETE FROM tbl_product WHERE id = $1";
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
 
-        client
+        let before = match DbMenuAudit::snapshot(&transaction, MenuEntity::Product, id).await? {
+            Some(before) => before,
+            None => return Err(DataError::NotFound("Product not found.".to_string())),
+        };
+
+        let query = "UPDATE tbl_product SET image = $2 WHERE id = $1";
+
+        transaction
+            .execute(query, &[&id, &image])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let after = DbMenuAudit::snapshot(&transaction, MenuEntity::Product, id).await?;
+        DbMenuAudit::record(
+            &transaction,
+            MenuEntity::Product,
+            id,
+            MenuAction::Update,
+            Some(user_id),
+            Some(before),
+            after,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Exclusão lógica: o produto deixa de aparecer no cardápio e nas consultas, mas pode ser restaurado
+    // até a remoção definitiva pela tarefa 'purge_deleted'. Os itens dos pedidos guardam o nome, o preço
+    // e a imagem do produto e continuam sendo exibidos no histórico.
+    // O histórico de preços e as alterações do produto continuam registrados após a exclusão.
+    pub async fn delete(id: i32, user_id: Option<i32>) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let before = match DbMenuAudit::snapshot(&transaction, MenuEntity::Product, id).await? {
+            Some(before) => before,
+            None => return Err(DataError::NotFound("Product not found.".to_string())),
+        };
+
+        let query = format!(
+            "UPDATE tbl_product SET deleted_at = {} WHERE id = $1",
+            DbSchedule::local_now()
+        );
+
+        transaction
+            .execute(query.as_str(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        DbMenuAudit::record(
+            &transaction,
+            MenuEntity::Product,
+            id,
+            MenuAction::Delete,
+            user_id,
+            Some(before),
+            None,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Desfaz a exclusão do produto, que volta ao cardápio com os dados que tinha ao ser excluído.
+    pub async fn restore(id: i32, user_id: i32) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let query = "UPDATE tbl_product SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL";
+
+        let rows_affected = transaction
             .execute(query, &[&id])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Product not found.".to_string()));
+        }
+
+        let after = DbMenuAudit::snapshot(&transaction, MenuEntity::Product, id).await?;
+        DbMenuAudit::record(
+            &transaction,
+            MenuEntity::Product,
+            id,
+            MenuAction::Restore,
+            Some(user_id),
+            None,
+            after,
+        )
+        .await?;
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
 
         Ok(())
     }
+
+    // Condição SQL de disponibilidade do produto: o cadastro, o estoque, os horários do produto
+    // e da categoria e, nos combos, os componentes. Produtos excluídos nunca estão disponíveis.
+    pub fn available_condition(product: &str) -> String {
+        format!(
+            "{0}.deleted_at IS NULL AND {0}.available AND {1} AND {2} AND {3}",
+            product,
+            DbStock::in_stock(product),
+            DbSchedule::product_on_schedule(product),
+            DbBundle::components_available(product)
+        )
+    }
+
+    // Expressão SQL com os caracteres especiais de HTML do texto escapados
+    fn escape_html(expression: &str) -> String {
+        format!(
+            "replace(replace(replace({}, '&', '&amp;'), '<', '&lt;'), '>', '&gt;')",
+            expression
+        )
+    }
+
+    // Colunas lidas pelo 'from_rows', com a disponibilidade calculada.
+    pub fn columns() -> String {
+        format!(
+            "id, sku, name, price, description, image, {} AS available, category_id, allergens, portion_grams, kcal, protein, carbs, fat",
+            Self::available_condition("tbl_product")
+        )
+    }
+
+    // Monta os produtos com os seus grupos de opções e as partes dos combos.
+    pub async fn from_rows<C: GenericClient>(
+        client: &C,
+        rows: Vec<Row>,
+    ) -> Result<Vec<Product>, DataError> {
+        let product_ids: Vec<i32> = rows.iter().map(|row| row.get("id")).collect();
+        let mut option_groups = DbOptionGroup::read_with(client, &product_ids).await?;
+        let mut bundles = DbBundle::read_with(client, &product_ids).await?;
+
+        let mut products = Vec::new();
+        for row in rows {
+            let id: i32 = row.get("id");
+            let allergens: Vec<String> = row.get("allergens");
+            let allergens = allergens
+                .iter()
+                .map(|allergen| Allergen::parse(allergen).map_err(DataError::invalid_row))
+                .collect::<Result<Vec<Allergen>, DataError>>()?;
+            let prod = Product::new(
+                Some(id),
+                row.get("sku"),
+                row.get("name"),
+                row.get("price"),
+                row.get("description"),
+                row.get("image"),
+                row.get("available"),
+                row.get("category_id"),
+                option_groups.remove(&id).unwrap_or_default(),
+                bundles.remove(&id).unwrap_or_default(),
+                allergens,
+                Self::nutrition_from_row(&row)?,
+            ).map_err(DataError::invalid_row)?;
+
+            products.push(prod);
+        }
+
+        Ok(products)
+    }
+
+    fn allergen_names(allergens: &[Allergen]) -> Vec<&'static str> {
+        allergens.iter().map(|allergen| allergen.as_str()).collect()
+    }
+
+    // A informação nutricional é gravada completa ou não é gravada
+    fn nutrition_from_row(row: &Row) -> Result<Option<NutritionFacts>, DataError> {
+        let portion_grams: Option<f64> = row.get("portion_grams");
+        match portion_grams {
+            Some(portion_grams) => Ok(Some(NutritionFacts::new(
+                portion_grams,
+                row.get("kcal"),
+                row.get("protein"),
+                row.get("carbs"),
+                row.get("fat"),
+            ).map_err(DataError::invalid_row)?)),
+            None => Ok(None),
+        }
+    }
 }
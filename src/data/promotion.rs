@@ -0,0 +1,318 @@
+use std::collections::BTreeMap;
+use tokio_postgres::{GenericClient, Row};
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::{
+    order_total::{AppliedPromotion, OrderTotal, PricedItem},
+    promotion::{Promotion, PromotionKind},
+};
+
+const COLUMNS: &str = "id, code, description, kind, product_id, category_id, min_order,
+    to_char(valid_from, 'YYYY-MM-DD HH24:MI:SS') AS valid_from, to_char(valid_until, 'YYYY-MM-DD HH24:MI:SS') AS valid_until,
+    max_uses, max_uses_per_user, active";
+
+const IN_WINDOW: &str = "active AND (valid_from IS NULL OR valid_from <= LOCALTIMESTAMP) AND (valid_until IS NULL OR valid_until > LOCALTIMESTAMP)";
+
+pub struct DbPromotion {}
+
+impl DbPromotion {
+    pub async fn create(promotion: Promotion) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "INSERT INTO tbl_promotion (code, description, kind, product_id, category_id, min_order, valid_from, valid_until, max_uses, max_uses_per_user, active)
+        VALUES ($1, $2, $3, $4, $5, $6, $7::TEXT::TIMESTAMP, $8::TEXT::TIMESTAMP, $9, $10, $11)";
+
+        client
+            .execute(
+                query,
+                &[
+                    &promotion.code(),
+                    &promotion.description(),
+                    &Self::kind_to_text(&promotion.kind())?,
+                    &promotion.product_id(),
+                    &promotion.category_id(),
+                    &promotion.min_order(),
+                    &promotion.valid_from(),
+                    &promotion.valid_until(),
+                    &promotion.max_uses(),
+                    &promotion.max_uses_per_user(),
+                    &promotion.active(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_all() -> Result<Vec<Promotion>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!("SELECT {} FROM tbl_promotion ORDER BY id DESC", COLUMNS);
+        let mut promotions = Vec::new();
+
+        for row in client
+            .query(query.as_str(), &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            promotions.push(Self::from_row(&row)?);
+        }
+
+        Ok(promotions)
+    }
+
+    // Promoções automáticas em vigor, exibidas nos aplicativos. Cupons não são listados.
+    pub async fn read_active() -> Result<Vec<Promotion>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Self::read_valid(&client, None).await
+    }
+
+    pub async fn update(id: i32, promotion: Promotion) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_promotion SET code = $2, description = $3, kind = $4, product_id = $5, category_id = $6, min_order = $7,
+        valid_from = $8::TEXT::TIMESTAMP, valid_until = $9::TEXT::TIMESTAMP, max_uses = $10, max_uses_per_user = $11, active = $12
+        WHERE id = $1";
+
+        let rows_affected = client
+            .execute(
+                query,
+                &[
+                    &id,
+                    &promotion.code(),
+                    &promotion.description(),
+                    &Self::kind_to_text(&promotion.kind())?,
+                    &promotion.product_id(),
+                    &promotion.category_id(),
+                    &promotion.min_order(),
+                    &promotion.valid_from(),
+                    &promotion.valid_until(),
+                    &promotion.max_uses(),
+                    &promotion.max_uses_per_user(),
+                    &promotion.active(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Promotion not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // A promoção é desativada em vez de removida para manter o registro nos pedidos.
+    pub async fn delete(id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_promotion SET active = false WHERE id = $1";
+
+        let rows_affected = client
+            .execute(query, &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Promotion not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Aplica ao pedido o melhor desconto automático de cada produto ou categoria e o cupom informado.
+    pub async fn apply(
+        user_id: i32,
+        coupon: Option<String>,
+        items: &[PricedItem],
+        total: &mut OrderTotal,
+    ) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let mut best: BTreeMap<(Option<i32>, Option<i32>), AppliedPromotion> = BTreeMap::new();
+        for promotion in Self::read_valid(&client, None).await? {
+            let id = match promotion.id() {
+                Some(id) => id,
+                None => continue,
+            };
+            let scope = (promotion.product_id(), promotion.category_id());
+            if Self::check_limits(&client, id, user_id).await.is_err() {
+                continue;
+            }
+
+            let amount = promotion.discount(items, total.subtotal(), total.delivery_fee());
+            if best.get(&scope).is_none_or(|b| amount > b.amount()) {
+                best.insert(scope, AppliedPromotion::new(id, None, amount));
+            }
+        }
+        for (_, promotion) in best {
+            total.add_promotion(promotion);
+        }
+
+        if let Some(code) = coupon {
+            let code = code.trim().to_uppercase();
+            let promotion = match Self::read_valid(&client, Some(code.as_str())).await?.pop() {
+                Some(promotion) => promotion,
+                None => return Err(DataError::Rule("Cupom inválido ou expirado".to_string())),
+            };
+            if total.subtotal() < promotion.min_order() {
+                return Err(DataError::Rule(format!(
+                    "O cupom exige pedido mínimo de R$ {}",
+                    promotion.min_order()
+                )));
+            }
+
+            let id = promotion.id().unwrap_or_default();
+            Self::check_limits(&client, id, user_id).await?;
+
+            let amount = promotion.discount(items, total.subtotal(), total.delivery_fee());
+            total.add_promotion(AppliedPromotion::new(id, Some(code), amount));
+        }
+
+        Ok(())
+    }
+
+    // Registra as promoções usadas no pedido, conferindo os limites novamente dentro da transação.
+    pub async fn record_usage<C: GenericClient>(
+        client: &C,
+        id_purchase: i32,
+        user_id: i32,
+        total: &OrderTotal,
+    ) -> Result<(), DataError> {
+        let query = "INSERT INTO tbl_order_promotion (id_purchase, id_promotion, idtbl_user, amount) VALUES ($1, $2, $3, $4)";
+
+        for promotion in total.promotions() {
+            Self::check_limits(client, promotion.promotion_id(), user_id).await?;
+
+            client
+                .execute(
+                    query,
+                    &[
+                        &id_purchase,
+                        &promotion.promotion_id(),
+                        &user_id,
+                        &promotion.amount(),
+                    ],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn read_usage<C: GenericClient>(
+        client: &C,
+        id_purchase: i32,
+    ) -> Result<Vec<AppliedPromotion>, DataError> {
+        let query = "SELECT op.id_promotion, p.code, op.amount FROM tbl_order_promotion op
+        JOIN tbl_promotion p ON p.id = op.id_promotion WHERE op.id_purchase = $1 ORDER BY op.id_promotion";
+        let mut promotions = Vec::new();
+
+        for row in client
+            .query(query, &[&id_purchase])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            promotions.push(AppliedPromotion::new(
+                row.get("id_promotion"),
+                row.get("code"),
+                row.get("amount"),
+            ));
+        }
+
+        Ok(promotions)
+    }
+
+    async fn check_limits<C: GenericClient>(
+        client: &C,
+        promotion_id: i32,
+        user_id: i32,
+    ) -> Result<(), DataError> {
+        let query = "SELECT p.max_uses, p.max_uses_per_user,
+        (SELECT COUNT(*) FROM tbl_order_promotion op WHERE op.id_promotion = p.id) AS uses,
+        (SELECT COUNT(*) FROM tbl_order_promotion op WHERE op.id_promotion = p.id AND op.idtbl_user = $2) AS user_uses
+        FROM tbl_promotion p WHERE p.id = $1 FOR UPDATE";
+
+        let row = client
+            .query_one(query, &[&promotion_id, &user_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let max_uses: Option<i32> = row.get("max_uses");
+        let max_uses_per_user: Option<i32> = row.get("max_uses_per_user");
+        let uses: i64 = row.get("uses");
+        let user_uses: i64 = row.get("user_uses");
+
+        if max_uses.is_some_and(|max| uses >= max as i64) {
+            return Err(DataError::Rule("Cupom esgotado".to_string()));
+        }
+        if max_uses_per_user.is_some_and(|max| user_uses >= max as i64) {
+            return Err(DataError::Rule("Limite de uso do cupom atingido para este usuário".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn read_valid<C: GenericClient>(
+        client: &C,
+        code: Option<&str>,
+    ) -> Result<Vec<Promotion>, DataError> {
+        let query = format!(
+            "SELECT {} FROM tbl_promotion WHERE {} AND (($1::TEXT IS NULL AND code IS NULL) OR code = $1) ORDER BY id",
+            COLUMNS, IN_WINDOW
+        );
+        let mut promotions = Vec::new();
+
+        for row in client
+            .query(query.as_str(), &[&code])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            promotions.push(Self::from_row(&row)?);
+        }
+
+        Ok(promotions)
+    }
+
+    fn kind_to_text(kind: &PromotionKind) -> Result<String, DataError> {
+        serde_json::to_string(kind).map_err(|e| DataError::Db(format!("Failed to serialize promotion kind: {}", e)))
+    }
+
+    fn from_row(row: &Row) -> Result<Promotion, DataError> {
+        let kind: String = row.get("kind");
+        let kind: PromotionKind = serde_json::from_str(&kind)
+            .map_err(|e| DataError::Db(format!("Failed to parse promotion kind: {}", e)))?;
+
+        Promotion::new(
+            row.get("id"),
+            row.get("code"),
+            row.get("description"),
+            kind,
+            row.get("product_id"),
+            row.get("category_id"),
+            row.get("min_order"),
+            row.get("valid_from"),
+            row.get("valid_until"),
+            row.get("max_uses"),
+            row.get("max_uses_per_user"),
+            row.get("active"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+}
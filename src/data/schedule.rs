@@ -0,0 +1,188 @@
+use super::database::Db;
+use super::error::DataError;
+use crate::models::schedule::{restaurant_timezone, Schedule, ScheduleException, ScheduleRange};
+
+// Dono dos horários: um produto ou uma categoria
+#[derive(Debug, Clone, Copy)]
+pub enum ScheduleOwner {
+    Product,
+    Category,
+}
+
+impl ScheduleOwner {
+    fn column(&self) -> &'static str {
+        match self {
+            ScheduleOwner::Product => "product_id",
+            ScheduleOwner::Category => "category_id",
+        }
+    }
+
+    fn table(&self) -> &'static str {
+        match self {
+            ScheduleOwner::Product => "tbl_product",
+            ScheduleOwner::Category => "tbl_category",
+        }
+    }
+}
+
+pub struct DbSchedule {}
+
+impl DbSchedule {
+    // Data e hora atuais no fuso do restaurante
+    pub fn local_now() -> String {
+        format!("(NOW() AT TIME ZONE '{}')", restaurant_timezone())
+    }
+
+    // Condição SQL que é verdadeira quando o item está dentro dos seus horários agora.
+    // Uma exceção para a data de hoje prevalece; sem intervalos cadastrados o item está sempre no horário.
+    // Intervalos que passam da meia-noite valem também na madrugada do dia seguinte.
+    pub fn on_schedule(owner: ScheduleOwner, id: &str) -> String {
+        let column = owner.column();
+        let now = Self::local_now();
+
+        format!(
+            "COALESCE(
+            (SELECT e.available FROM tbl_schedule_exception e WHERE e.{column} = {id} AND e.date = {now}::DATE),
+            NOT EXISTS (SELECT 1 FROM tbl_schedule_range r WHERE r.{column} = {id})
+            OR EXISTS (SELECT 1 FROM tbl_schedule_range r WHERE r.{column} = {id} AND (
+                (r.weekday = EXTRACT(DOW FROM {now}) AND {now}::TIME >= r.start_time
+                    AND (r.end_time <= r.start_time OR {now}::TIME < r.end_time))
+                OR (r.end_time <= r.start_time AND r.weekday = (EXTRACT(DOW FROM {now})::INTEGER + 6) % 7
+                    AND {now}::TIME < r.end_time))))",
+            column = column,
+            id = id,
+            now = now
+        )
+    }
+
+    // Condição SQL com os horários do produto e os da sua categoria.
+    pub fn product_on_schedule(product: &str) -> String {
+        format!(
+            "{} AND {}",
+            Self::on_schedule(ScheduleOwner::Product, &format!("{}.id", product)),
+            Self::on_schedule(ScheduleOwner::Category, &format!("{}.category_id", product))
+        )
+    }
+
+    pub async fn read(owner: ScheduleOwner, id: i32) -> Result<Schedule, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let column = owner.column();
+
+        let range_query = format!(
+            "SELECT weekday, to_char(start_time, 'HH24:MI') AS start_time, to_char(end_time, 'HH24:MI') AS end_time
+            FROM tbl_schedule_range WHERE {} = $1 ORDER BY weekday, start_time",
+            column
+        );
+        let mut ranges = Vec::new();
+
+        for row in client
+            .query(range_query.as_str(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            ranges.push(ScheduleRange::new(
+                row.get("weekday"),
+                row.get("start_time"),
+                row.get("end_time"),
+            ).map_err(DataError::invalid_row)?);
+        }
+
+        let exception_query = format!(
+            "SELECT to_char(date, 'YYYY-MM-DD') AS date, available FROM tbl_schedule_exception
+            WHERE {} = $1 AND date >= {}::DATE ORDER BY date",
+            column,
+            Self::local_now()
+        );
+        let mut exceptions = Vec::new();
+
+        for row in client
+            .query(exception_query.as_str(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            exceptions.push(ScheduleException::new(
+                row.get("date"),
+                row.get("available"),
+            ).map_err(DataError::invalid_row)?);
+        }
+
+        Schedule::new(ranges, exceptions).map_err(DataError::invalid_row)
+    }
+
+    // Substitui os horários e as exceções do produto ou categoria.
+    pub async fn replace(owner: ScheduleOwner, id: i32, schedule: Schedule) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let column = owner.column();
+
+        let exists: bool = transaction
+            .query_one(
+                format!("SELECT EXISTS (SELECT 1 FROM {} WHERE id = $1)", owner.table()).as_str(),
+                &[&id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+        if !exists {
+            return match owner {
+                ScheduleOwner::Product => Err(DataError::NotFound("Product not found.".to_string())),
+                ScheduleOwner::Category => Err(DataError::NotFound("Category not found.".to_string())),
+            };
+        }
+
+        for table in ["tbl_schedule_range", "tbl_schedule_exception"] {
+            transaction
+                .execute(
+                    format!("DELETE FROM {} WHERE {} = $1", table, column).as_str(),
+                    &[&id],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        let range_query = format!(
+            "INSERT INTO tbl_schedule_range ({}, weekday, start_time, end_time) VALUES ($1, $2, $3::TEXT::TIME, $4::TEXT::TIME)",
+            column
+        );
+        for range in schedule.ranges() {
+            transaction
+                .execute(
+                    range_query.as_str(),
+                    &[&id, &range.weekday(), &range.start(), &range.end()],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        let exception_query = format!(
+            "INSERT INTO tbl_schedule_exception ({}, date, available) VALUES ($1, $2::TEXT::DATE, $3)",
+            column
+        );
+        for exception in schedule.exceptions() {
+            transaction
+                .execute(
+                    exception_query.as_str(),
+                    &[&id, &exception.date(), &exception.available()],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,605 @@
+use std::collections::BTreeMap;
+use tokio_postgres::{GenericClient, Row};
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::stock::{
+    AdjustmentReason, Ingredient, RecipeItem, StockAdjustment, StockLevel, StockMovement,
+    StockSettings,
+};
+
+pub struct DbStock {}
+
+// Origem de uma movimentação de estoque, gravada junto com a quantidade
+struct MovementSource<'a> {
+    reason: AdjustmentReason,
+    note: &'a str,
+    id_purchase: Option<i32>,
+    user_id: Option<i32>,
+}
+
+impl DbStock {
+    // Condição SQL que é verdadeira quando o produto tem estoque para ao menos uma unidade,
+    // contando também os ingredientes da receita. Produtos sem controle de estoque sempre a satisfazem.
+    pub fn in_stock(product: &str) -> String {
+        format!(
+            "({0}.stock IS NULL OR {0}.stock > 0) AND NOT EXISTS (
+            SELECT 1 FROM tbl_recipe r JOIN tbl_ingredient i ON i.id = r.ingredient_id
+            WHERE r.product_id = {0}.id AND i.stock < r.amount)",
+            product
+        )
+    }
+
+    pub async fn update_settings(product_id: i32, settings: StockSettings) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_product SET stock = CASE WHEN $2 THEN COALESCE(stock, 0) ELSE NULL END, low_stock_threshold = $3 WHERE id = $1";
+
+        let rows_affected = client
+            .execute(
+                query,
+                &[
+                    &product_id,
+                    &settings.tracked(),
+                    &settings.low_stock_threshold(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Product not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Lista o estoque dos produtos controlados e dos ingredientes.
+    // Com 'only_low' lista apenas os que chegaram ao limite de estoque baixo.
+    pub async fn read_levels(only_low: bool) -> Result<Vec<StockLevel>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id AS product_id, NULL::INTEGER AS ingredient_id, name, stock::DOUBLE PRECISION AS stock, low_stock_threshold::DOUBLE PRECISION AS low_stock_threshold
+        FROM tbl_product WHERE stock IS NOT NULL AND deleted_at IS NULL
+        UNION ALL
+        SELECT NULL::INTEGER, id, name, stock, low_stock_threshold FROM tbl_ingredient
+        ORDER BY name";
+        let mut levels = Vec::new();
+
+        for row in client
+            .query(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let level = StockLevel::new(
+                row.get("product_id"),
+                row.get("ingredient_id"),
+                row.get("name"),
+                row.get("stock"),
+                row.get("low_stock_threshold"),
+            );
+            if !only_low || level.low() {
+                levels.push(level);
+            }
+        }
+
+        Ok(levels)
+    }
+
+    pub async fn adjust(user_id: i32, adjustment: StockAdjustment) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let (table, id) = match (adjustment.product_id(), adjustment.ingredient_id()) {
+            (Some(product_id), _) => ("tbl_product", product_id),
+            (None, Some(ingredient_id)) => ("tbl_ingredient", ingredient_id),
+            (None, None) => return Err(DataError::Rule("Stock adjustment has no target".to_string())),
+        };
+
+        let query = format!(
+            "SELECT name, stock::DOUBLE PRECISION AS stock FROM {} WHERE id = $1 FOR UPDATE",
+            table
+        );
+        let row = transaction
+            .query_opt(query.as_str(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("Stock item not found.".to_string()))?;
+
+        let name: String = row.get("name");
+        let stock: Option<f64> = row.get("stock");
+        let stock = stock.ok_or_else(|| DataError::Rule(format!("Stock of '{}' is not tracked", name)))?;
+        if stock + adjustment.delta() < 0.0 {
+            return Err(DataError::Rule(format!(
+                "Stock of '{}' cannot be negative ({} available)",
+                name, stock
+            )));
+        }
+        if adjustment.product_id().is_some() && stock + adjustment.delta() > i32::MAX as f64 {
+            return Err(DataError::Rule(format!(
+                "Stock of '{}' cannot exceed {} units",
+                name,
+                i32::MAX
+            )));
+        }
+
+        let note = adjustment.note();
+        let source = MovementSource {
+            reason: adjustment.reason(),
+            note: &note,
+            id_purchase: None,
+            user_id: Some(user_id),
+        };
+        match adjustment.product_id() {
+            Some(product_id) => {
+                Self::move_product(
+                    &transaction,
+                    product_id,
+                    &name,
+                    // Inteiro dentro do intervalo de i32, validado em StockAdjustment::new
+                    adjustment.delta() as i32,
+                    &source,
+                )
+                .await?
+            }
+            None => {
+                Self::move_ingredient(
+                    &transaction,
+                    id,
+                    &name,
+                    adjustment.delta(),
+                    &source,
+                )
+                .await?
+            }
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_log(limit: i64) -> Result<Vec<StockMovement>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, product_id, ingredient_id, name, delta, reason, note, id_purchase, idtbl_user, created_at::TEXT AS created_at
+        FROM tbl_stock_movement ORDER BY created_at DESC, id DESC LIMIT $1";
+        let mut movements = Vec::new();
+
+        for row in client
+            .query(query, &[&limit])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let reason: String = row.get("reason");
+            movements.push(StockMovement::new(
+                row.get("id"),
+                row.get("product_id"),
+                row.get("ingredient_id"),
+                row.get("name"),
+                row.get("delta"),
+                AdjustmentReason::parse(&reason).map_err(DataError::invalid_row)?,
+                row.get("note"),
+                row.get("id_purchase"),
+                row.get("idtbl_user"),
+                row.get("created_at"),
+            ));
+        }
+
+        Ok(movements)
+    }
+
+    // Baixa o estoque dos produtos e dos ingredientes de um pedido.
+    // 'demand' lista o id de cada produto vendido com a quantidade, incluindo os componentes dos combos.
+    pub async fn reserve<C: GenericClient>(
+        client: &C,
+        id_purchase: i32,
+        user_id: i32,
+        demand: &[(i32, i32)],
+    ) -> Result<(), DataError> {
+        let source = MovementSource {
+            reason: AdjustmentReason::Order,
+            note: "",
+            id_purchase: Some(id_purchase),
+            user_id: Some(user_id),
+        };
+
+        // Os produtos são travados sempre na mesma ordem para evitar deadlocks entre pedidos
+        let mut products: BTreeMap<i32, i32> = BTreeMap::new();
+        for (product_id, quantity) in demand {
+            *products.entry(*product_id).or_default() += quantity;
+        }
+
+        let mut ingredients: BTreeMap<i32, f64> = BTreeMap::new();
+        for (product_id, quantity) in products.iter() {
+            let row = client
+                .query_one(
+                    "SELECT name, stock FROM tbl_product WHERE id = $1 FOR UPDATE",
+                    &[product_id],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+            let name: String = row.get("name");
+            let stock: Option<i32> = row.get("stock");
+            if let Some(stock) = stock {
+                if stock < *quantity {
+                    return Err(DataError::Rule(format!(
+                        "Estoque insuficiente de '{}' ({} disponíveis)",
+                        name, stock
+                    )));
+                }
+                Self::move_product(
+                    client,
+                    *product_id,
+                    &name,
+                    -quantity,
+                    &source,
+                )
+                .await?;
+            }
+
+            for recipe_item in Self::read_recipe_with(client, *product_id).await? {
+                *ingredients.entry(recipe_item.ingredient_id()).or_default() +=
+                    recipe_item.amount() * *quantity as f64;
+            }
+        }
+
+        for (ingredient_id, amount) in ingredients {
+            let row = client
+                .query_one(
+                    "SELECT name, stock FROM tbl_ingredient WHERE id = $1 FOR UPDATE",
+                    &[&ingredient_id],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+            let name: String = row.get("name");
+            let stock: f64 = row.get("stock");
+            if stock < amount {
+                return Err(DataError::Rule(format!("Estoque insuficiente de '{}'", name)));
+            }
+            Self::move_ingredient(
+                client,
+                ingredient_id,
+                &name,
+                -amount,
+                &source,
+            )
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    // Devolve ao estoque o que foi baixado pelo pedido cancelado. Chamar mais de uma vez não tem efeito.
+    pub async fn restock<C: GenericClient>(client: &C, id_purchase: i32) -> Result<(), DataError> {
+        let query = "SELECT product_id, ingredient_id, MAX(name) AS name, SUM(delta) AS delta FROM tbl_stock_movement
+        WHERE id_purchase = $1 AND reason IN ('order', 'cancellation')
+        GROUP BY product_id, ingredient_id HAVING SUM(delta) <> 0 ORDER BY product_id, ingredient_id";
+        let source = MovementSource {
+            reason: AdjustmentReason::Cancellation,
+            note: "",
+            id_purchase: Some(id_purchase),
+            user_id: None,
+        };
+
+        for row in client
+            .query(query, &[&id_purchase])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            let product_id: Option<i32> = row.get("product_id");
+            let ingredient_id: Option<i32> = row.get("ingredient_id");
+            let name: String = row.get("name");
+            let delta: f64 = row.get("delta");
+
+            match (product_id, ingredient_id) {
+                (Some(product_id), _) => {
+                    Self::move_product(
+                        client,
+                        product_id,
+                        &name,
+                        (-delta).round() as i32,
+                        &source,
+                    )
+                    .await?
+                }
+                (None, Some(ingredient_id)) => {
+                    Self::move_ingredient(
+                        client,
+                        ingredient_id,
+                        &name,
+                        -delta,
+                        &source,
+                    )
+                    .await?
+                }
+                // O produto ou ingrediente foi removido depois do pedido
+                (None, None) => continue,
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn create_ingredient(ingredient: Ingredient) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "INSERT INTO tbl_ingredient (name, unit, low_stock_threshold) VALUES ($1, $2, $3)";
+
+        client
+            .execute(
+                query,
+                &[
+                    &ingredient.name(),
+                    &ingredient.unit(),
+                    &ingredient.low_stock_threshold(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_ingredients() -> Result<Vec<Ingredient>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "SELECT id, name, unit, stock, low_stock_threshold FROM tbl_ingredient ORDER BY name";
+        let mut ingredients = Vec::new();
+
+        for row in client
+            .query(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            ingredients.push(Self::ingredient_from_row(&row)?);
+        }
+
+        Ok(ingredients)
+    }
+
+    // O estoque do ingrediente não é alterado, apenas por ajustes.
+    pub async fn update_ingredient(id: i32, ingredient: Ingredient) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_ingredient SET name = $2, unit = $3, low_stock_threshold = $4 WHERE id = $1";
+
+        let rows_affected = client
+            .execute(
+                query,
+                &[
+                    &id,
+                    &ingredient.name(),
+                    &ingredient.unit(),
+                    &ingredient.low_stock_threshold(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Ingredient not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // O ingrediente também é removido das receitas.
+    pub async fn delete_ingredient(id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "DELETE FROM tbl_ingredient WHERE id = $1";
+
+        let rows_affected = client
+            .execute(query, &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Ingredient not found.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    pub async fn read_recipe(product_id: i32) -> Result<Vec<RecipeItem>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Self::read_recipe_with(&client, product_id).await
+    }
+
+    pub async fn replace_recipe(product_id: i32, recipe: Vec<RecipeItem>) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        transaction
+            .execute("DELETE FROM tbl_recipe WHERE product_id = $1", &[&product_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let query = "INSERT INTO tbl_recipe (product_id, ingredient_id, amount) VALUES ($1, $2, $3)";
+        for item in recipe.iter() {
+            transaction
+                .execute(query, &[&product_id, &item.ingredient_id(), &item.amount()])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn read_recipe_with<C: GenericClient>(
+        client: &C,
+        product_id: i32,
+    ) -> Result<Vec<RecipeItem>, DataError> {
+        let query = "SELECT ingredient_id, amount FROM tbl_recipe WHERE product_id = $1 ORDER BY ingredient_id";
+        let mut recipe = Vec::new();
+
+        for row in client
+            .query(query, &[&product_id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            recipe.push(RecipeItem::new(row.get("ingredient_id"), row.get("amount")).map_err(DataError::invalid_row)?);
+        }
+
+        Ok(recipe)
+    }
+
+    async fn move_product<C: GenericClient>(
+        client: &C,
+        product_id: i32,
+        name: &str,
+        delta: i32,
+        source: &MovementSource<'_>,
+    ) -> Result<(), DataError> {
+        let row = client
+            .query_opt(
+                "UPDATE tbl_product SET stock = stock + $2 WHERE id = $1 AND stock IS NOT NULL
+                RETURNING stock, low_stock_threshold",
+                &[&product_id, &delta],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        if let Some(row) = row {
+            let stock: i32 = row.get("stock");
+            let threshold: i32 = row.get("low_stock_threshold");
+            Self::alert_low_stock(name, (stock - delta) as f64, stock as f64, threshold as f64);
+        }
+
+        Self::log(
+            client,
+            Some(product_id),
+            None,
+            name,
+            delta as f64,
+            source,
+        )
+        .await
+    }
+
+    async fn move_ingredient<C: GenericClient>(
+        client: &C,
+        ingredient_id: i32,
+        name: &str,
+        delta: f64,
+        source: &MovementSource<'_>,
+    ) -> Result<(), DataError> {
+        let row = client
+            .query_opt(
+                "UPDATE tbl_ingredient SET stock = stock + $2 WHERE id = $1
+                RETURNING stock, low_stock_threshold",
+                &[&ingredient_id, &delta],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        if let Some(row) = row {
+            let stock: f64 = row.get("stock");
+            let threshold: f64 = row.get("low_stock_threshold");
+            Self::alert_low_stock(name, stock - delta, stock, threshold);
+        }
+
+        Self::log(
+            client,
+            None,
+            Some(ingredient_id),
+            name,
+            delta,
+            source,
+        )
+        .await
+    }
+
+    // Registra no log um aviso para a equipe quando o estoque chega ao limite mínimo. O aviso sai apenas
+    // na passagem pelo limite, e não a cada venda com o estoque já baixo; a lista completa fica em read_levels.
+    fn alert_low_stock(name: &str, before: f64, after: f64, threshold: f64) {
+        if before > threshold && after <= threshold {
+            log::warn!(
+                "Low stock: '{}' reached {} (threshold {})",
+                name,
+                after,
+                threshold
+            );
+        }
+    }
+
+    async fn log<C: GenericClient>(
+        client: &C,
+        product_id: Option<i32>,
+        ingredient_id: Option<i32>,
+        name: &str,
+        delta: f64,
+        source: &MovementSource<'_>,
+    ) -> Result<(), DataError> {
+        let query = "INSERT INTO tbl_stock_movement (product_id, ingredient_id, name, delta, reason, note, id_purchase, idtbl_user, created_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())";
+
+        client
+            .execute(
+                query,
+                &[
+                    &product_id,
+                    &ingredient_id,
+                    &name,
+                    &delta,
+                    &source.reason.as_str(),
+                    &source.note,
+                    &source.id_purchase,
+                    &source.user_id,
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    fn ingredient_from_row(row: &Row) -> Result<Ingredient, DataError> {
+        Ingredient::new(
+            row.get("id"),
+            row.get("name"),
+            row.get("unit"),
+            row.get("stock"),
+            row.get("low_stock_threshold"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+}
@@ -0,0 +1,355 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use tokio_postgres::GenericClient;
+
+use super::database::Db;
+use super::error::DataError;
+use super::schedule::DbSchedule;
+use crate::models::{
+    product_cart::STATUS_CANCELLED,
+    schedule::{ScheduleRange, DATE_FORMAT},
+    store::{
+        Holiday, OrderScheduleSettings, ScheduleSlot, StoreHours, StorePause, StoreStatus,
+        DATETIME_FORMAT,
+    },
+};
+
+pub struct DbStore {}
+
+impl DbStore {
+    // Busca o horário de funcionamento e os feriados a partir de ontem.
+    pub async fn read_hours() -> Result<StoreHours, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Self::read_hours_with(&client).await
+    }
+
+    pub async fn update_hours(hours: StoreHours) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        for query in ["DELETE FROM tbl_opening_hours", "DELETE FROM tbl_holiday"] {
+            transaction
+                .execute(query, &[])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        let hours_query = "INSERT INTO tbl_opening_hours (weekday, open_time, close_time) VALUES ($1, $2::TEXT::TIME, $3::TEXT::TIME)";
+        for range in hours.opening_hours() {
+            transaction
+                .execute(hours_query, &[&range.weekday(), &range.start(), &range.end()])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        let holiday_query = "INSERT INTO tbl_holiday (date, description) VALUES ($1::TEXT::DATE, $2)";
+        for holiday in hours.holidays() {
+            transaction
+                .execute(holiday_query, &[&holiday.date(), &holiday.description()])
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Pausa os pedidos. O horário de retorno é informado no fuso do restaurante.
+    pub async fn pause(pause: StorePause) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_store_settings SET paused = true, pause_reason = $1, resume_at = $2::TEXT::TIMESTAMP WHERE id = 1";
+
+        client
+            .execute(query, &[&pause.reason(), &pause.resume_at()])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn resume() -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_store_settings SET paused = false, pause_reason = '', resume_at = NULL WHERE id = 1";
+
+        client
+            .execute(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    pub async fn read_status() -> Result<StoreStatus, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let (now, pause, hours) = Self::read_state_with(&client).await?;
+
+        Ok(StoreStatus::new(&hours, now, pause))
+    }
+
+    // Confere se o pedido pode ser aceito. Sem horário agendado o restaurante precisa estar aberto;
+    // com horário agendado ele é validado pelo horário de funcionamento, pela pausa e pelas regras de agendamento.
+    // Retorna o horário agendado normalizado.
+    pub async fn validate_checkout(scheduled_for: Option<String>) -> Result<Option<String>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let (now, pause, hours) = Self::read_state_with(&client).await?;
+
+        let scheduled_for = match scheduled_for {
+            Some(scheduled_for) => NaiveDateTime::parse_from_str(&scheduled_for, DATETIME_FORMAT)
+                .map_err(|_| {
+                    DataError::Rule(format!(
+                        "Horário agendado '{}' deve estar no formato YYYY-MM-DD HH:MM:SS",
+                        scheduled_for
+                    ))
+                })?,
+            None => {
+                let status = StoreStatus::new(&hours, now, pause);
+                if !status.open() {
+                    return Err(DataError::Rule(format!("{}. Agende o pedido para um horário de funcionamento", status.closed_message())));
+                }
+                return Ok(None);
+            }
+        };
+
+        let settings = Self::read_schedule_settings_with(&client).await?;
+        settings.validate_time(&hours, scheduled_for, now).map_err(DataError::Rule)?;
+
+        // A vaga é conferida de novo, com bloqueio, na gravação do pedido
+        let start = settings.slot_start(scheduled_for);
+        if Self::count_slot_orders(&client, &settings, start).await? >= settings.slot_capacity() {
+            return Err(DataError::Rule(String::from("Não há mais vagas para o horário escolhido")));
+        }
+
+        if let Some(pause) = pause {
+            let resume_at = pause
+                .resume_at()
+                .and_then(|t| NaiveDateTime::parse_from_str(&t, DATETIME_FORMAT).ok());
+            if resume_at.is_none_or(|resume_at| scheduled_for < resume_at) {
+                return Err(DataError::Rule(StoreStatus::new(&hours, now, Some(pause)).closed_message()));
+            }
+        }
+
+        Ok(Some(scheduled_for.format(DATETIME_FORMAT).to_string()))
+    }
+
+    pub async fn read_schedule_settings() -> Result<OrderScheduleSettings, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        Self::read_schedule_settings_with(&client).await
+    }
+
+    pub async fn update_schedule_settings(settings: OrderScheduleSettings) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = "UPDATE tbl_store_settings SET schedule_slot_minutes = $1, schedule_slot_capacity = $2, schedule_lead_minutes = $3,
+        schedule_min_advance_minutes = $4, schedule_max_days_ahead = $5 WHERE id = 1";
+
+        client
+            .execute(
+                query,
+                &[
+                    &settings.slot_minutes(),
+                    &settings.slot_capacity(),
+                    &settings.lead_minutes(),
+                    &settings.min_advance_minutes(),
+                    &settings.max_days_ahead(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Lista os horários de um dia disponíveis para agendamento, com as vagas restantes.
+    pub async fn read_slots(date: String) -> Result<Vec<ScheduleSlot>, DataError> {
+        let date = NaiveDate::parse_from_str(&date, DATE_FORMAT)
+            .map_err(|_| DataError::Rule(format!("Date '{}' must be in the format YYYY-MM-DD", date)))?;
+
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let (now, _, hours) = Self::read_state_with(&client).await?;
+        let settings = Self::read_schedule_settings_with(&client).await?;
+
+        let mut slots = Vec::new();
+        for start in settings.slots(&hours, date, now) {
+            let used = Self::count_slot_orders(&client, &settings, start).await?;
+            let remaining = settings.slot_capacity() - used;
+            if remaining > 0 {
+                slots.push(ScheduleSlot::new(start, remaining));
+            }
+        }
+
+        Ok(slots)
+    }
+
+    // Reserva uma vaga no intervalo do horário agendado. Deve ser chamada na transação que grava o pedido.
+    pub async fn reserve_slot<C: GenericClient>(client: &C, scheduled_for: &str) -> Result<(), DataError> {
+        let scheduled_for = NaiveDateTime::parse_from_str(scheduled_for, DATETIME_FORMAT)
+            .map_err(|e| DataError::Db(format!("Failed to read scheduled time: {}", e)))?;
+
+        // Serializa os agendamentos para que dois pedidos não ocupem a última vaga
+        client
+            .execute("SELECT pg_advisory_xact_lock(hashtext('tbl_order.scheduled_for'))", &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let settings = Self::read_schedule_settings_with(client).await?;
+        let start = settings.slot_start(scheduled_for);
+
+        if Self::count_slot_orders(client, &settings, start).await? >= settings.slot_capacity() {
+            return Err(DataError::Rule(String::from(
+                "Não há mais vagas para o horário escolhido",
+            )));
+        }
+
+        Ok(())
+    }
+
+    // Pedidos agendados no intervalo que começa em 'start', sem contar os cancelados
+    async fn count_slot_orders<C: GenericClient>(
+        client: &C,
+        settings: &OrderScheduleSettings,
+        start: NaiveDateTime,
+    ) -> Result<i32, DataError> {
+        let query = format!(
+            "SELECT COUNT(*)::INTEGER FROM tbl_order o
+            WHERE o.scheduled_for >= $1::TEXT::TIMESTAMP AND o.scheduled_for < $1::TEXT::TIMESTAMP + make_interval(mins => $2)
+            AND NOT EXISTS (SELECT 1 FROM tbl_cart_item ci WHERE ci.id_purchase = o.id_purchase AND ci.status = {})",
+            STATUS_CANCELLED
+        );
+
+        let count: i32 = client
+            .query_one(
+                query.as_str(),
+                &[
+                    &start.format(DATETIME_FORMAT).to_string(),
+                    &settings.slot_minutes(),
+                ],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+
+        Ok(count)
+    }
+
+    // Horário local, pausa em vigor e horário de funcionamento.
+    // A pausa termina sozinha quando chega o horário de retorno.
+    async fn read_state_with<C: GenericClient>(
+        client: &C,
+    ) -> Result<(NaiveDateTime, Option<StorePause>, StoreHours), DataError> {
+        let query = format!(
+            "SELECT to_char({now}, 'YYYY-MM-DD HH24:MI:SS') AS now,
+            paused AND (resume_at IS NULL OR resume_at > {now}) AS paused, pause_reason,
+            to_char(resume_at, 'YYYY-MM-DD HH24:MI:SS') AS resume_at
+            FROM tbl_store_settings WHERE id = 1",
+            now = DbSchedule::local_now()
+        );
+
+        let row = client
+            .query_one(query.as_str(), &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let now: String = row.get("now");
+        let now = NaiveDateTime::parse_from_str(&now, DATETIME_FORMAT)
+            .map_err(|e| DataError::Db(format!("Failed to read current time: {}", e)))?;
+
+        let paused: bool = row.get("paused");
+        let pause = match paused {
+            true => Some(StorePause::new(row.get("pause_reason"), row.get("resume_at")).map_err(DataError::invalid_row)?),
+            false => None,
+        };
+
+        let hours = Self::read_hours_with(client).await?;
+
+        Ok((now, pause, hours))
+    }
+
+    async fn read_schedule_settings_with<C: GenericClient>(
+        client: &C,
+    ) -> Result<OrderScheduleSettings, DataError> {
+        let query = "SELECT schedule_slot_minutes, schedule_slot_capacity, schedule_lead_minutes, schedule_min_advance_minutes, schedule_max_days_ahead
+        FROM tbl_store_settings WHERE id = 1";
+
+        let row = client
+            .query_one(query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        OrderScheduleSettings::new(
+            row.get("schedule_slot_minutes"),
+            row.get("schedule_slot_capacity"),
+            row.get("schedule_lead_minutes"),
+            row.get("schedule_min_advance_minutes"),
+            row.get("schedule_max_days_ahead"),
+        )
+        .map_err(DataError::invalid_row)
+    }
+
+    async fn read_hours_with<C: GenericClient>(client: &C) -> Result<StoreHours, DataError> {
+        let hours_query = "SELECT weekday, to_char(open_time, 'HH24:MI') AS open_time, to_char(close_time, 'HH24:MI') AS close_time
+        FROM tbl_opening_hours ORDER BY weekday, open_time";
+        let mut opening_hours = Vec::new();
+
+        for row in client
+            .query(hours_query, &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            opening_hours.push(ScheduleRange::new(
+                row.get("weekday"),
+                row.get("open_time"),
+                row.get("close_time"),
+            ).map_err(DataError::invalid_row)?);
+        }
+
+        // O dia anterior é mantido para os intervalos que passam da meia-noite
+        let holiday_query = format!(
+            "SELECT to_char(date, 'YYYY-MM-DD') AS date, description FROM tbl_holiday
+            WHERE date >= {}::DATE - 1 ORDER BY date",
+            DbSchedule::local_now()
+        );
+        let mut holidays = Vec::new();
+
+        for row in client
+            .query(holiday_query.as_str(), &[])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            holidays.push(Holiday::new(row.get("date"), row.get("description")).map_err(DataError::invalid_row)?);
+        }
+
+        StoreHours::new(opening_hours, holidays).map_err(DataError::invalid_row)
+    }
+}
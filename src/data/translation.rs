@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use tokio_postgres::GenericClient;
+
+use super::database::Db;
+use super::error::DataError;
+use crate::models::{category::Category, product::Product, translation::Translation};
+
+// Dono das traduções: um produto ou uma categoria
+#[derive(Debug, Clone, Copy)]
+pub enum TranslationOwner {
+    Product,
+    Category,
+}
+
+impl TranslationOwner {
+    fn column(&self) -> &'static str {
+        match self {
+            TranslationOwner::Product => "product_id",
+            TranslationOwner::Category => "category_id",
+        }
+    }
+
+    // Produtos excluídos não recebem traduções
+    fn exists_query(&self) -> &'static str {
+        match self {
+            TranslationOwner::Product => {
+                "SELECT EXISTS (SELECT 1 FROM tbl_product WHERE id = $1 AND deleted_at IS NULL)"
+            }
+            TranslationOwner::Category => {
+                "SELECT EXISTS (SELECT 1 FROM tbl_category WHERE id = $1)"
+            }
+        }
+    }
+}
+
+pub struct DbTranslation {}
+
+impl DbTranslation {
+    pub async fn read(owner: TranslationOwner, id: i32) -> Result<Vec<Translation>, DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!(
+            "SELECT locale, name, description FROM tbl_translation WHERE {} = $1 ORDER BY locale",
+            owner.column()
+        );
+        let mut translations = Vec::new();
+
+        for row in client
+            .query(query.as_str(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            translations.push(
+                Translation::new(row.get("locale"), row.get("name"), row.get("description"))
+                    .map_err(DataError::invalid_row)?,
+            );
+        }
+
+        Ok(translations)
+    }
+
+    // Substitui as traduções do produto ou categoria
+    pub async fn replace(
+        owner: TranslationOwner,
+        id: i32,
+        translations: Vec<Translation>,
+    ) -> Result<(), DataError> {
+        let mut client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let transaction = client
+            .transaction()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to start transaction: {}", e)))?;
+
+        let column = owner.column();
+
+        let exists: bool = transaction
+            .query_one(owner.exists_query(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get(0);
+        if !exists {
+            return match owner {
+                TranslationOwner::Product => {
+                    Err(DataError::NotFound("Product not found.".to_string()))
+                }
+                TranslationOwner::Category => {
+                    Err(DataError::NotFound("Category not found.".to_string()))
+                }
+            };
+        }
+
+        transaction
+            .execute(
+                format!("DELETE FROM tbl_translation WHERE {} = $1", column).as_str(),
+                &[&id],
+            )
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        let insert_query = format!(
+            "INSERT INTO tbl_translation ({}, locale, name, description) VALUES ($1, $2, $3, $4)",
+            column
+        );
+        for translation in translations {
+            transaction
+                .execute(
+                    insert_query.as_str(),
+                    &[
+                        &id,
+                        &translation.locale(),
+                        &translation.name(),
+                        &translation.description(),
+                    ],
+                )
+                .await
+                .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+        }
+
+        transaction
+            .commit()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to commit transaction: {}", e)))?;
+
+        Ok(())
+    }
+
+    // Troca o nome e a descrição dos produtos pela tradução mais próxima do início da cadeia de idiomas.
+    // Produtos sem tradução em nenhum dos idiomas mantêm o conteúdo original.
+    pub async fn translate_products<C: GenericClient>(
+        client: &C,
+        products: &mut [Product],
+        locales: &[String],
+    ) -> Result<(), DataError> {
+        let ids: Vec<i32> = products.iter().filter_map(|product| product.id()).collect();
+        let mut translations =
+            Self::best_with(client, TranslationOwner::Product, &ids, locales).await?;
+
+        for product in products.iter_mut() {
+            if let Some(translation) = product.id().and_then(|id| translations.remove(&id)) {
+                product
+                    .set_name(translation.name())
+                    .map_err(DataError::invalid_row)?;
+                if let Some(description) = translation.description() {
+                    product
+                        .set_description(description)
+                        .map_err(DataError::invalid_row)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Troca o nome e a descrição das categorias, como em 'translate_products'
+    pub async fn translate_categories<C: GenericClient>(
+        client: &C,
+        categories: &mut [Category],
+        locales: &[String],
+    ) -> Result<(), DataError> {
+        let ids: Vec<i32> = categories
+            .iter()
+            .filter_map(|category| category.id())
+            .collect();
+        let mut translations =
+            Self::best_with(client, TranslationOwner::Category, &ids, locales).await?;
+
+        for category in categories.iter_mut() {
+            if let Some(translation) = category.id().and_then(|id| translations.remove(&id)) {
+                category
+                    .set_name(translation.name())
+                    .map_err(DataError::invalid_row)?;
+                if let Some(description) = translation.description() {
+                    category
+                        .set_description(description)
+                        .map_err(DataError::invalid_row)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Tradução de cada item no primeiro idioma da cadeia em que ela existe, pelo id do item
+    async fn best_with<C: GenericClient>(
+        client: &C,
+        owner: TranslationOwner,
+        ids: &[i32],
+        locales: &[String],
+    ) -> Result<BTreeMap<i32, Translation>, DataError> {
+        let mut translations = BTreeMap::new();
+        if ids.is_empty() || locales.is_empty() {
+            return Ok(translations);
+        }
+
+        let query = format!(
+            "SELECT DISTINCT ON ({column}) {column} AS id, locale, name, description FROM tbl_translation
+            WHERE {column} = ANY($1) AND locale = ANY($2)
+            ORDER BY {column}, array_position($2, locale)",
+            column = owner.column()
+        );
+
+        for row in client
+            .query(query.as_str(), &[&ids, &locales])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+        {
+            translations.insert(
+                row.get("id"),
+                Translation::new(row.get("locale"), row.get("name"), row.get("description"))
+                    .map_err(DataError::invalid_row)?,
+            );
+        }
+
+        Ok(translations)
+    }
+}
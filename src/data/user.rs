@@ -1,22 +1,27 @@
 use crate::data::database::Db;
+use crate::data::payment_method::DbPaymentMethod;
+use crate::data::schedule::DbSchedule;
 use crate::models::user::User;
+use super::error::DataError;
+
 
 pub struct DbUser {}
 
 impl DbUser {
-    pub async fn create(new_user: User) -> Result<(), String> {
+    // Retorna o usuário gravado, com o id criado
+    pub async fn create(new_user: User) -> Result<User, DataError> {
         if Self::username_exists(&new_user.username()).await? {
-            return Err("Username already exists.".to_string());
+            return Err(DataError::Conflict("Username already exists.".to_string()));
         }
 
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "INSERT INTO tbl_user (name, username, password, email, phone, address, acess) VALUES ($1, $2, $3, $4, $5, $6, $7)";
+        let query = "INSERT INTO tbl_user (name, username, password, email, phone, address, acess) VALUES ($1, $2, $3, $4, $5, $6, $7) RETURNING id";
 
-        client
-            .execute(
+        let user_id: i32 = client
+            .query_one(
                 query,
                 &[
                     &new_user.name(),
@@ -29,22 +34,24 @@ impl DbUser {
                 ],
             )
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .get("id");
 
-        Ok(())
+        Self::read(user_id).await
     }
 
-    pub async fn read(id: i32) -> Result<User, String> {
+    pub async fn read(id: i32) -> Result<User, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "SELECT id, name, username, password, email, phone, address, acess FROM tbl_user WHERE id = $1";
+        let query = "SELECT id, name, username, password, email, phone, address, acess FROM tbl_user WHERE id = $1 AND deleted_at IS NULL";
 
         let row = client
-            .query_one(query, &[&id])
+            .query_opt(query, &[&id])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("User not found.".to_string()))?;
 
         Ok(User::new(
             row.get("id"),
@@ -54,17 +61,18 @@ impl DbUser {
             row.get("email"),
             row.get("phone"),
             row.get("address"),
-            Vec::new(),
+            DbPaymentMethod::read_with(&client, id).await?,
             row.get("acess"),
-        )?)
+        ).map_err(DataError::invalid_row)?)
     }
 
-    pub async fn update(new_user: User) -> Result<(), String> {
+    // Os métodos de pagamento não fazem parte do perfil, são alterados pelas rotas '/payment_method'
+    pub async fn update(new_user: User) -> Result<(), DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "UPDATE tbl_user SET name = $1, password = $3, email = $4, phone = $5, address = $6, acess = $7 WHERE username = $2";
+        let query = "UPDATE tbl_user SET name = $1, password = $3, email = $4, phone = $5, address = $6, acess = $7 WHERE username = $2 AND deleted_at IS NULL";
 
         let rows_affected = client
             .execute(
@@ -80,109 +88,160 @@ impl DbUser {
                 ],
             )
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
 
         if rows_affected != 1 {
-            return Err("Failed to update, incorrect data.".to_string());
+            return Err(DataError::NotFound("Failed to update, incorrect data.".to_string()));
         }
 
         Ok(())
     }
 
-    pub async fn update_password(email: &str, new_password: &str) -> Result<(), String> {
+    pub async fn update_password(email: &str, new_password: &str) -> Result<(), DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "UPDATE tbl_user SET password = $1 WHERE email = $2";
+        let query = "UPDATE tbl_user SET password = $1 WHERE email = $2 AND deleted_at IS NULL";
 
         let rows_affected = client
             .execute(query, &[&new_password, &email])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("Falha ao atualizar a senha, e-mail ou dados incorretos.".to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Exclusão lógica: o usuário não consegue mais entrar e deixa de aparecer nas consultas, mas os seus
+    // pedidos continuam no histórico. Pode ser restaurado até a tarefa 'purge_deleted' apagar os dados.
+    pub async fn delete(id: i32) -> Result<(), DataError> {
+        let client = Db::connect()
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
+
+        let query = format!(
+            "UPDATE tbl_user SET deleted_at = {} WHERE id = $1 AND deleted_at IS NULL",
+            DbSchedule::local_now()
+        );
+
+        let rows_affected = client
+            .execute(query.as_str(), &[&id])
+            .await
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
 
         if rows_affected != 1 {
-            return Err("Falha ao atualizar a senha, e-mail ou dados incorretos.".to_string());
+            return Err(DataError::NotFound("User not found.".to_string()));
         }
 
         Ok(())
     }
 
-    pub async fn delete(id: i32) -> Result<(), String> {
+    // Desfaz a exclusão do usuário, enquanto os seus dados ainda não foram apagados
+    pub async fn restore(id: i32) -> Result<(), DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "DELETE FROM tbl_user WHERE id = $1";
+        let query = "UPDATE tbl_user SET deleted_at = NULL WHERE id = $1 AND deleted_at IS NOT NULL AND purged_at IS NULL";
 
-        client
+        let rows_affected = client
             .execute(query, &[&id])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?;
+
+        if rows_affected != 1 {
+            return Err(DataError::NotFound("User not found.".to_string()));
+        }
 
         Ok(())
     }
 
-    pub async fn username_exists(username: &str) -> Result<bool, String> {
+    // Usuários excluídos continuam reservando o nome de usuário, para que possam ser restaurados
+
+    pub async fn username_exists(username: &str) -> Result<bool, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
         let query = "SELECT EXISTS (SELECT 1 FROM tbl_user WHERE username = $1)";
 
         let exists: bool = client
             .query_one(query, &[&username])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
             .get(0);
 
         Ok(exists)
     }
 
-    pub async fn autenticate(username: String, password: String) -> Result<i32, String> {
+    pub async fn autenticate(username: String, password: String) -> Result<i32, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "SELECT id FROM tbl_user WHERE username = $1 AND password = $2";
+        let query = "SELECT id FROM tbl_user WHERE username = $1 AND password = $2 AND deleted_at IS NULL";
 
         match client
             .query_opt(query, &[&username, &password])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
         {
             Some(row) => Ok(row.get("id")),
-            None => Err("Invalid username or password".to_string()),
+            None => Err(DataError::Rule("Invalid username or password".to_string())),
         }
     }
 
-    pub async fn read_acess(id: i32) -> Result<i32, String> {
+    pub async fn read_acess(id: i32) -> Result<i32, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "SELECT acess FROM tbl_user WHERE id = $1";
+        let query = "SELECT acess FROM tbl_user WHERE id = $1 AND deleted_at IS NULL";
 
         let row = client
-            .query_one(query, &[&id])
+            .query_opt(query, &[&id])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
+            .ok_or_else(|| DataError::NotFound("User not found.".to_string()))?;
 
         Ok(row.get("acess"))
     }
 
-    pub async fn read_admins() -> Result<Vec<User>, String> {
+    // Funcionários (acess = 1) e administradores (acess = 2)
+    pub async fn is_staff(id: i32) -> Result<bool, DataError> {
+        Ok(matches!(Self::read_staff_acess(id).await?, Some(1) | Some(2)))
+    }
+
+    // Administradores (acess = 2)
+    pub async fn is_admin(id: i32) -> Result<bool, DataError> {
+        Ok(Self::read_staff_acess(id).await? == Some(2))
+    }
+
+    // Usuário removido com um token ainda válido não é funcionário, em vez de 'não encontrado'
+    async fn read_staff_acess(id: i32) -> Result<Option<i32>, DataError> {
+        match Self::read_acess(id).await {
+            Ok(acess) => Ok(Some(acess)),
+            Err(DataError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn read_admins() -> Result<Vec<User>, DataError> {
         let client = Db::connect()
             .await
-            .map_err(|e| format!("Failed to connect to database: {}", e))?;
+            .map_err(|e| DataError::Db(format!("Failed to connect to database: {}", e)))?;
 
-        let query = "SELECT id, name, username, password, email, phone, address, acess FROM tbl_user WHERE acess = 1 OR acess = 2";
+        let query = "SELECT id, name, username, password, email, phone, address, acess FROM tbl_user WHERE (acess = 1 OR acess = 2) AND deleted_at IS NULL";
         let mut users = Vec::new();
 
         for row in client
             .query(query, &[])
             .await
-            .map_err(|e| format!("Failed to execute query: {}", e))?
+            .map_err(|e| DataError::Db(format!("Failed to execute query: {}", e)))?
         {
             let user = User::new(
                 row.get("id"),
@@ -194,7 +253,7 @@ impl DbUser {
                 row.get("address"),
                 Vec::new(),
                 row.get("acess"),
-            )?;
+            ).map_err(DataError::invalid_row)?;
 
             users.push(user);
         }
@@ -0,0 +1,66 @@
+use std::future::Future;
+use std::pin::Pin;
+use tokio::time::{self, Duration, MissedTickBehavior};
+
+use crate::data::{archive::DbArchive, error::DataError, historic::DbHistoric, loyalty::DbLoyalty};
+use crate::models::{archive::retention_days, tokens::expire_tokens};
+
+type JobFuture = Pin<Box<dyn Future<Output = Result<(), DataError>>>>;
+
+// Tarefa executada periodicamente em segundo plano
+pub struct Job {
+    name: &'static str,
+    interval: Duration,
+    run: fn() -> JobFuture,
+}
+
+impl Job {
+    pub fn new(name: &'static str, interval: Duration, run: fn() -> JobFuture) -> Job {
+        Job {
+            name,
+            interval,
+            run,
+        }
+    }
+}
+
+// Tarefas do servidor
+pub fn jobs() -> Vec<Job> {
+    vec![
+        Job::new("expire_tokens", Duration::from_secs(300), || {
+            Box::pin(async {
+                expire_tokens();
+                Ok(())
+            })
+        }),
+        Job::new("expire_loyalty_points", Duration::from_secs(300), || {
+            Box::pin(async { DbLoyalty::expire_points().await.map(|_| ()) })
+        }),
+        Job::new("release_scheduled_orders", Duration::from_secs(60), || {
+            Box::pin(async { DbHistoric::release_scheduled().await.map(|_| ()) })
+        }),
+        Job::new("purge_deleted", Duration::from_secs(3600), || {
+            Box::pin(async { DbArchive::purge(retention_days()).await.map(|_| ()) })
+        }),
+    ]
+}
+
+// Inicia cada tarefa no seu intervalo. Uma falha é registrada e a tarefa roda de novo no próximo intervalo;
+// uma execução demorada adia a seguinte em vez de acumular execuções.
+pub fn spawn(jobs: Vec<Job>) {
+    for job in jobs {
+        actix_web::rt::spawn(async move {
+            let mut interval = time::interval(job.interval);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // O primeiro intervalo termina imediatamente
+            interval.tick().await;
+
+            loop {
+                interval.tick().await;
+                if let Err(e) = (job.run)().await {
+                    log::error!("Job '{}' failed: {}", job.name, e);
+                }
+            }
+        });
+    }
+}
@@ -1,54 +1,120 @@
 use actix_cors::Cors;
 use actix_web::{middleware::Logger, App, HttpServer};
-use models::tokens::expire_tokens;
 use std::env;
-use tokio::time::Duration;
 use crate::routes::user::RecoveryCodes;
 use actix_web::web;
 
 mod data {
+    pub mod address;
+    pub mod archive;
+    pub mod blob_store;
+    pub mod bundle;
+    pub mod catalog;
+    pub mod category;
+    pub mod courier;
     pub mod database;
+    pub mod delivery;
+    pub mod delivery_zone;
+    pub mod error;
     pub mod historic;
+    pub mod loyalty;
+    pub mod menu_audit;
+    pub mod option_group;
+    pub mod payment_method;
     pub mod product;
+    pub mod promotion;
+    pub mod schedule;
+    pub mod stock;
+    pub mod store;
+    pub mod translation;
     pub mod user;
 }
 mod models {
+    pub mod address;
+    pub mod api_error;
+    pub mod archive;
+    pub mod bundle;
+    pub mod catalog;
+    pub mod category;
+    pub mod courier;
     pub mod credentials;
+    pub mod delivery;
+    pub mod delivery_zone;
+    pub mod image;
     pub mod login_response;
+    pub mod loyalty;
+    pub mod menu_audit;
+    pub mod money;
+    pub mod nutrition;
+    pub mod option_group;
+    pub mod order;
+    pub mod order_total;
+    pub mod page;
+    pub mod payment_method;
     pub mod product;
     pub mod product_cart;
     pub mod product_cart_update;
+    pub mod product_listing;
+    pub mod product_search;
+    pub mod promotion;
+    pub mod schedule;
+    pub mod stock;
+    pub mod store;
     pub mod tokens;
+    pub mod translation;
     pub mod user;
+    pub mod validation;
 }
 mod routes {
+    pub mod address;
+    pub mod archive;
     pub mod auth;
+    pub mod catalog;
+    pub mod category;
+    pub mod courier;
+    pub mod delivery;
+    pub mod delivery_zone;
     pub mod history;
+    pub mod image;
+    pub mod loyalty;
+    pub mod menu_audit;
+    pub mod payment_method;
     pub mod product;
+    pub mod promotion;
+    pub mod schedule;
+    pub mod stock;
+    pub mod store;
+    pub mod translation;
     pub mod user;
 }
+mod cli;
 mod config;
+mod jobs;
+mod request_context;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
+    // Com argumentos executa um comando, como a importação do catálogo, em vez do servidor
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(args).await.map_err(std::io::Error::other);
+    }
+
     // Criação do objeto compartilhado para armazenar os códigos de recuperação
     let recovery_codes = web::Data::new(RecoveryCodes::new());
 
-    // Logs no terminal
-    env::set_var("RUST_LOG", "actix_web=debug,my_app=info");
+    // Logs no terminal. Os erros da aplicação são registrados pela crate 'log'.
+    env::set_var("RUST_LOG", "actix_web=debug,backend=info");
     env_logger::init();
 
-    // Inicializa a verificação do token
-    actix_web::rt::spawn(async move {
-        loop {
-            tokio::time::sleep(Duration::from_secs(300)).await;
-            expire_tokens();
-        }
-    });
+    // Inicializa as tarefas em segundo plano: validade dos tokens e dos pontos de fidelidade
+    // e envio dos pedidos agendados à cozinha
+    jobs::spawn(jobs::jobs());
 
     HttpServer::new(move || {
         App::new()
             .app_data(recovery_codes.clone())
+            .wrap_fn(request_context::middleware)
             .wrap(Logger::default())
             .wrap(
                 Cors::default()
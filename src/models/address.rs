@@ -0,0 +1,299 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+use super::validation::{FieldError, ValidationErrors};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Address {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    #[serde(default)]
+    label: String,
+    street: String,
+    number: String,
+    #[serde(default)]
+    complement: String,
+    neighborhood: String,
+    city: String,
+    cep: String,
+    #[serde(default)]
+    latitude: Option<f64>,
+    #[serde(default)]
+    longitude: Option<f64>,
+    #[serde(default)]
+    is_default: bool,
+}
+
+impl Address {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<i32>,
+        label: String,
+        street: String,
+        number: String,
+        complement: String,
+        neighborhood: String,
+        city: String,
+        cep: String,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+        is_default: bool,
+    ) -> Result<Address, ValidationErrors> {
+        let mut new_address = Address {
+            id,
+            label: String::new(),
+            street: String::new(),
+            number: String::new(),
+            complement: String::new(),
+            neighborhood: String::new(),
+            city: String::new(),
+            cep: String::new(),
+            latitude: None,
+            longitude: None,
+            is_default: false,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("label", new_address.set_label(label));
+        errors.check("street", new_address.set_street(street));
+        errors.check("number", new_address.set_number(number));
+        errors.check("complement", new_address.set_complement(complement));
+        errors.check("neighborhood", new_address.set_neighborhood(neighborhood));
+        errors.check("city", new_address.set_city(city));
+        errors.check("cep", new_address.set_cep(cep));
+        // O erro das coordenadas já indica se é da latitude ou da longitude
+        errors.check("", new_address.set_coordinates(latitude, longitude));
+        new_address.set_is_default(is_default);
+
+        errors.result(new_address)
+    }
+
+    pub fn from_json(json: web::Json<Address>) -> Result<Address, ValidationErrors> {
+        Address::new(
+            json.id(),
+            json.label(),
+            json.street(),
+            json.number(),
+            json.complement(),
+            json.neighborhood(),
+            json.city(),
+            json.cep(),
+            json.latitude(),
+            json.longitude(),
+            json.is_default(),
+        )
+    }
+
+    // Endereço em texto livre do perfil do usuário, usado nos pedidos de quem ainda não cadastrou
+    // endereços. Não passa pelas validações dos campos, que o texto antigo não segue.
+    pub fn legacy(address: String) -> Address {
+        Address {
+            id: None,
+            label: String::new(),
+            street: address.chars().take(255).collect(),
+            number: String::from("S/N"),
+            complement: String::new(),
+            neighborhood: String::new(),
+            city: String::new(),
+            cep: String::new(),
+            latitude: None,
+            longitude: None,
+            is_default: false,
+        }
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn label(&self) -> String {
+        self.label.clone()
+    }
+    pub fn street(&self) -> String {
+        self.street.clone()
+    }
+    pub fn number(&self) -> String {
+        self.number.clone()
+    }
+    pub fn complement(&self) -> String {
+        self.complement.clone()
+    }
+    pub fn neighborhood(&self) -> String {
+        self.neighborhood.clone()
+    }
+    pub fn city(&self) -> String {
+        self.city.clone()
+    }
+    pub fn cep(&self) -> String {
+        self.cep.clone()
+    }
+    pub fn latitude(&self) -> Option<f64> {
+        self.latitude
+    }
+    pub fn longitude(&self) -> Option<f64> {
+        self.longitude
+    }
+    pub fn is_default(&self) -> bool {
+        self.is_default
+    }
+
+    // Set
+    pub fn set_label(&mut self, label: String) -> Result<(), FieldError> {
+        Self::validate_label(&label)?;
+        self.label = label;
+        Ok(())
+    }
+    pub fn set_street(&mut self, street: String) -> Result<(), FieldError> {
+        Self::validate_street(&street)?;
+        self.street = street;
+        Ok(())
+    }
+    pub fn set_number(&mut self, number: String) -> Result<(), FieldError> {
+        Self::validate_number(&number)?;
+        self.number = number;
+        Ok(())
+    }
+    pub fn set_complement(&mut self, complement: String) -> Result<(), FieldError> {
+        Self::validate_complement(&complement)?;
+        self.complement = complement;
+        Ok(())
+    }
+    pub fn set_neighborhood(&mut self, neighborhood: String) -> Result<(), FieldError> {
+        Self::validate_neighborhood(&neighborhood)?;
+        self.neighborhood = neighborhood;
+        Ok(())
+    }
+    pub fn set_city(&mut self, city: String) -> Result<(), FieldError> {
+        Self::validate_city(&city)?;
+        self.city = city;
+        Ok(())
+    }
+    // O CEP é salvo apenas com os 8 dígitos, aceitando a entrada no formato '00000-000'
+    pub fn set_cep(&mut self, cep: String) -> Result<(), FieldError> {
+        Self::validate_cep(&cep)?;
+        self.cep = cep.chars().filter(|c| c.is_ascii_digit()).collect();
+        Ok(())
+    }
+    pub fn set_coordinates(
+        &mut self,
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+    ) -> Result<(), FieldError> {
+        Self::validate_coordinates(latitude, longitude)?;
+        self.latitude = latitude;
+        self.longitude = longitude;
+        Ok(())
+    }
+    pub fn set_is_default(&mut self, is_default: bool) {
+        self.is_default = is_default;
+    }
+
+    // Validate
+    fn validate_label(label: &str) -> Result<(), FieldError> {
+        if label.len() > 30 {
+            return Err(FieldError::too_long(
+                "O campo 'label' deve ter no máximo 30 caracteres",
+                30,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_street(street: &str) -> Result<(), FieldError> {
+        if street.trim().is_empty() {
+            return Err(FieldError::required(
+                "O campo 'street' não pode estar vazio",
+            ));
+        }
+        if street.len() > 255 {
+            return Err(FieldError::too_long(
+                "O campo 'street' deve ter no máximo 255 caracteres",
+                255,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_number(number: &str) -> Result<(), FieldError> {
+        if number.trim().is_empty() {
+            return Err(FieldError::required(
+                "O campo 'number' não pode estar vazio, use 'S/N' se não houver número",
+            ));
+        }
+        if number.len() > 10 {
+            return Err(FieldError::too_long(
+                "O campo 'number' deve ter no máximo 10 caracteres",
+                10,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_complement(complement: &str) -> Result<(), FieldError> {
+        if complement.len() > 100 {
+            return Err(FieldError::too_long(
+                "O campo 'complement' deve ter no máximo 100 caracteres",
+                100,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_neighborhood(neighborhood: &str) -> Result<(), FieldError> {
+        if neighborhood.trim().is_empty() {
+            return Err(FieldError::required(
+                "O campo 'neighborhood' não pode estar vazio",
+            ));
+        }
+        Ok(())
+    }
+    fn validate_city(city: &str) -> Result<(), FieldError> {
+        if city.trim().is_empty() {
+            return Err(FieldError::required("O campo 'city' não pode estar vazio"));
+        }
+        Ok(())
+    }
+    fn validate_cep(cep: &str) -> Result<(), FieldError> {
+        let valid = match cep.len() {
+            8 => cep.chars().all(|c| c.is_ascii_digit()),
+            9 => cep.char_indices().all(|(i, c)| match i {
+                5 => c == '-',
+                _ => c.is_ascii_digit(),
+            }),
+            _ => false,
+        };
+        if !valid {
+            return Err(FieldError::invalid_format(
+                "O campo 'cep' deve estar no formato '00000-000' ou '00000000'",
+            ));
+        }
+        Ok(())
+    }
+    fn validate_coordinates(
+        latitude: Option<f64>,
+        longitude: Option<f64>,
+    ) -> Result<(), FieldError> {
+        let out_of_range = |field: &str, max: f64| {
+            FieldError::new(
+                "out_of_range",
+                String::from("Os campos 'latitude' e 'longitude' estão fora do intervalo válido"),
+            )
+            .with_param("min", -max)
+            .with_param("max", max)
+            .at(field)
+        };
+        let missing = |field: &str| {
+            FieldError::required("Os campos 'latitude' e 'longitude' devem ser informados juntos")
+                .at(field)
+        };
+
+        match (latitude, longitude) {
+            (None, None) => Ok(()),
+            (Some(lat), Some(_)) if !(-90.0..=90.0).contains(&lat) => {
+                Err(out_of_range("latitude", 90.0))
+            }
+            (Some(_), Some(lon)) if !(-180.0..=180.0).contains(&lon) => {
+                Err(out_of_range("longitude", 180.0))
+            }
+            (Some(_), Some(_)) => Ok(()),
+            (Some(_), None) => Err(missing("longitude")),
+            (None, Some(_)) => Err(missing("latitude")),
+        }
+    }
+}
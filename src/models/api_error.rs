@@ -0,0 +1,151 @@
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use serde::Serialize;
+use std::fmt;
+
+use super::validation::{FieldError, ValidationErrors};
+use crate::data::error::DataError;
+use crate::request_context::RequestContext;
+
+// Erros retornados pela API. Cada variante tem um código estável para os aplicativos e uma mensagem
+// no idioma da requisição; os detalhes trazem os erros de validação de cada campo dos dados.
+#[derive(Debug)]
+pub enum ApiError {
+    Validation(Vec<FieldError>),
+    Unauthorized,
+    NotFound(String),
+    Conflict(String),
+    Unprocessable(String),
+    PayloadTooLarge(String),
+    Internal(String),
+}
+
+impl ApiError {
+    // Erro de validação com uma mensagem sem campo, como as retornadas pelas regras do pedido
+    pub fn validation(message: String) -> ApiError {
+        ApiError::Validation(vec![FieldError::new("invalid", message)])
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::Validation(_) => "validation_failed",
+            ApiError::Unauthorized => "unauthorized",
+            ApiError::NotFound(_) => "not_found",
+            ApiError::Conflict(_) => "conflict",
+            ApiError::Unprocessable(_) => "unprocessable",
+            ApiError::PayloadTooLarge(_) => "payload_too_large",
+            ApiError::Internal(_) => "internal_error",
+        }
+    }
+
+    // Mensagem geral do erro em português ou inglês
+    fn message(&self, english: bool) -> &'static str {
+        match (self, english) {
+            (ApiError::Validation(_), false) => "Os dados enviados são inválidos.",
+            (ApiError::Validation(_), true) => "The submitted data is invalid.",
+            (ApiError::Unauthorized, false) => "Token inválido ou usuário sem permissão.",
+            (ApiError::Unauthorized, true) => "Invalid token or insufficient permissions.",
+            (ApiError::NotFound(_), false) => "Registro não encontrado.",
+            (ApiError::NotFound(_), true) => "Record not found.",
+            (ApiError::Conflict(_), false) => "O registro já existe.",
+            (ApiError::Conflict(_), true) => "The record already exists.",
+            (ApiError::Unprocessable(_), false) => {
+                "A operação não pode ser realizada no estado atual."
+            }
+            (ApiError::Unprocessable(_), true) => {
+                "The operation cannot be performed in the current state."
+            }
+            (ApiError::PayloadTooLarge(_), false) => "O conteúdo enviado é grande demais.",
+            (ApiError::PayloadTooLarge(_), true) => "The submitted content is too large.",
+            (ApiError::Internal(_), false) => "Erro interno do servidor.",
+            (ApiError::Internal(_), true) => "Internal server error.",
+        }
+    }
+
+    // Detalhes enviados ao cliente. Mensagens internas, que podem conter o texto dos erros do
+    // banco de dados, ficam apenas no log do servidor.
+    fn details(&self) -> Vec<FieldError> {
+        let message = match self {
+            ApiError::Validation(details) => return details.clone(),
+            ApiError::Unauthorized | ApiError::Internal(_) => return Vec::new(),
+            ApiError::NotFound(message)
+            | ApiError::Conflict(message)
+            | ApiError::Unprocessable(message)
+            | ApiError::PayloadTooLarge(message) => message,
+        };
+
+        match message.is_empty() {
+            true => Vec::new(),
+            false => vec![FieldError::new(self.code(), message.clone())],
+        }
+    }
+
+    fn internal_message(&self) -> Option<&str> {
+        match self {
+            ApiError::Internal(message) => Some(message),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message(false))
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorBody {
+    code: &'static str,
+    message: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<FieldError>,
+    request_id: String,
+}
+
+// Erros dos construtores dos modelos, com todos os campos inválidos
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> ApiError {
+        ApiError::Validation(errors.errors())
+    }
+}
+
+// Erros da camada de dados. As falhas do banco viram erro interno; as regras do negócio, erro de validação.
+impl From<DataError> for ApiError {
+    fn from(error: DataError) -> ApiError {
+        match error {
+            DataError::Db(message) => ApiError::Internal(message),
+            DataError::NotFound(message) => ApiError::NotFound(message),
+            DataError::Conflict(message) => ApiError::Conflict(message),
+            DataError::Rule(message) => ApiError::validation(message),
+        }
+    }
+}
+
+impl ResponseError for ApiError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::Unauthorized => StatusCode::UNAUTHORIZED,
+            ApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::Conflict(_) => StatusCode::CONFLICT,
+            ApiError::Unprocessable(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ApiError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let context = RequestContext::current();
+
+        if let Some(message) = self.internal_message() {
+            log::error!("Request {} failed: {}", context.request_id(), message);
+        }
+
+        HttpResponse::build(self.status_code()).json(ErrorBody {
+            code: self.code(),
+            message: self.message(context.english()),
+            details: self.details(),
+            request_id: context.request_id(),
+        })
+    }
+}
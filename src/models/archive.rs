@@ -0,0 +1,36 @@
+use lazy_static::lazy_static;
+use serde::Serialize;
+use std::env;
+
+lazy_static! {
+    // Dias em que produtos e usuários excluídos podem ser restaurados antes da remoção definitiva
+    static ref RETENTION_DAYS: i32 = env::var("SOFT_DELETE_RETENTION_DAYS")
+        .ok()
+        .and_then(|days| days.parse().ok())
+        .filter(|days| *days >= 0)
+        .unwrap_or(30);
+}
+
+pub fn retention_days() -> i32 {
+    *RETENTION_DAYS
+}
+
+// Produto ou usuário excluído, com o momento em que será removido definitivamente
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchivedItem {
+    id: i32,
+    name: String,
+    deleted_at: String,
+    purge_at: String,
+}
+
+impl ArchivedItem {
+    pub fn new(id: i32, name: String, deleted_at: String, purge_at: String) -> ArchivedItem {
+        ArchivedItem {
+            id,
+            name,
+            deleted_at,
+            purge_at,
+        }
+    }
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+use super::validation::{FieldError, ValidationErrors};
+
+// Parte de um combo. Com um único produto a parte é fixa (o bife do prato feito),
+// com vários o cliente escolhe um deles (a bebida).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSlot {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    name: String,
+    #[serde(default = "default_quantity")]
+    quantity: i32,
+    product_ids: Vec<i32>,
+}
+
+fn default_quantity() -> i32 {
+    1
+}
+
+impl BundleSlot {
+    pub fn new(
+        id: Option<i32>,
+        name: String,
+        quantity: i32,
+        product_ids: Vec<i32>,
+    ) -> Result<BundleSlot, ValidationErrors> {
+        let mut new_slot = BundleSlot {
+            id,
+            name: String::new(),
+            quantity: 0,
+            product_ids: Vec::new(),
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_slot.set_name(name));
+        errors.check("quantity", new_slot.set_quantity(quantity));
+        errors.check("product_ids", new_slot.set_product_ids(product_ids));
+
+        errors.result(new_slot)
+    }
+
+    // Reconstrói pelo construtor uma parte recebida no JSON, que não passou pelas validações
+    pub fn rebuild(&self) -> Result<BundleSlot, ValidationErrors> {
+        BundleSlot::new(
+            self.id,
+            self.name.clone(),
+            self.quantity,
+            self.product_ids.clone(),
+        )
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn quantity(&self) -> i32 {
+        self.quantity
+    }
+    pub fn product_ids(&self) -> Vec<i32> {
+        self.product_ids.clone()
+    }
+    pub fn is_fixed(&self) -> bool {
+        self.product_ids.len() == 1
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        Self::validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_quantity(&mut self, quantity: i32) -> Result<(), FieldError> {
+        Self::validate_quantity(quantity)?;
+        self.quantity = quantity;
+        Ok(())
+    }
+    pub fn set_product_ids(&mut self, product_ids: Vec<i32>) -> Result<(), FieldError> {
+        Self::validate_product_ids(&product_ids)?;
+        self.product_ids = product_ids;
+        Ok(())
+    }
+
+    // Validate
+    fn validate_name(name: &str) -> Result<(), FieldError> {
+        if name.trim().is_empty() {
+            return Err(FieldError::required("Bundle slot name cannot be empty"));
+        }
+        if name.chars().count() > 50 {
+            return Err(FieldError::too_long(
+                "Bundle slot name cannot exceed 50 characters",
+                50,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_quantity(quantity: i32) -> Result<(), FieldError> {
+        if quantity <= 0 {
+            return Err(FieldError::min(
+                "Bundle slot quantity must be greater than 0",
+                1,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_product_ids(product_ids: &[i32]) -> Result<(), FieldError> {
+        if product_ids.is_empty() {
+            return Err(FieldError::required(
+                "Bundle slot must have at least one product",
+            ));
+        }
+        for (i, product_id) in product_ids.iter().enumerate() {
+            if product_ids[..i].contains(product_id) {
+                return Err(FieldError::duplicate(format!(
+                    "Product {} is repeated in the bundle slot",
+                    product_id
+                ))
+                .at(&format!("[{}]", i)));
+            }
+        }
+        Ok(())
+    }
+}
+
+// Produto de um combo no item do pedido, usado pela cozinha.
+// O aplicativo envia apenas 'slot_id' e 'product_id' das partes com escolha,
+// o servidor completa as partes fixas e os demais campos.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleComponent {
+    slot_id: i32,
+    product_id: i32,
+    #[serde(default)]
+    slot: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    quantity: i32,
+}
+
+impl BundleComponent {
+    pub fn new(
+        slot_id: i32,
+        product_id: i32,
+        slot: String,
+        name: String,
+        quantity: i32,
+    ) -> BundleComponent {
+        BundleComponent {
+            slot_id,
+            product_id,
+            slot,
+            name,
+            quantity,
+        }
+    }
+
+    // Get
+    pub fn slot_id(&self) -> i32 {
+        self.slot_id
+    }
+    pub fn product_id(&self) -> i32 {
+        self.product_id
+    }
+    pub fn quantity(&self) -> i32 {
+        self.quantity
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) {
+        self.name = name;
+    }
+}
+
+// Confere as escolhas do cliente e retorna o produto de cada parte do combo com o nome da parte.
+// O nome do produto é preenchido depois, com os dados do banco.
+pub fn select_components(
+    slots: &[BundleSlot],
+    choices: &[BundleComponent],
+) -> Result<Vec<BundleComponent>, String> {
+    for choice in choices {
+        if !slots.iter().any(|s| s.id() == Some(choice.slot_id())) {
+            return Err(format!("Parte {} não pertence ao combo", choice.slot_id()));
+        }
+    }
+
+    let mut components = Vec::new();
+    for slot in slots {
+        let slot_id = slot.id().unwrap_or_default();
+        let chosen: Vec<&BundleComponent> =
+            choices.iter().filter(|c| c.slot_id() == slot_id).collect();
+
+        let product_id = match (chosen.as_slice(), slot.is_fixed()) {
+            ([], true) => slot.product_ids[0],
+            ([], false) => return Err(format!("Escolha uma opção de '{}'", slot.name())),
+            ([choice], _) if slot.product_ids.contains(&choice.product_id()) => choice.product_id(),
+            ([_], _) => {
+                return Err(format!(
+                    "Produto escolhido não pertence a '{}'",
+                    slot.name()
+                ))
+            }
+            _ => return Err(format!("Escolha apenas uma opção de '{}'", slot.name())),
+        };
+
+        components.push(BundleComponent::new(
+            slot_id,
+            product_id,
+            slot.name(),
+            String::new(),
+            slot.quantity(),
+        ));
+    }
+
+    Ok(components)
+}
@@ -0,0 +1,719 @@
+use serde::{Deserialize, Serialize};
+
+use super::category::Category;
+use super::money::Money;
+use super::nutrition::{Allergen, NutritionFacts};
+use super::option_group::{OptionGroup, ProductOption};
+use super::product::Product;
+use super::validation::ValidationErrors;
+
+// Separador dos alergênicos nas colunas do CSV
+const CSV_LIST_SEPARATOR: char = '|';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogFormat {
+    Csv,
+    Json,
+}
+
+impl CatalogFormat {
+    pub fn parse(format: &str) -> Result<CatalogFormat, String> {
+        match format {
+            "csv" => Ok(CatalogFormat::Csv),
+            "json" => Ok(CatalogFormat::Json),
+            _ => Err(format!("Invalid catalog format '{}'", format)),
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            CatalogFormat::Csv => "text/csv; charset=utf-8",
+            CatalogFormat::Json => "application/json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            CatalogFormat::Csv => "csv",
+            CatalogFormat::Json => "json",
+        }
+    }
+}
+
+// Parâmetros '?format=csv|json&dry_run=false' da exportação e da importação do catálogo
+#[derive(Debug, Deserialize)]
+pub struct CatalogQuery {
+    format: Option<String>,
+    dry_run: Option<bool>,
+}
+
+impl CatalogQuery {
+    // JSON quando o formato não é informado
+    pub fn format(&self) -> Result<CatalogFormat, String> {
+        CatalogFormat::parse(self.format.as_deref().unwrap_or("json"))
+    }
+    // A importação só grava as alterações com 'dry_run=false'
+    pub fn dry_run(&self) -> bool {
+        self.dry_run.unwrap_or(true)
+    }
+}
+
+// Produto do catálogo, identificado pelo SKU e ligado à categoria pelo nome.
+// Os combos não fazem parte do catálogo e as partes dos combos existentes são mantidas na importação.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogProduct {
+    sku: String,
+    name: String,
+    price: Money,
+    description: String,
+    image: String,
+    available: bool,
+    #[serde(default)]
+    category: Option<String>,
+    #[serde(default)]
+    allergens: Vec<Allergen>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nutrition: Option<NutritionFacts>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    option_groups: Vec<OptionGroup>,
+    // Linha do arquivo importado, para os erros encontrados ao gravar
+    #[serde(skip)]
+    row: usize,
+}
+
+impl CatalogProduct {
+    // Exporta o produto sem os ids das opções, que só valem neste banco de dados
+    pub fn from_product(
+        product: &Product,
+        category: Option<String>,
+    ) -> Result<CatalogProduct, String> {
+        Ok(CatalogProduct {
+            sku: product.sku().unwrap_or_default(),
+            name: product.name(),
+            price: product.price(),
+            description: product.description(),
+            image: product.image(),
+            available: product.available(),
+            category,
+            allergens: product.allergens(),
+            nutrition: product.nutrition(),
+            option_groups: product
+                .option_groups()
+                .iter()
+                .map(|group| with_option_ids(group, None))
+                .collect::<Result<Vec<OptionGroup>, ValidationErrors>>()?,
+            row: 0,
+        })
+    }
+
+    // Monta o produto com as validações do cadastro. As opções com o mesmo grupo e nome das opções
+    // atuais ('current') mantêm os ids, para que os aplicativos com o cardápio em cache continuem válidos.
+    pub fn product(
+        &self,
+        category_id: Option<i32>,
+        current: Option<&Product>,
+    ) -> Result<Product, String> {
+        let current_groups = current.map(|p| p.option_groups()).unwrap_or_default();
+        let option_groups = self
+            .option_groups
+            .iter()
+            .map(|group| {
+                let current = current_groups.iter().find(|g| g.name() == group.name());
+                with_option_ids(group, current)
+            })
+            .collect::<Result<Vec<OptionGroup>, ValidationErrors>>()?;
+
+        Product::new(
+            current.and_then(|p| p.id()),
+            Some(self.sku.clone()),
+            self.name.clone(),
+            self.price,
+            self.description.clone(),
+            self.image.clone(),
+            self.available,
+            category_id,
+            option_groups,
+            current.map(|p| p.bundle()).unwrap_or_default(),
+            self.allergens.clone(),
+            self.nutrition,
+        )
+        .map_err(String::from)
+    }
+
+    // Get
+    pub fn sku(&self) -> String {
+        self.sku.clone()
+    }
+    pub fn category(&self) -> Option<String> {
+        self.category.clone()
+    }
+    pub fn row(&self) -> usize {
+        self.row
+    }
+}
+
+// Cópia do grupo com os ids do grupo atual de mesmo nome, ou sem ids
+fn with_option_ids(
+    group: &OptionGroup,
+    current: Option<&OptionGroup>,
+) -> Result<OptionGroup, ValidationErrors> {
+    let current_options = current.map(|g| g.options()).unwrap_or_default();
+    let mut options = Vec::new();
+
+    for option in group.options() {
+        let id = current_options
+            .iter()
+            .find(|o| o.name() == option.name())
+            .and_then(|o| o.id());
+        options.push(ProductOption::new(
+            id,
+            option.name(),
+            option.price_delta(),
+            option.available(),
+        )?);
+    }
+
+    OptionGroup::new(
+        current.and_then(|g| g.id()),
+        group.name(),
+        group.min_select(),
+        group.max_select(),
+        options,
+    )
+}
+
+// Cardápio completo para exportação e importação
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Catalog {
+    #[serde(default)]
+    categories: Vec<Category>,
+    #[serde(default)]
+    products: Vec<CatalogProduct>,
+    // Linha de cada categoria no arquivo importado
+    #[serde(skip)]
+    category_rows: Vec<usize>,
+}
+
+impl Catalog {
+    pub fn new(categories: Vec<Category>, products: Vec<CatalogProduct>) -> Catalog {
+        Catalog {
+            category_rows: vec![0; categories.len()],
+            categories,
+            products,
+        }
+    }
+
+    // Get
+    pub fn categories(&self) -> Vec<Category> {
+        self.categories.clone()
+    }
+    pub fn products(&self) -> Vec<CatalogProduct> {
+        self.products.clone()
+    }
+    pub fn category_row(&self, index: usize) -> usize {
+        self.category_rows.get(index).copied().unwrap_or_default()
+    }
+
+    // Lê o catálogo, validando cada linha. Retorna o catálogo com as linhas válidas e os erros das demais.
+    pub fn parse(
+        format: CatalogFormat,
+        data: &[u8],
+    ) -> Result<(Catalog, Vec<CatalogError>), String> {
+        match format {
+            CatalogFormat::Json => Self::parse_json(data),
+            CatalogFormat::Csv => Self::parse_csv(data),
+        }
+    }
+
+    pub fn write(&self, format: CatalogFormat) -> Result<Vec<u8>, String> {
+        match format {
+            CatalogFormat::Json => serde_json::to_vec_pretty(self)
+                .map_err(|e| format!("Failed to write catalog: {}", e)),
+            CatalogFormat::Csv => self.write_csv(),
+        }
+    }
+
+    fn parse_json(data: &[u8]) -> Result<(Catalog, Vec<CatalogError>), String> {
+        #[derive(Deserialize)]
+        struct RawCatalog {
+            #[serde(default)]
+            categories: Vec<serde_json::Value>,
+            #[serde(default)]
+            products: Vec<serde_json::Value>,
+        }
+
+        let raw: RawCatalog =
+            serde_json::from_slice(data).map_err(|e| format!("Invalid catalog: {}", e))?;
+        let mut catalog = Catalog::default();
+        let mut errors = Vec::new();
+
+        for (i, value) in raw.categories.into_iter().enumerate() {
+            let key = Self::json_key(&value, "name");
+            match serde_json::from_value::<Category>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|c| {
+                    Category::new(
+                        None,
+                        c.name(),
+                        c.description(),
+                        c.icon(),
+                        c.position(),
+                        c.available(),
+                    )
+                    .map_err(String::from)
+                }) {
+                Ok(category) => catalog.push_category(i + 1, category),
+                Err(e) => errors.push(CatalogError::new(i + 1, CatalogEntity::Category, key, e)),
+            }
+        }
+
+        for (i, value) in raw.products.into_iter().enumerate() {
+            let key = Self::json_key(&value, "sku");
+            match serde_json::from_value::<CatalogProduct>(value)
+                .map_err(|e| e.to_string())
+                .and_then(|p| p.product(None, None).map(|_| p))
+            {
+                Ok(product) => catalog.push_product(i + 1, product),
+                Err(e) => errors.push(CatalogError::new(i + 1, CatalogEntity::Product, key, e)),
+            }
+        }
+
+        catalog.check_duplicates(&mut errors);
+        Ok((catalog, errors))
+    }
+
+    fn json_key(value: &serde_json::Value, field: &str) -> String {
+        value
+            .get(field)
+            .and_then(|key| key.as_str())
+            .unwrap_or_default()
+            .to_string()
+    }
+
+    // No CSV as categorias e os produtos ficam no mesmo arquivo, diferenciados pela coluna 'kind'.
+    // A primeira linha do arquivo é o cabeçalho, por isso os dados começam na linha 2.
+    fn parse_csv(data: &[u8]) -> Result<(Catalog, Vec<CatalogError>), String> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(data);
+        let mut catalog = Catalog::default();
+        let mut errors = Vec::new();
+
+        for (i, record) in reader.deserialize::<CsvRow>().enumerate() {
+            let line = i + 2;
+            let row = match record {
+                Ok(row) => row,
+                Err(e) => {
+                    errors.push(CatalogError::new(
+                        line,
+                        CatalogEntity::Product,
+                        String::new(),
+                        format!("Invalid row: {}", e),
+                    ));
+                    continue;
+                }
+            };
+
+            match row.kind.as_str() {
+                "category" => match row.category() {
+                    Ok(category) => catalog.push_category(line, category),
+                    Err(e) => errors.push(CatalogError::new(
+                        line,
+                        CatalogEntity::Category,
+                        row.name,
+                        e,
+                    )),
+                },
+                "product" => match row
+                    .catalog_product()
+                    .and_then(|p| p.product(None, None).map(|_| p))
+                {
+                    Ok(product) => catalog.push_product(line, product),
+                    Err(e) => {
+                        errors.push(CatalogError::new(line, CatalogEntity::Product, row.sku, e))
+                    }
+                },
+                kind => errors.push(CatalogError::new(
+                    line,
+                    CatalogEntity::Product,
+                    row.sku.clone(),
+                    format!(
+                        "Invalid row kind '{}', expected 'category' or 'product'",
+                        kind
+                    ),
+                )),
+            }
+        }
+
+        catalog.check_duplicates(&mut errors);
+        Ok((catalog, errors))
+    }
+
+    fn push_category(&mut self, row: usize, category: Category) {
+        self.categories.push(category);
+        self.category_rows.push(row);
+    }
+
+    fn push_product(&mut self, row: usize, mut product: CatalogProduct) {
+        product.row = row;
+        self.products.push(product);
+    }
+
+    fn write_csv(&self) -> Result<Vec<u8>, String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        for category in self.categories.iter() {
+            writer
+                .serialize(CsvRow::from_category(category))
+                .map_err(|e| format!("Failed to write catalog: {}", e))?;
+        }
+        for product in self.products.iter() {
+            writer
+                .serialize(CsvRow::from_catalog_product(product)?)
+                .map_err(|e| format!("Failed to write catalog: {}", e))?;
+        }
+
+        writer
+            .into_inner()
+            .map_err(|e| format!("Failed to write catalog: {}", e))
+    }
+
+    // Categorias com o mesmo nome e produtos com o mesmo SKU não podem se repetir no arquivo
+    fn check_duplicates(&self, errors: &mut Vec<CatalogError>) {
+        for (i, category) in self.categories.iter().enumerate() {
+            if self.categories[..i]
+                .iter()
+                .any(|c| c.name() == category.name())
+            {
+                errors.push(CatalogError::new(
+                    self.category_row(i),
+                    CatalogEntity::Category,
+                    category.name(),
+                    String::from("Category is repeated in the catalog"),
+                ));
+            }
+        }
+        for (i, product) in self.products.iter().enumerate() {
+            if self.products[..i].iter().any(|p| p.sku == product.sku) {
+                errors.push(CatalogError::new(
+                    product.row,
+                    CatalogEntity::Product,
+                    product.sku(),
+                    String::from("SKU is repeated in the catalog"),
+                ));
+            }
+        }
+    }
+}
+
+// Linha do CSV. As colunas que não se aplicam ao tipo da linha ficam vazias.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CsvRow {
+    kind: String,
+    #[serde(default)]
+    sku: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    price: String,
+    #[serde(default)]
+    image: String,
+    #[serde(default)]
+    available: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    position: String,
+    #[serde(default)]
+    allergens: String,
+    #[serde(default)]
+    portion_grams: String,
+    #[serde(default)]
+    kcal: String,
+    #[serde(default)]
+    protein: String,
+    #[serde(default)]
+    carbs: String,
+    #[serde(default)]
+    fat: String,
+    // Grupos de opções em JSON, no mesmo formato da API de produtos
+    #[serde(default)]
+    option_groups: String,
+}
+
+impl CsvRow {
+    fn from_category(category: &Category) -> CsvRow {
+        CsvRow {
+            kind: String::from("category"),
+            name: category.name(),
+            description: category.description(),
+            available: category.available().to_string(),
+            icon: category.icon(),
+            position: category.position().to_string(),
+            ..CsvRow::default()
+        }
+    }
+
+    fn from_catalog_product(product: &CatalogProduct) -> Result<CsvRow, String> {
+        let number = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        let nutrition = product.nutrition;
+
+        Ok(CsvRow {
+            kind: String::from("product"),
+            sku: product.sku.clone(),
+            name: product.name.clone(),
+            description: product.description.clone(),
+            price: product.price.to_string(),
+            image: product.image.clone(),
+            available: product.available.to_string(),
+            category: product.category.clone().unwrap_or_default(),
+            allergens: product
+                .allergens
+                .iter()
+                .map(|allergen| allergen.as_str())
+                .collect::<Vec<&str>>()
+                .join(&CSV_LIST_SEPARATOR.to_string()),
+            portion_grams: number(nutrition.map(|n| n.portion_grams())),
+            kcal: number(nutrition.map(|n| n.kcal())),
+            protein: number(nutrition.map(|n| n.protein())),
+            carbs: number(nutrition.map(|n| n.carbs())),
+            fat: number(nutrition.map(|n| n.fat())),
+            option_groups: match product.option_groups.is_empty() {
+                true => String::new(),
+                false => serde_json::to_string(&product.option_groups)
+                    .map_err(|e| format!("Failed to write catalog: {}", e))?,
+            },
+            ..CsvRow::default()
+        })
+    }
+
+    fn category(&self) -> Result<Category, String> {
+        Category::new(
+            None,
+            self.name.clone(),
+            self.description.clone(),
+            self.icon.clone(),
+            match self.position.is_empty() {
+                true => 0,
+                false => self
+                    .position
+                    .parse()
+                    .map_err(|_| format!("Invalid position '{}'", self.position))?,
+            },
+            Self::parse_bool(&self.available)?,
+        )
+        .map_err(String::from)
+    }
+
+    fn catalog_product(&self) -> Result<CatalogProduct, String> {
+        let nutrition_columns = [
+            &self.portion_grams,
+            &self.kcal,
+            &self.protein,
+            &self.carbs,
+            &self.fat,
+        ];
+        let nutrition = match nutrition_columns.iter().all(|value| value.is_empty()) {
+            true => None,
+            false => {
+                let mut values = Vec::new();
+                for value in nutrition_columns {
+                    values.push(
+                        value
+                            .replace(',', ".")
+                            .parse::<f64>()
+                            .map_err(|_| format!("Invalid nutrition value '{}'", value))?,
+                    );
+                }
+                Some(
+                    NutritionFacts::new(values[0], values[1], values[2], values[3], values[4])
+                        .map_err(|e| e.to_string())?,
+                )
+            }
+        };
+
+        Ok(CatalogProduct {
+            sku: self.sku.clone(),
+            name: self.name.clone(),
+            price: Money::parse(&self.price)?,
+            description: self.description.clone(),
+            image: self.image.clone(),
+            available: Self::parse_bool(&self.available)?,
+            category: Some(self.category.clone()).filter(|category| !category.is_empty()),
+            allergens: Allergen::parse_list(&self.allergens.replace(CSV_LIST_SEPARATOR, ","))?,
+            nutrition,
+            option_groups: match self.option_groups.is_empty() {
+                true => Vec::new(),
+                false => serde_json::from_str(&self.option_groups)
+                    .map_err(|e| format!("Invalid option groups: {}", e))?,
+            },
+            row: 0,
+        })
+    }
+
+    fn parse_bool(value: &str) -> Result<bool, String> {
+        match value.to_lowercase().as_str() {
+            "true" | "1" | "sim" => Ok(true),
+            "false" | "0" | "nao" | "não" | "" => Ok(false),
+            _ => Err(format!("Invalid availability '{}'", value)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogEntity {
+    Category,
+    Product,
+}
+
+// Linha do arquivo que não pôde ser importada. 'row' é a linha no CSV ou a posição na lista do JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogError {
+    row: usize,
+    entity: CatalogEntity,
+    key: String,
+    message: String,
+}
+
+impl CatalogError {
+    pub fn new(row: usize, entity: CatalogEntity, key: String, message: String) -> CatalogError {
+        CatalogError {
+            row,
+            entity,
+            key,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CatalogAction {
+    Create,
+    Update,
+    Unchanged,
+}
+
+// O que a importação faz com cada categoria (pelo nome) ou produto (pelo SKU), com os campos alterados
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogChange {
+    entity: CatalogEntity,
+    key: String,
+    action: CatalogAction,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    fields: Vec<&'static str>,
+}
+
+impl CatalogChange {
+    pub fn new(
+        entity: CatalogEntity,
+        key: String,
+        action: CatalogAction,
+        fields: Vec<&'static str>,
+    ) -> CatalogChange {
+        CatalogChange {
+            entity,
+            key,
+            action,
+            fields,
+        }
+    }
+}
+
+// Resultado da importação. Com 'dry_run' ou com erros nada é gravado.
+#[derive(Debug, Clone, Serialize)]
+pub struct CatalogImport {
+    dry_run: bool,
+    applied: bool,
+    changes: Vec<CatalogChange>,
+    errors: Vec<CatalogError>,
+}
+
+impl CatalogImport {
+    pub fn new(
+        dry_run: bool,
+        applied: bool,
+        changes: Vec<CatalogChange>,
+        errors: Vec<CatalogError>,
+    ) -> CatalogImport {
+        CatalogImport {
+            dry_run,
+            applied,
+            changes,
+            errors,
+        }
+    }
+
+    // Get
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+}
+
+// Campos do produto que mudam com a importação
+pub fn product_changes(current: &Product, new: &Product) -> Vec<&'static str> {
+    let groups = |product: &Product| {
+        let groups: Result<Vec<OptionGroup>, ValidationErrors> = product
+            .option_groups()
+            .iter()
+            .map(|group| with_option_ids(group, None))
+            .collect();
+        groups
+            .ok()
+            .and_then(|groups| serde_json::to_value(groups).ok())
+    };
+
+    let mut fields = Vec::new();
+    if current.name() != new.name() {
+        fields.push("name");
+    }
+    if current.price() != new.price() {
+        fields.push("price");
+    }
+    if current.description() != new.description() {
+        fields.push("description");
+    }
+    if current.image() != new.image() {
+        fields.push("image");
+    }
+    if current.available() != new.available() {
+        fields.push("available");
+    }
+    if current.category_id() != new.category_id() {
+        fields.push("category");
+    }
+    if current.allergens() != new.allergens() {
+        fields.push("allergens");
+    }
+    if current.nutrition() != new.nutrition() {
+        fields.push("nutrition");
+    }
+    if groups(current) != groups(new) {
+        fields.push("option_groups");
+    }
+    fields
+}
+
+// Campos da categoria que mudam com a importação
+pub fn category_changes(current: &Category, new: &Category) -> Vec<&'static str> {
+    let mut fields = Vec::new();
+    if current.description() != new.description() {
+        fields.push("description");
+    }
+    if current.icon() != new.icon() {
+        fields.push("icon");
+    }
+    if current.position() != new.position() {
+        fields.push("position");
+    }
+    if current.available() != new.available() {
+        fields.push("available");
+    }
+    fields
+}
@@ -0,0 +1,174 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+use super::product::Product;
+use super::validation::{FieldError, ValidationErrors};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Category {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    icon: String,
+    // Posição da seção no cardápio, menores primeiro
+    #[serde(default)]
+    position: i32,
+    available: bool,
+}
+
+impl Category {
+    pub fn new(
+        id: Option<i32>,
+        name: String,
+        description: String,
+        icon: String,
+        position: i32,
+        available: bool,
+    ) -> Result<Category, ValidationErrors> {
+        let mut new_category = Category {
+            id,
+            name: String::new(),
+            description: String::new(),
+            icon: String::new(),
+            position: 0,
+            available: false,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_category.set_name(name));
+        errors.check("description", new_category.set_description(description));
+        errors.check("icon", new_category.set_icon(icon));
+        errors.check("position", new_category.set_position(position));
+        new_category.set_available(available);
+
+        errors.result(new_category)
+    }
+
+    pub fn from_json(json: web::Json<Category>) -> Result<Category, ValidationErrors> {
+        Category::new(
+            json.id(),
+            json.name(),
+            json.description(),
+            json.icon(),
+            json.position(),
+            json.available(),
+        )
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn description(&self) -> String {
+        self.description.clone()
+    }
+    pub fn icon(&self) -> String {
+        self.icon.clone()
+    }
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+    pub fn available(&self) -> bool {
+        self.available
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        Self::validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_description(&mut self, description: String) -> Result<(), FieldError> {
+        Self::validate_description(&description)?;
+        self.description = description;
+        Ok(())
+    }
+    pub fn set_icon(&mut self, icon: String) -> Result<(), FieldError> {
+        Self::validate_icon(&icon)?;
+        self.icon = icon;
+        Ok(())
+    }
+    pub fn set_position(&mut self, position: i32) -> Result<(), FieldError> {
+        Self::validate_position(position)?;
+        self.position = position;
+        Ok(())
+    }
+    pub fn set_available(&mut self, available: bool) {
+        self.available = available;
+    }
+
+    // Validate
+    fn validate_name(name: &str) -> Result<(), FieldError> {
+        if name.trim().is_empty() {
+            return Err(FieldError::required("Category name cannot be empty"));
+        }
+        if name.chars().count() > 50 {
+            return Err(FieldError::too_long(
+                "Category name cannot exceed 50 characters",
+                50,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_description(description: &str) -> Result<(), FieldError> {
+        if description.chars().count() > 255 {
+            return Err(FieldError::too_long(
+                "Category description cannot exceed 255 characters",
+                255,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_icon(icon: &str) -> Result<(), FieldError> {
+        if icon.chars().count() > 255 {
+            return Err(FieldError::too_long(
+                "Category icon cannot exceed 255 characters",
+                255,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_position(position: i32) -> Result<(), FieldError> {
+        if position < 0 {
+            return Err(FieldError::min("Category position cannot be negative", 0));
+        }
+        Ok(())
+    }
+}
+
+// Seção do cardápio: a categoria com os seus produtos disponíveis
+#[derive(Debug, Clone, Serialize)]
+pub struct MenuSection {
+    #[serde(flatten)]
+    category: Category,
+    products: Vec<Product>,
+}
+
+impl MenuSection {
+    pub fn new(category: Category, products: Vec<Product>) -> MenuSection {
+        MenuSection { category, products }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Menu {
+    sections: Vec<MenuSection>,
+    // Produtos disponíveis que ainda não foram colocados em uma categoria
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    uncategorized: Vec<Product>,
+}
+
+impl Menu {
+    pub fn new(sections: Vec<MenuSection>, uncategorized: Vec<Product>) -> Menu {
+        Menu {
+            sections,
+            uncategorized,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Courier {
+    id: i32,
+    name: String,
+    phone: String,
+    available: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location: Option<CourierLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location_updated_at: Option<String>,
+}
+
+impl Courier {
+    pub fn new(
+        id: i32,
+        name: String,
+        phone: String,
+        available: bool,
+        location: Option<CourierLocation>,
+        location_updated_at: Option<String>,
+    ) -> Courier {
+        Courier {
+            id,
+            name,
+            phone,
+            available,
+            location,
+            location_updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CourierLocation {
+    latitude: f64,
+    longitude: f64,
+}
+
+impl CourierLocation {
+    pub fn new(latitude: f64, longitude: f64) -> Result<CourierLocation, String> {
+        Self::validate_coordinates(latitude, longitude)?;
+        Ok(CourierLocation {
+            latitude,
+            longitude,
+        })
+    }
+
+    pub fn from_json(json: web::Json<CourierLocation>) -> Result<CourierLocation, String> {
+        CourierLocation::new(json.latitude(), json.longitude())
+    }
+
+    // Get
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    // Validate
+    fn validate_coordinates(latitude: f64, longitude: f64) -> Result<(), String> {
+        if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+            return Err(String::from(
+                "Os campos 'latitude' e 'longitude' estão fora do intervalo válido",
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use actix_web::web;
 use serde::{Deserialize, Serialize};
 
+use super::validation::{FieldError, ValidationErrors};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Credentials {
     username: String,
@@ -8,20 +10,20 @@ pub struct Credentials {
 }
 
 impl Credentials {
-    pub fn new(username: String, password: String) -> Result<Self, String> {
+    pub fn new(username: String, password: String) -> Result<Self, ValidationErrors> {
         let mut credentials = Self {
             username: String::new(),
             password: String::new(),
         };
-        credentials.set_username(username)?;
-        credentials.set_password(password)?;
+        let mut errors = ValidationErrors::new();
+        errors.check("username", credentials.set_username(username));
+        errors.check("password", credentials.set_password(password));
 
-        Ok(credentials)
+        errors.result(credentials)
     }
 
-    pub fn from_json(json: web::Json<Credentials>) -> Result<Credentials, String> {
-        let credentials = Credentials::new(json.username(), json.password())?;
-        Ok(credentials)
+    pub fn from_json(json: web::Json<Credentials>) -> Result<Credentials, ValidationErrors> {
+        Credentials::new(json.username(), json.password())
     }
 
     // Get
@@ -33,30 +35,32 @@ impl Credentials {
     }
 
     //Set
-    pub fn set_username(&mut self, username: String) -> Result<(), String> {
+    pub fn set_username(&mut self, username: String) -> Result<(), FieldError> {
         Self::validate_username(&username)?;
         self.username = username;
         Ok(())
     }
-    pub fn set_password(&mut self, password: String) -> Result<(), String> {
+    pub fn set_password(&mut self, password: String) -> Result<(), FieldError> {
         Self::validate_password(&password)?;
         self.password = password;
         Ok(())
     }
 
     // Validate
-    fn validate_username(username: &str) -> Result<(), String> {
+    fn validate_username(username: &str) -> Result<(), FieldError> {
         if username.len() > 50 {
-            return Err(String::from(
+            return Err(FieldError::too_long(
                 "O campo 'username' deve ter no máximo 50 caracteres",
+                50,
             ));
         }
         Ok(())
     }
-    fn validate_password(password: &str) -> Result<(), String> {
+    fn validate_password(password: &str) -> Result<(), FieldError> {
         if password.len() < 4 {
-            return Err(String::from(
+            return Err(FieldError::too_short(
                 "O campo 'password' deve ter pelo menos 8 caracteres",
+                4,
             ));
         }
         Ok(())
@@ -0,0 +1,140 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+use super::courier::CourierLocation;
+use super::validation::{FieldError, ValidationErrors};
+
+// Status dos itens do pedido controlados pela entrega
+pub const STATUS_OUT_FOR_DELIVERY: i32 = 3;
+pub const STATUS_DELIVERED: i32 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Delivery {
+    id_purchase: i32,
+    courier_id: i32,
+    courier_name: String,
+    assigned_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delivered_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recipient_name: Option<String>,
+    // Código exibido ao cliente e informado ao entregador na entrega
+    #[serde(skip_serializing_if = "Option::is_none")]
+    confirmation_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    courier_location: Option<CourierLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    location_updated_at: Option<String>,
+}
+
+impl Delivery {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id_purchase: i32,
+        courier_id: i32,
+        courier_name: String,
+        assigned_at: String,
+        delivered_at: Option<String>,
+        recipient_name: Option<String>,
+        confirmation_code: Option<String>,
+        courier_location: Option<CourierLocation>,
+        location_updated_at: Option<String>,
+    ) -> Delivery {
+        Delivery {
+            id_purchase,
+            courier_id,
+            courier_name,
+            assigned_at,
+            delivered_at,
+            recipient_name,
+            confirmation_code,
+            courier_location,
+            location_updated_at,
+        }
+    }
+
+    // Remove o código de confirmação antes de enviar os dados ao entregador
+    pub fn hide_confirmation_code(&mut self) {
+        self.confirmation_code = None;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryConfirmation {
+    confirmation_code: String,
+    recipient_name: String,
+}
+
+impl DeliveryConfirmation {
+    pub fn new(
+        confirmation_code: String,
+        recipient_name: String,
+    ) -> Result<DeliveryConfirmation, ValidationErrors> {
+        let mut confirmation = DeliveryConfirmation {
+            confirmation_code: String::new(),
+            recipient_name: String::new(),
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check(
+            "confirmation_code",
+            confirmation.set_confirmation_code(confirmation_code),
+        );
+        errors.check(
+            "recipient_name",
+            confirmation.set_recipient_name(recipient_name),
+        );
+
+        errors.result(confirmation)
+    }
+
+    pub fn from_json(
+        json: web::Json<DeliveryConfirmation>,
+    ) -> Result<DeliveryConfirmation, ValidationErrors> {
+        DeliveryConfirmation::new(json.confirmation_code(), json.recipient_name())
+    }
+
+    // Get
+    pub fn confirmation_code(&self) -> String {
+        self.confirmation_code.clone()
+    }
+    pub fn recipient_name(&self) -> String {
+        self.recipient_name.clone()
+    }
+
+    // Set
+    pub fn set_confirmation_code(&mut self, confirmation_code: String) -> Result<(), FieldError> {
+        Self::validate_confirmation_code(&confirmation_code)?;
+        self.confirmation_code = confirmation_code;
+        Ok(())
+    }
+    pub fn set_recipient_name(&mut self, recipient_name: String) -> Result<(), FieldError> {
+        Self::validate_recipient_name(&recipient_name)?;
+        self.recipient_name = recipient_name;
+        Ok(())
+    }
+
+    // Validate
+    fn validate_confirmation_code(confirmation_code: &str) -> Result<(), FieldError> {
+        if confirmation_code.len() != 4 || !confirmation_code.chars().all(|c| c.is_ascii_digit()) {
+            return Err(FieldError::invalid_format(
+                "O campo 'confirmation_code' deve ter 4 dígitos",
+            ));
+        }
+        Ok(())
+    }
+    fn validate_recipient_name(recipient_name: &str) -> Result<(), FieldError> {
+        if recipient_name.trim().is_empty() {
+            return Err(FieldError::required(
+                "O campo 'recipient_name' não pode estar vazio",
+            ));
+        }
+        if recipient_name.len() > 100 {
+            return Err(FieldError::too_long(
+                "O campo 'recipient_name' deve ter no máximo 100 caracteres",
+                100,
+            ));
+        }
+        Ok(())
+    }
+}
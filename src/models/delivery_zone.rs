@@ -0,0 +1,339 @@
+use actix_web::web;
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+use super::money::Money;
+use super::validation::{FieldError, ValidationErrors};
+
+lazy_static! {
+    // Coordenadas do restaurante, usadas como centro das zonas por raio
+    static ref RESTAURANT_COORDINATES: Option<(f64, f64)> = {
+        let latitude = env::var("RESTAURANT_LATITUDE").ok()?.parse().ok()?;
+        let longitude = env::var("RESTAURANT_LONGITUDE").ok()?.parse().ok()?;
+        Some((latitude, longitude))
+    };
+}
+
+pub fn restaurant_coordinates() -> Result<(f64, f64), String> {
+    RESTAURANT_COORDINATES.ok_or_else(|| {
+        String::from("Localização do restaurante não configurada (RESTAURANT_LATITUDE/RESTAURANT_LONGITUDE)")
+    })
+}
+
+// Distância em km entre dois pontos pela fórmula de haversine
+pub fn distance_km(latitude_a: f64, longitude_a: f64, latitude_b: f64, longitude_b: f64) -> f64 {
+    let d_lat = (latitude_b - latitude_a).to_radians();
+    let d_lon = (longitude_b - longitude_a).to_radians();
+    let a = (d_lat / 2.0).sin().powi(2)
+        + latitude_a.to_radians().cos() * latitude_b.to_radians().cos() * (d_lon / 2.0).sin().powi(2);
+
+    6371.0 * 2.0 * a.sqrt().atan2((1.0 - a).sqrt())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ZoneArea {
+    // Anel ao redor do restaurante, de min_km (inclusivo) até max_km (exclusivo)
+    Radius { min_km: f64, max_km: f64 },
+    // Polígono com os vértices no formato [latitude, longitude]
+    Polygon { points: Vec<[f64; 2]> },
+}
+
+impl ZoneArea {
+    pub fn contains(&self, latitude: f64, longitude: f64) -> Result<bool, String> {
+        match self {
+            ZoneArea::Radius { min_km, max_km } => {
+                let (restaurant_latitude, restaurant_longitude) = restaurant_coordinates()?;
+                let distance =
+                    distance_km(restaurant_latitude, restaurant_longitude, latitude, longitude);
+                Ok(distance >= *min_km && distance < *max_km)
+            }
+            ZoneArea::Polygon { points } => {
+                // Ray casting: conta quantas arestas são cruzadas a partir do ponto
+                let mut inside = false;
+                let mut j = points.len() - 1;
+                for i in 0..points.len() {
+                    let (lat_i, lon_i) = (points[i][0], points[i][1]);
+                    let (lat_j, lon_j) = (points[j][0], points[j][1]);
+                    if (lat_i > latitude) != (lat_j > latitude)
+                        && longitude < (lon_j - lon_i) * (latitude - lat_i) / (lat_j - lat_i) + lon_i
+                    {
+                        inside = !inside;
+                    }
+                    j = i;
+                }
+                Ok(inside)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryZone {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    name: String,
+    area: ZoneArea,
+    fee: Money,
+    min_order: Money,
+    eta_minutes: i32,
+    active: bool,
+}
+
+impl DeliveryZone {
+    pub fn new(
+        id: Option<i32>,
+        name: String,
+        area: ZoneArea,
+        fee: Money,
+        min_order: Money,
+        eta_minutes: i32,
+        active: bool,
+    ) -> Result<DeliveryZone, ValidationErrors> {
+        let mut new_zone = DeliveryZone {
+            id,
+            name: String::new(),
+            area: ZoneArea::Radius {
+                min_km: 0.0,
+                max_km: 0.0,
+            },
+            fee: Money::zero(),
+            min_order: Money::zero(),
+            eta_minutes: 0,
+            active: false,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_zone.set_name(name));
+        errors.check("area", new_zone.set_area(area));
+        errors.check("fee", new_zone.set_fee(fee));
+        errors.check("min_order", new_zone.set_min_order(min_order));
+        errors.check("eta_minutes", new_zone.set_eta_minutes(eta_minutes));
+        new_zone.set_active(active);
+
+        errors.result(new_zone)
+    }
+
+    pub fn from_json(json: web::Json<DeliveryZone>) -> Result<DeliveryZone, ValidationErrors> {
+        DeliveryZone::new(
+            json.id(),
+            json.name(),
+            json.area(),
+            json.fee(),
+            json.min_order(),
+            json.eta_minutes(),
+            json.active(),
+        )
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn area(&self) -> ZoneArea {
+        self.area.clone()
+    }
+    pub fn fee(&self) -> Money {
+        self.fee
+    }
+    pub fn min_order(&self) -> Money {
+        self.min_order
+    }
+    pub fn eta_minutes(&self) -> i32 {
+        self.eta_minutes
+    }
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        Self::validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_area(&mut self, area: ZoneArea) -> Result<(), FieldError> {
+        Self::validate_area(&area)?;
+        self.area = area;
+        Ok(())
+    }
+    pub fn set_fee(&mut self, fee: Money) -> Result<(), FieldError> {
+        Self::validate_fee(fee)?;
+        self.fee = fee;
+        Ok(())
+    }
+    pub fn set_min_order(&mut self, min_order: Money) -> Result<(), FieldError> {
+        Self::validate_min_order(min_order)?;
+        self.min_order = min_order;
+        Ok(())
+    }
+    pub fn set_eta_minutes(&mut self, eta_minutes: i32) -> Result<(), FieldError> {
+        Self::validate_eta_minutes(eta_minutes)?;
+        self.eta_minutes = eta_minutes;
+        Ok(())
+    }
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    // Validate
+    fn validate_name(name: &str) -> Result<(), FieldError> {
+        if name.is_empty() {
+            return Err(FieldError::required("Zone name cannot be empty"));
+        }
+        Ok(())
+    }
+    fn validate_area(area: &ZoneArea) -> Result<(), FieldError> {
+        match area {
+            ZoneArea::Radius { min_km, max_km } => {
+                if *min_km < 0.0 || max_km <= min_km {
+                    return Err(FieldError::new(
+                        "out_of_range",
+                        String::from("Zone radius must satisfy 0 <= min_km < max_km"),
+                    )
+                    .at("max_km"));
+                }
+            }
+            ZoneArea::Polygon { points } => {
+                if points.len() < 3 {
+                    return Err(FieldError::new(
+                        "too_short",
+                        String::from("Zone polygon must have at least 3 points"),
+                    )
+                    .with_param("min", 3)
+                    .at("points"));
+                }
+                if points.iter().any(|p| {
+                    !(-90.0..=90.0).contains(&p[0]) || !(-180.0..=180.0).contains(&p[1])
+                }) {
+                    return Err(FieldError::invalid_format("Zone polygon has invalid coordinates")
+                        .at("points"));
+                }
+            }
+        }
+        Ok(())
+    }
+    fn validate_fee(fee: Money) -> Result<(), FieldError> {
+        if fee.is_negative() {
+            return Err(FieldError::min("Zone fee cannot be negative", 0));
+        }
+        Ok(())
+    }
+    fn validate_min_order(min_order: Money) -> Result<(), FieldError> {
+        if min_order.is_negative() {
+            return Err(FieldError::min("Zone minimum order cannot be negative", 0));
+        }
+        Ok(())
+    }
+    fn validate_eta_minutes(eta_minutes: i32) -> Result<(), FieldError> {
+        if eta_minutes <= 0 {
+            return Err(FieldError::min("Zone ETA must be greater than 0", 1));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeliveryQuote {
+    zone_id: Option<i32>,
+    zone_name: String,
+    fee: Money,
+    min_order: Money,
+    eta_minutes: i32,
+}
+
+impl DeliveryQuote {
+    pub fn new(zone: &DeliveryZone) -> DeliveryQuote {
+        DeliveryQuote {
+            zone_id: zone.id(),
+            zone_name: zone.name(),
+            fee: zone.fee(),
+            min_order: zone.min_order(),
+            eta_minutes: zone.eta_minutes(),
+        }
+    }
+
+    // Get
+    pub fn zone_id(&self) -> Option<i32> {
+        self.zone_id
+    }
+    pub fn fee(&self) -> Money {
+        self.fee
+    }
+    pub fn min_order(&self) -> Money {
+        self.min_order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[[f64; 2]]) -> ZoneArea {
+        ZoneArea::Polygon {
+            points: points.to_vec(),
+        }
+    }
+
+    #[test]
+    fn distance_km_uses_the_earth_radius() {
+        assert_eq!(distance_km(-23.55, -46.63, -23.55, -46.63), 0.0);
+        // Um grau de latitude tem cerca de 111,19 km
+        assert!((distance_km(0.0, 0.0, 1.0, 0.0) - 111.19).abs() < 0.01);
+        // São Paulo (Praça da Sé) até o Rio de Janeiro (Centro)
+        let distance = distance_km(-23.5505, -46.6333, -22.9068, -43.1729);
+        assert!((distance - 360.7).abs() < 0.5, "distance = {}", distance);
+        let reverse = distance_km(-22.9068, -43.1729, -23.5505, -46.6333);
+        assert!((distance - reverse).abs() < 1e-9);
+    }
+
+    #[test]
+    fn polygon_contains_points_inside_a_square() {
+        let square = polygon(&[[0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]]);
+        assert_eq!(square.contains(0.5, 0.5), Ok(true));
+        assert_eq!(square.contains(0.01, 0.99), Ok(true));
+        assert_eq!(square.contains(1.5, 0.5), Ok(false));
+        assert_eq!(square.contains(0.5, -0.1), Ok(false));
+    }
+
+    #[test]
+    fn polygon_excludes_the_notch_of_a_concave_shape() {
+        // Formato de "U" aberto para o norte, entre as longitudes 1 e 2
+        let shape = polygon(&[
+            [0.0, 0.0],
+            [3.0, 0.0],
+            [3.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [3.0, 2.0],
+            [3.0, 3.0],
+            [0.0, 3.0],
+        ]);
+        assert_eq!(shape.contains(0.5, 1.5), Ok(true));
+        assert_eq!(shape.contains(2.0, 0.5), Ok(true));
+        assert_eq!(shape.contains(2.0, 1.5), Ok(false));
+        assert_eq!(shape.contains(2.0, 2.5), Ok(true));
+    }
+
+    #[test]
+    fn area_validation_rejects_open_polygons_and_empty_rings() {
+        assert!(DeliveryZone::validate_area(&polygon(&[[0.0, 0.0], [1.0, 1.0]])).is_err());
+        assert!(
+            DeliveryZone::validate_area(&polygon(&[[0.0, 0.0], [1.0, 1.0], [91.0, 0.0]])).is_err()
+        );
+        assert!(DeliveryZone::validate_area(&ZoneArea::Radius {
+            min_km: 2.0,
+            max_km: 2.0
+        })
+        .is_err());
+        assert!(DeliveryZone::validate_area(&ZoneArea::Radius {
+            min_km: 0.0,
+            max_km: 5.0
+        })
+        .is_ok());
+    }
+}
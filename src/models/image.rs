@@ -0,0 +1,192 @@
+use image::{imageops::FilterType, DynamicImage, ImageFormat, ImageOutputFormat};
+use serde::Serialize;
+use std::io::Cursor;
+
+// Tamanho máximo do arquivo enviado
+pub const MAX_UPLOAD_BYTES: usize = 5 * 1024 * 1024;
+
+// Tipos aceitos no envio, com o formato esperado no conteúdo do arquivo
+const ACCEPTED_TYPES: [(&str, ImageFormat); 3] = [
+    ("image/jpeg", ImageFormat::Jpeg),
+    ("image/png", ImageFormat::Png),
+    ("image/webp", ImageFormat::WebP),
+];
+
+// Menor lado aceito, para evitar fotos que ficam borradas no cardápio
+const MIN_DIMENSION: u32 = 200;
+// Maior lado aceito antes da decodificação, para evitar imagens que ocupam memória demais
+const MAX_DIMENSION: u32 = 8000;
+
+// Variantes geradas: nome, maior lado em pixels e se é uma miniatura WebP
+const VARIANTS: [(&str, u32, bool); 5] = [
+    ("large", 1200, false),
+    ("medium", 600, false),
+    ("small", 300, false),
+    ("thumb", 150, true),
+    ("thumb_2x", 300, true),
+];
+
+// Variante codificada, pronta para ser gravada
+pub struct EncodedImage {
+    name: &'static str,
+    width: u32,
+    height: u32,
+    content_type: &'static str,
+    extension: &'static str,
+    bytes: Vec<u8>,
+}
+
+impl EncodedImage {
+    // Get
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+    pub fn content_type(&self) -> &'static str {
+        self.content_type
+    }
+    pub fn extension(&self) -> &'static str {
+        self.extension
+    }
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+// Confere o tipo e o tamanho do arquivo e gera as variantes redimensionadas.
+// As imagens são decodificadas e codificadas de novo, o que descarta EXIF, XMP e demais metadados.
+pub fn process_upload(bytes: &[u8], content_type: &str) -> Result<Vec<EncodedImage>, String> {
+    if bytes.is_empty() {
+        return Err(String::from("Image file is empty"));
+    }
+    if bytes.len() > MAX_UPLOAD_BYTES {
+        return Err(format!(
+            "Image cannot exceed {} MB",
+            MAX_UPLOAD_BYTES / (1024 * 1024)
+        ));
+    }
+
+    let format = match ACCEPTED_TYPES.iter().find(|(mime, _)| *mime == content_type) {
+        Some((_, format)) => *format,
+        None => {
+            return Err(String::from(
+                "Image must be of type image/jpeg, image/png or image/webp",
+            ))
+        }
+    };
+    // O conteúdo precisa corresponder ao tipo informado
+    if image::guess_format(bytes).ok() != Some(format) {
+        return Err(format!("Image content does not match type {}", content_type));
+    }
+
+    let (width, height) = image::io::Reader::with_format(Cursor::new(bytes), format)
+        .into_dimensions()
+        .map_err(|e| format!("Invalid image: {}", e))?;
+    if width.max(height) > MAX_DIMENSION {
+        return Err(format!(
+            "Image cannot be larger than {}x{} pixels",
+            MAX_DIMENSION, MAX_DIMENSION
+        ));
+    }
+    if width.min(height) < MIN_DIMENSION {
+        return Err(format!(
+            "Image must be at least {}x{} pixels",
+            MIN_DIMENSION, MIN_DIMENSION
+        ));
+    }
+
+    let image = image::load_from_memory_with_format(bytes, format)
+        .map_err(|e| format!("Invalid image: {}", e))?;
+
+    VARIANTS
+        .iter()
+        .map(|(name, size, thumbnail)| encode_variant(&image, name, *size, *thumbnail))
+        .collect()
+}
+
+fn encode_variant(
+    image: &DynamicImage,
+    name: &'static str,
+    size: u32,
+    thumbnail: bool,
+) -> Result<EncodedImage, String> {
+    // Imagens menores que a variante não são ampliadas
+    let resized = match image.width().max(image.height()) > size {
+        true if thumbnail => image.thumbnail(size, size),
+        true => image.resize(size, size, FilterType::Lanczos3),
+        false => image.clone(),
+    };
+
+    // Fotos sem transparência viram JPEG; com transparência, PNG
+    let (resized, output, content_type, extension) = match (thumbnail, resized.color().has_alpha()) {
+        (true, _) => (resized, ImageOutputFormat::WebP, "image/webp", "webp"),
+        (false, true) => (resized, ImageOutputFormat::Png, "image/png", "png"),
+        (false, false) => (
+            DynamicImage::ImageRgb8(resized.to_rgb8()),
+            ImageOutputFormat::Jpeg(85),
+            "image/jpeg",
+            "jpg",
+        ),
+    };
+
+    let mut bytes = Cursor::new(Vec::new());
+    resized
+        .write_to(&mut bytes, output)
+        .map_err(|e| format!("Failed to encode image: {}", e))?;
+
+    Ok(EncodedImage {
+        name,
+        width: resized.width(),
+        height: resized.height(),
+        content_type,
+        extension,
+        bytes: bytes.into_inner(),
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImageVariant {
+    name: String,
+    width: u32,
+    height: u32,
+    url: String,
+}
+
+impl ImageVariant {
+    pub fn new(name: String, width: u32, height: u32, url: String) -> ImageVariant {
+        ImageVariant {
+            name,
+            width,
+            height,
+            url,
+        }
+    }
+}
+
+// Resultado do envio. 'image' é o valor usado em 'Product::image', relativo à pasta de imagens.
+#[derive(Debug, Clone, Serialize)]
+pub struct UploadedImage {
+    image: String,
+    url: String,
+    variants: Vec<ImageVariant>,
+}
+
+impl UploadedImage {
+    pub fn new(image: String, url: String, variants: Vec<ImageVariant>) -> UploadedImage {
+        UploadedImage {
+            image,
+            url,
+            variants,
+        }
+    }
+
+    // Get
+    pub fn image(&self) -> String {
+        self.image.clone()
+    }
+}
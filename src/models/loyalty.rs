@@ -0,0 +1,197 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+use super::validation::{FieldError, ValidationErrors};
+
+// Contas do livro de pontos. Cada transação lança valores opostos em duas contas, somando zero.
+pub const ACCOUNT_ISSUED: &str = "system:issued";
+pub const ACCOUNT_REDEEMED: &str = "system:redeemed";
+pub const ACCOUNT_EXPIRED: &str = "system:expired";
+
+pub fn user_account(user_id: i32) -> String {
+    format!("user:{}", user_id)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoyaltySettings {
+    // Pontos ganhos por real gasto
+    earn_rate: f64,
+    // Valor em reais de cada ponto resgatado
+    burn_rate: f64,
+    expiry_days: i32,
+    min_redeem_points: i32,
+}
+
+impl LoyaltySettings {
+    pub fn new(
+        earn_rate: f64,
+        burn_rate: f64,
+        expiry_days: i32,
+        min_redeem_points: i32,
+    ) -> Result<LoyaltySettings, ValidationErrors> {
+        let mut settings = LoyaltySettings {
+            earn_rate: 0.0,
+            burn_rate: 0.0,
+            expiry_days: 0,
+            min_redeem_points: 0,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("earn_rate", settings.set_earn_rate(earn_rate));
+        errors.check("burn_rate", settings.set_burn_rate(burn_rate));
+        errors.check("expiry_days", settings.set_expiry_days(expiry_days));
+        errors.check(
+            "min_redeem_points",
+            settings.set_min_redeem_points(min_redeem_points),
+        );
+
+        errors.result(settings)
+    }
+
+    pub fn from_json(json: web::Json<LoyaltySettings>) -> Result<LoyaltySettings, ValidationErrors> {
+        LoyaltySettings::new(
+            json.earn_rate(),
+            json.burn_rate(),
+            json.expiry_days(),
+            json.min_redeem_points(),
+        )
+    }
+
+    // Pontos ganhos por um pedido com o valor informado
+    pub fn points_for(&self, amount: Money) -> i32 {
+        (amount.to_decimal() * self.earn_rate).floor().max(0.0) as i32
+    }
+
+    // Get
+    pub fn earn_rate(&self) -> f64 {
+        self.earn_rate
+    }
+    pub fn burn_rate(&self) -> f64 {
+        self.burn_rate
+    }
+    pub fn expiry_days(&self) -> i32 {
+        self.expiry_days
+    }
+    pub fn min_redeem_points(&self) -> i32 {
+        self.min_redeem_points
+    }
+
+    // Set
+    pub fn set_earn_rate(&mut self, earn_rate: f64) -> Result<(), FieldError> {
+        Self::validate_rate(earn_rate)?;
+        self.earn_rate = earn_rate;
+        Ok(())
+    }
+    pub fn set_burn_rate(&mut self, burn_rate: f64) -> Result<(), FieldError> {
+        Self::validate_rate(burn_rate)?;
+        self.burn_rate = burn_rate;
+        Ok(())
+    }
+    pub fn set_expiry_days(&mut self, expiry_days: i32) -> Result<(), FieldError> {
+        if expiry_days <= 0 {
+            return Err(FieldError::min("Loyalty expiry days must be greater than 0", 1));
+        }
+        self.expiry_days = expiry_days;
+        Ok(())
+    }
+    pub fn set_min_redeem_points(&mut self, min_redeem_points: i32) -> Result<(), FieldError> {
+        if min_redeem_points < 0 {
+            return Err(FieldError::min(
+                "Loyalty minimum redeem points cannot be negative",
+                0,
+            ));
+        }
+        self.min_redeem_points = min_redeem_points;
+        Ok(())
+    }
+
+    // Validate
+    fn validate_rate(rate: f64) -> Result<(), FieldError> {
+        if rate < 0.0 {
+            return Err(FieldError::min("Loyalty rates cannot be negative", 0));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoyaltyTransaction {
+    id: i32,
+    kind: String,
+    points: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id_purchase: Option<i32>,
+    created_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+}
+
+impl LoyaltyTransaction {
+    pub fn new(
+        id: i32,
+        kind: String,
+        points: i32,
+        id_purchase: Option<i32>,
+        created_at: String,
+        expires_at: Option<String>,
+    ) -> LoyaltyTransaction {
+        LoyaltyTransaction {
+            id,
+            kind,
+            points,
+            id_purchase,
+            created_at,
+            expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LoyaltyStatement {
+    balance: i64,
+    transactions: Vec<LoyaltyTransaction>,
+}
+
+impl LoyaltyStatement {
+    pub fn new(balance: i64, transactions: Vec<LoyaltyTransaction>) -> LoyaltyStatement {
+        LoyaltyStatement {
+            balance,
+            transactions,
+        }
+    }
+}
+
+// Conferência do livro: toda transação deve somar zero e o saldo dos usuários deve ser
+// igual aos pontos emitidos menos os resgatados e expirados.
+#[derive(Debug, Clone, Serialize)]
+pub struct LoyaltyAudit {
+    unbalanced_transactions: Vec<i32>,
+    issued: i64,
+    redeemed: i64,
+    expired: i64,
+    outstanding: i64,
+    balanced: bool,
+}
+
+impl LoyaltyAudit {
+    pub fn new(
+        unbalanced_transactions: Vec<i32>,
+        issued: i64,
+        redeemed: i64,
+        expired: i64,
+        outstanding: i64,
+    ) -> LoyaltyAudit {
+        let balanced =
+            unbalanced_transactions.is_empty() && issued - redeemed - expired == outstanding;
+
+        LoyaltyAudit {
+            unbalanced_transactions,
+            issued,
+            redeemed,
+            expired,
+            outstanding,
+            balanced,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+use super::schedule::DATE_FORMAT;
+use super::store::DATETIME_FORMAT;
+
+// Itens do cardápio com alterações registradas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MenuEntity {
+    Product,
+    Category,
+}
+
+impl MenuEntity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MenuEntity::Product => "product",
+            MenuEntity::Category => "category",
+        }
+    }
+
+    pub fn parse(entity: &str) -> Result<MenuEntity, String> {
+        match entity {
+            "product" => Ok(MenuEntity::Product),
+            "category" => Ok(MenuEntity::Category),
+            _ => Err(format!("Invalid menu entity '{}'", entity)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MenuAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+}
+
+impl MenuAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MenuAction::Create => "create",
+            MenuAction::Update => "update",
+            MenuAction::Delete => "delete",
+            MenuAction::Restore => "restore",
+        }
+    }
+
+    pub fn parse(action: &str) -> Result<MenuAction, String> {
+        match action {
+            "create" => Ok(MenuAction::Create),
+            "update" => Ok(MenuAction::Update),
+            "delete" => Ok(MenuAction::Delete),
+            "restore" => Ok(MenuAction::Restore),
+            _ => Err(format!("Invalid menu action '{}'", action)),
+        }
+    }
+}
+
+// Alteração do cardápio com os dados antes e depois. 'user_id' é nulo quando o funcionário não foi identificado.
+#[derive(Debug, Clone, Serialize)]
+pub struct MenuChange {
+    id: i32,
+    entity: MenuEntity,
+    entity_id: i32,
+    action: MenuAction,
+    user_id: Option<i32>,
+    changed_at: String,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+}
+
+impl MenuChange {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i32,
+        entity: MenuEntity,
+        entity_id: i32,
+        action: MenuAction,
+        user_id: Option<i32>,
+        changed_at: String,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> MenuChange {
+        MenuChange {
+            id,
+            entity,
+            entity_id,
+            action,
+            user_id,
+            changed_at,
+            before,
+            after,
+        }
+    }
+}
+
+// Preço do produto a partir de 'changed_at'
+#[derive(Debug, Clone, Serialize)]
+pub struct PriceChange {
+    product_id: i32,
+    price: Money,
+    user_id: Option<i32>,
+    changed_at: String,
+}
+
+impl PriceChange {
+    pub fn new(product_id: i32, price: Money, user_id: Option<i32>, changed_at: String) -> PriceChange {
+        PriceChange {
+            product_id,
+            price,
+            user_id,
+            changed_at,
+        }
+    }
+}
+
+// Momento consultado no histórico, no formato YYYY-MM-DD HH:MM:SS. Uma data sem horário
+// corresponde ao fim do dia, retornando o último preço praticado nela.
+pub fn parse_moment(moment: &str) -> Result<String, String> {
+    if let Ok(moment) = NaiveDateTime::parse_from_str(moment, DATETIME_FORMAT) {
+        return Ok(moment.format(DATETIME_FORMAT).to_string());
+    }
+
+    NaiveDate::parse_from_str(moment, DATE_FORMAT)
+        .ok()
+        .and_then(|date| date.and_hms_opt(23, 59, 59))
+        .map(|moment| moment.format(DATETIME_FORMAT).to_string())
+        .ok_or_else(|| {
+            format!(
+                "Date '{}' must be in the format YYYY-MM-DD or YYYY-MM-DD HH:MM:SS",
+                moment
+            )
+        })
+}
@@ -0,0 +1,425 @@
+use bytes::{BufMut, BytesMut};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::error::Error;
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Sub};
+use tokio_postgres::types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+
+// Valores monetários guardados em centavos, sem as perdas de arredondamento do ponto flutuante.
+//
+// Regras de arredondamento: valores com mais de duas casas decimais são arredondados para o centavo mais
+// próximo, com as metades arredondadas para longe do zero (0,005 => 0,01 e -0,005 => -0,01). Percentuais
+// e taxas aplicados a um valor seguem a mesma regra sobre o resultado em centavos.
+//
+// No JSON o valor continua sendo um número em reais (ex.: 12.5), como os aplicativos já esperam.
+// Também são aceitos textos com ponto ou vírgula decimal (ex.: "12.50" ou "12,50").
+// No banco de dados os valores são colunas NUMERIC(12, 2).
+
+// Maior valor aceito, em centavos, limitado pelas colunas NUMERIC(12, 2)
+const MAX_CENTS: i64 = 999_999_999_999;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum Currency {
+    #[serde(rename = "BRL")]
+    Brl,
+}
+
+// Todos os valores estão na moeda do restaurante
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money {
+    cents: i64,
+    currency: Currency,
+}
+
+impl Money {
+    pub fn from_cents(cents: i64) -> Money {
+        Money {
+            cents,
+            currency: Currency::Brl,
+        }
+    }
+
+    pub fn zero() -> Money {
+        Money::from_cents(0)
+    }
+
+    // Converte um valor em reais, arredondando para o centavo
+    pub fn from_decimal(value: f64) -> Result<Money, String> {
+        if !value.is_finite() {
+            return Err(String::from("Amount must be a number"));
+        }
+        // A representação decimal mais curta do f64 evita que 0.285 vire 28.499999... centavos
+        Money::parse(&value.to_string())
+    }
+
+    // Lê um valor em reais no formato "1234.56" ou "1234,56"
+    pub fn parse(value: &str) -> Result<Money, String> {
+        let invalid = || format!("Invalid amount '{}'", value);
+
+        let cents = round_scaled(value.trim(), 2).ok_or_else(invalid)?;
+        if cents.abs() > MAX_CENTS {
+            return Err(format!("Amount '{}' is out of range", value));
+        }
+        Ok(Money::from_cents(cents))
+    }
+
+    // Get
+    pub fn currency(&self) -> Currency {
+        self.currency
+    }
+    pub fn to_decimal(self) -> f64 {
+        self.cents as f64 / 100.0
+    }
+    pub fn is_positive(&self) -> bool {
+        self.cents > 0
+    }
+    pub fn is_negative(&self) -> bool {
+        self.cents < 0
+    }
+
+    // Valor multiplicado por uma quantidade. Falha se o resultado passar do maior valor aceito.
+    pub fn times(&self, quantity: i32) -> Result<Money, String> {
+        match self.cents.checked_mul(quantity as i64) {
+            Some(cents) if cents.abs() <= MAX_CENTS => Ok(Money::from_cents(cents)),
+            _ => Err(format!("Amount {} x {} is out of range", self, quantity)),
+        }
+    }
+
+    // Percentual do valor (ex.: 10.0 => 10%), arredondado para o centavo
+    pub fn percent(&self, percent: f64) -> Money {
+        self.scale(percent / 100.0)
+    }
+
+    // Valor multiplicado por uma taxa, arredondado para o centavo
+    pub fn scale(&self, rate: f64) -> Money {
+        let cents = self.cents as f64 * rate;
+        match cents.is_finite() {
+            true => Money::from_cents(round_scaled(&cents.to_string(), 0).unwrap_or_default()),
+            false => Money::zero(),
+        }
+    }
+}
+
+impl Default for Money {
+    fn default() -> Money {
+        Money::zero()
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.cents < 0 { "-" } else { "" };
+        write!(
+            f,
+            "{}{}.{:02}",
+            sign,
+            self.cents.abs() / 100,
+            self.cents.abs() % 100
+        )
+    }
+}
+
+impl Add for Money {
+    type Output = Money;
+
+    fn add(self, other: Money) -> Money {
+        Money::from_cents(self.cents + other.cents)
+    }
+}
+
+impl AddAssign for Money {
+    fn add_assign(&mut self, other: Money) {
+        self.cents += other.cents;
+    }
+}
+
+impl Sub for Money {
+    type Output = Money;
+
+    fn sub(self, other: Money) -> Money {
+        Money::from_cents(self.cents - other.cents)
+    }
+}
+
+impl Sum for Money {
+    fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+        iter.fold(Money::zero(), |total, value| total + value)
+    }
+}
+
+// Arredonda um número decimal em texto para 'decimals' casas, com as metades para longe do zero,
+// e retorna o resultado como inteiro (ex.: ("12.345", 2) => 1235).
+fn round_scaled(value: &str, decimals: usize) -> Option<i64> {
+    let (negative, digits) = match value.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, value),
+    };
+    let (integer, fraction) = match digits.split_once(['.', ',']) {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (digits, ""),
+    };
+    if integer.is_empty() && fraction.is_empty()
+        || !integer.chars().all(|c| c.is_ascii_digit())
+        || !fraction.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let mut scaled: i64 = 0;
+    for digit in integer
+        .chars()
+        .chain(fraction.chars().chain(std::iter::repeat('0')).take(decimals))
+    {
+        scaled = scaled
+            .checked_mul(10)?
+            .checked_add(digit.to_digit(10)? as i64)?;
+    }
+    if fraction.chars().nth(decimals).is_some_and(|digit| digit >= '5') {
+        scaled = scaled.checked_add(1)?;
+    }
+
+    Some(if negative { -scaled } else { scaled })
+}
+
+impl Serialize for Money {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(self.to_decimal())
+    }
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Money, D::Error> {
+        deserializer.deserialize_any(MoneyVisitor)
+    }
+}
+
+struct MoneyVisitor;
+
+impl<'de> de::Visitor<'de> for MoneyVisitor {
+    type Value = Money;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "an amount such as 12.5 or \"12.50\"")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Money, E> {
+        Money::from_decimal(value).map_err(E::custom)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Money, E> {
+        Money::parse(&value.to_string()).map_err(E::custom)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Money, E> {
+        Money::parse(&value.to_string()).map_err(E::custom)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Money, E> {
+        Money::parse(value).map_err(E::custom)
+    }
+}
+
+// Formato binário do NUMERIC: quantidade de dígitos, peso, sinal e escala, seguidos dos dígitos na base 10000
+const NUMERIC_POSITIVE: u16 = 0x0000;
+const NUMERIC_NEGATIVE: u16 = 0x4000;
+
+impl<'a> FromSql<'a> for Money {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Money, Box<dyn Error + Sync + Send>> {
+        let read_u16 = |index: usize| -> Result<u16, Box<dyn Error + Sync + Send>> {
+            raw.get(index * 2..index * 2 + 2)
+                .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+                .ok_or_else(|| "Invalid NUMERIC value".into())
+        };
+
+        let digits = read_u16(0)? as usize;
+        let weight = read_u16(1)? as i16 as i32;
+        let sign = read_u16(2)?;
+        if sign != NUMERIC_POSITIVE && sign != NUMERIC_NEGATIVE {
+            return Err("NUMERIC value is not a number".into());
+        }
+
+        // Valor em décimos de milésimo (4 casas), arredondado depois para centavos
+        let mut scaled: i128 = 0;
+        for index in 0..digits {
+            let exponent = weight - index as i32 + 1;
+            if exponent < 0 {
+                break;
+            }
+            let digit = read_u16(4 + index)? as i128;
+            scaled = 10000i128
+                .checked_pow(exponent as u32)
+                .and_then(|power| power.checked_mul(digit))
+                .and_then(|value| value.checked_add(scaled))
+                .ok_or("NUMERIC value is out of range")?;
+        }
+
+        let cents = scaled / 100 + i128::from(scaled % 100 >= 50);
+        if cents > MAX_CENTS as i128 {
+            return Err("NUMERIC value is out of range".into());
+        }
+        let cents = cents as i64;
+
+        Ok(Money::from_cents(if sign == NUMERIC_NEGATIVE { -cents } else { cents }))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+}
+
+impl ToSql for Money {
+    fn to_sql(&self, _: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + Sync + Send>> {
+        let cents = self.cents.unsigned_abs();
+
+        // Dígitos na base 10000: a parte inteira seguida dos centavos como primeiro dígito fracionário
+        let mut digits: Vec<u16> = Vec::new();
+        let mut integer = cents / 100;
+        while integer > 0 {
+            digits.insert(0, (integer % 10000) as u16);
+            integer /= 10000;
+        }
+        let mut weight = digits.len() as i16 - 1;
+        digits.push((cents % 100 * 100) as u16);
+
+        while digits.first() == Some(&0) {
+            digits.remove(0);
+            weight -= 1;
+        }
+        while digits.last() == Some(&0) {
+            digits.pop();
+        }
+        if digits.is_empty() {
+            weight = 0;
+        }
+
+        out.put_u16(digits.len() as u16);
+        out.put_i16(weight);
+        out.put_u16(if self.cents < 0 { NUMERIC_NEGATIVE } else { NUMERIC_POSITIVE });
+        out.put_u16(2);
+        for digit in digits {
+            out.put_u16(digit);
+        }
+
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        *ty == Type::NUMERIC
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numeric(money: Money) -> Vec<u8> {
+        let mut out = BytesMut::new();
+        money.to_sql(&Type::NUMERIC, &mut out).unwrap();
+        out.to_vec()
+    }
+
+    fn words(raw: &[u8]) -> Vec<u16> {
+        raw.chunks(2)
+            .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+            .collect()
+    }
+
+    #[test]
+    fn parse_rounds_half_away_from_zero() {
+        assert_eq!(Money::parse("12.345"), Ok(Money::from_cents(1235)));
+        assert_eq!(Money::parse("12.344"), Ok(Money::from_cents(1234)));
+        assert_eq!(Money::parse("-0.005"), Ok(Money::from_cents(-1)));
+        assert_eq!(Money::parse("12,5"), Ok(Money::from_cents(1250)));
+        assert_eq!(Money::parse(" 7 "), Ok(Money::from_cents(700)));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_and_out_of_range_amounts() {
+        assert!(Money::parse("").is_err());
+        assert!(Money::parse(".").is_err());
+        assert!(Money::parse("1.2.3").is_err());
+        assert!(Money::parse("abc").is_err());
+        assert!(Money::parse("10000000000.00").is_err());
+    }
+
+    #[test]
+    fn from_decimal_uses_the_shortest_representation() {
+        assert_eq!(Money::from_decimal(0.285), Ok(Money::from_cents(29)));
+        assert_eq!(Money::from_decimal(1.005), Ok(Money::from_cents(101)));
+        assert!(Money::from_decimal(f64::NAN).is_err());
+    }
+
+    #[test]
+    fn percent_and_times_round_to_the_cent() {
+        assert_eq!(
+            Money::from_cents(1999).percent(10.0),
+            Money::from_cents(200)
+        );
+        assert_eq!(
+            Money::from_cents(-1999).percent(10.0),
+            Money::from_cents(-200)
+        );
+        assert_eq!(Money::from_cents(250).times(3), Ok(Money::from_cents(750)));
+        assert!(Money::from_cents(MAX_CENTS).times(2).is_err());
+        assert!(Money::from_cents(MAX_CENTS).times(i32::MAX).is_err());
+    }
+
+    #[test]
+    fn display_pads_the_cents() {
+        assert_eq!(Money::from_cents(1205).to_string(), "12.05");
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+    }
+
+    #[test]
+    fn to_sql_writes_base_10000_digits() {
+        // 12345.67 => dígitos [1, 2345, 6700], peso 1, escala 2
+        assert_eq!(
+            words(&numeric(Money::from_cents(1234567))),
+            vec![3, 1, NUMERIC_POSITIVE, 2, 1, 2345, 6700]
+        );
+        // 0.05 => dígito [500] na primeira casa fracionária
+        assert_eq!(
+            words(&numeric(Money::from_cents(-5))),
+            vec![1, 0xFFFF, NUMERIC_NEGATIVE, 2, 500]
+        );
+        assert_eq!(
+            words(&numeric(Money::zero())),
+            vec![0, 0, NUMERIC_POSITIVE, 2]
+        );
+    }
+
+    #[test]
+    fn numeric_round_trips() {
+        for cents in [
+            0, 1, 99, 100, 10000, 1234567, -1, -100050, MAX_CENTS, -MAX_CENTS,
+        ] {
+            let money = Money::from_cents(cents);
+            assert_eq!(
+                Money::from_sql(&Type::NUMERIC, &numeric(money)).unwrap(),
+                money
+            );
+        }
+    }
+
+    #[test]
+    fn from_sql_rounds_extra_decimals_to_the_cent() {
+        // 1.2350 com quatro casas => dígitos [1, 2350], peso 0
+        let raw: Vec<u8> = [2u16, 0, NUMERIC_POSITIVE, 4, 1, 2350]
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .collect();
+        assert_eq!(
+            Money::from_sql(&Type::NUMERIC, &raw).unwrap(),
+            Money::from_cents(124)
+        );
+
+        let nan: Vec<u8> = [0u16, 0, 0xC000, 0]
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .collect();
+        assert!(Money::from_sql(&Type::NUMERIC, &nan).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use serde::{Deserialize, Serialize};
+
+use super::validation::FieldError;
+
+// Alergênicos de declaração obrigatória pela ANVISA (RDC nº 26/2015), mais o glúten (Lei nº 10.674/2003).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Allergen {
+    Wheat,
+    Rye,
+    Barley,
+    Oats,
+    Crustaceans,
+    Eggs,
+    Fish,
+    Peanuts,
+    Soy,
+    Milk,
+    Almonds,
+    Hazelnuts,
+    Cashews,
+    BrazilNuts,
+    Macadamias,
+    Walnuts,
+    Pecans,
+    Pistachios,
+    PineNuts,
+    Chestnuts,
+    NaturalLatex,
+    Gluten,
+}
+
+// Cereais que contêm glúten
+const GLUTEN_CEREALS: [Allergen; 4] = [
+    Allergen::Wheat,
+    Allergen::Rye,
+    Allergen::Barley,
+    Allergen::Oats,
+];
+
+impl Allergen {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Allergen::Wheat => "wheat",
+            Allergen::Rye => "rye",
+            Allergen::Barley => "barley",
+            Allergen::Oats => "oats",
+            Allergen::Crustaceans => "crustaceans",
+            Allergen::Eggs => "eggs",
+            Allergen::Fish => "fish",
+            Allergen::Peanuts => "peanuts",
+            Allergen::Soy => "soy",
+            Allergen::Milk => "milk",
+            Allergen::Almonds => "almonds",
+            Allergen::Hazelnuts => "hazelnuts",
+            Allergen::Cashews => "cashews",
+            Allergen::BrazilNuts => "brazil_nuts",
+            Allergen::Macadamias => "macadamias",
+            Allergen::Walnuts => "walnuts",
+            Allergen::Pecans => "pecans",
+            Allergen::Pistachios => "pistachios",
+            Allergen::PineNuts => "pine_nuts",
+            Allergen::Chestnuts => "chestnuts",
+            Allergen::NaturalLatex => "natural_latex",
+            Allergen::Gluten => "gluten",
+        }
+    }
+
+    pub fn parse(allergen: &str) -> Result<Allergen, String> {
+        match allergen {
+            "wheat" => Ok(Allergen::Wheat),
+            "rye" => Ok(Allergen::Rye),
+            "barley" => Ok(Allergen::Barley),
+            "oats" => Ok(Allergen::Oats),
+            "crustaceans" => Ok(Allergen::Crustaceans),
+            "eggs" => Ok(Allergen::Eggs),
+            "fish" => Ok(Allergen::Fish),
+            "peanuts" => Ok(Allergen::Peanuts),
+            "soy" => Ok(Allergen::Soy),
+            "milk" => Ok(Allergen::Milk),
+            "almonds" => Ok(Allergen::Almonds),
+            "hazelnuts" => Ok(Allergen::Hazelnuts),
+            "cashews" => Ok(Allergen::Cashews),
+            "brazil_nuts" => Ok(Allergen::BrazilNuts),
+            "macadamias" => Ok(Allergen::Macadamias),
+            "walnuts" => Ok(Allergen::Walnuts),
+            "pecans" => Ok(Allergen::Pecans),
+            "pistachios" => Ok(Allergen::Pistachios),
+            "pine_nuts" => Ok(Allergen::PineNuts),
+            "chestnuts" => Ok(Allergen::Chestnuts),
+            "natural_latex" => Ok(Allergen::NaturalLatex),
+            "gluten" => Ok(Allergen::Gluten),
+            _ => Err(format!("Invalid allergen '{}'", allergen)),
+        }
+    }
+
+    // Lê uma lista separada por vírgulas, como "gluten,milk"
+    pub fn parse_list(allergens: &str) -> Result<Vec<Allergen>, String> {
+        allergens
+            .split(',')
+            .map(|allergen| allergen.trim())
+            .filter(|allergen| !allergen.is_empty())
+            .map(Allergen::parse)
+            .collect()
+    }
+}
+
+// Valida os alergênicos declarados em um produto
+pub fn validate_allergens(allergens: &[Allergen]) -> Result<(), FieldError> {
+    for (i, allergen) in allergens.iter().enumerate() {
+        if allergens[..i].contains(allergen) {
+            return Err(FieldError::duplicate(format!(
+                "Allergen '{}' is repeated in the product",
+                allergen.as_str()
+            ))
+            .at(&format!("[{}]", i)));
+        }
+    }
+    if allergens
+        .iter()
+        .any(|allergen| GLUTEN_CEREALS.contains(allergen))
+        && !allergens.contains(&Allergen::Gluten)
+    {
+        return Err(FieldError::new(
+            "missing_gluten",
+            String::from("Products with wheat, rye, barley or oats must also declare gluten"),
+        ));
+    }
+    Ok(())
+}
+
+// Informação nutricional de uma porção do produto. As quantidades são em gramas e a energia em kcal.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NutritionFacts {
+    portion_grams: f64,
+    kcal: f64,
+    protein: f64,
+    carbs: f64,
+    fat: f64,
+}
+
+// Maior energia possível por grama de alimento (a da gordura)
+const MAX_KCAL_PER_GRAM: f64 = 9.0;
+
+impl NutritionFacts {
+    pub fn new(
+        portion_grams: f64,
+        kcal: f64,
+        protein: f64,
+        carbs: f64,
+        fat: f64,
+    ) -> Result<NutritionFacts, FieldError> {
+        let facts = NutritionFacts {
+            portion_grams,
+            kcal,
+            protein,
+            carbs,
+            fat,
+        };
+
+        facts.validate()?;
+
+        Ok(facts)
+    }
+
+    // Get
+    pub fn portion_grams(&self) -> f64 {
+        self.portion_grams
+    }
+    pub fn kcal(&self) -> f64 {
+        self.kcal
+    }
+    pub fn protein(&self) -> f64 {
+        self.protein
+    }
+    pub fn carbs(&self) -> f64 {
+        self.carbs
+    }
+    pub fn fat(&self) -> f64 {
+        self.fat
+    }
+
+    // Validate
+    pub fn validate(&self) -> Result<(), FieldError> {
+        if !self.portion_grams.is_finite() || self.portion_grams <= 0.0 {
+            return Err(
+                FieldError::min("Portion size must be greater than 0", 0).at("portion_grams")
+            );
+        }
+        for (field, name, value) in [
+            ("kcal", "Energy", self.kcal),
+            ("protein", "Protein", self.protein),
+            ("carbs", "Carbohydrates", self.carbs),
+            ("fat", "Fat", self.fat),
+        ] {
+            if !value.is_finite() || value < 0.0 {
+                return Err(
+                    FieldError::new("min", format!("{} cannot be negative", name))
+                        .with_param("min", 0)
+                        .at(field),
+                );
+            }
+        }
+        if self.protein + self.carbs + self.fat > self.portion_grams {
+            return Err(FieldError::new(
+                "exceeds_portion",
+                String::from("Protein, carbohydrates and fat cannot exceed the portion size"),
+            )
+            .with_param("max", self.portion_grams));
+        }
+        if self.kcal > self.portion_grams * MAX_KCAL_PER_GRAM {
+            return Err(FieldError::new(
+                "max",
+                format!(
+                    "Energy cannot exceed {} kcal per gram of the portion",
+                    MAX_KCAL_PER_GRAM
+                ),
+            )
+            .with_param("max", self.portion_grams * MAX_KCAL_PER_GRAM)
+            .at("kcal"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+use super::validation::{FieldError, ValidationErrors};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProductOption {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    name: String,
+    // Valor somado ao preço do produto quando a opção é escolhida, pode ser negativo
+    #[serde(default)]
+    price_delta: Money,
+    available: bool,
+}
+
+impl ProductOption {
+    pub fn new(
+        id: Option<i32>,
+        name: String,
+        price_delta: Money,
+        available: bool,
+    ) -> Result<ProductOption, ValidationErrors> {
+        let mut new_option = ProductOption {
+            id,
+            name: String::new(),
+            price_delta: Money::zero(),
+            available: false,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_option.set_name(name));
+        new_option.set_price_delta(price_delta);
+        new_option.set_available(available);
+
+        errors.result(new_option)
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn price_delta(&self) -> Money {
+        self.price_delta
+    }
+    pub fn available(&self) -> bool {
+        self.available
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        Self::validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_price_delta(&mut self, price_delta: Money) {
+        self.price_delta = price_delta;
+    }
+    pub fn set_available(&mut self, available: bool) {
+        self.available = available;
+    }
+
+    // Validate
+    fn validate_name(name: &str) -> Result<(), FieldError> {
+        if name.trim().is_empty() {
+            return Err(FieldError::required("Option name cannot be empty"));
+        }
+        if name.chars().count() > 50 {
+            return Err(FieldError::too_long(
+                "Option name cannot exceed 50 characters",
+                50,
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Grupo de opções de um produto, como o tamanho (escolha 1) ou os adicionais (escolha até 3)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptionGroup {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    name: String,
+    #[serde(default)]
+    min_select: i32,
+    max_select: i32,
+    options: Vec<ProductOption>,
+}
+
+impl OptionGroup {
+    pub fn new(
+        id: Option<i32>,
+        name: String,
+        min_select: i32,
+        max_select: i32,
+        options: Vec<ProductOption>,
+    ) -> Result<OptionGroup, ValidationErrors> {
+        let mut new_group = OptionGroup {
+            id,
+            name: String::new(),
+            min_select: 0,
+            max_select: 0,
+            options: Vec::new(),
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_group.set_name(name));
+        errors.check("options", new_group.set_options(options));
+        errors.check("", new_group.set_limits(min_select, max_select));
+
+        errors.result(new_group)
+    }
+
+    // Reconstrói pelos construtores um grupo recebido no JSON, que não passou pelas validações do
+    // grupo nem das opções
+    pub fn rebuild(&self) -> Result<OptionGroup, ValidationErrors> {
+        let mut options = Vec::new();
+        let mut errors = ValidationErrors::new();
+        for (i, option) in self.options.iter().enumerate() {
+            match ProductOption::new(
+                option.id,
+                option.name.clone(),
+                option.price_delta,
+                option.available,
+            ) {
+                Ok(option) => options.push(option),
+                Err(e) => {
+                    errors.nested(&format!("options[{}]", i), e);
+                    options.push(option.clone());
+                }
+            }
+        }
+
+        match OptionGroup::new(
+            self.id,
+            self.name.clone(),
+            self.min_select,
+            self.max_select,
+            options,
+        ) {
+            Ok(group) => errors.result(group),
+            Err(e) => {
+                errors.nested("", e);
+                Err(errors)
+            }
+        }
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn min_select(&self) -> i32 {
+        self.min_select
+    }
+    pub fn max_select(&self) -> i32 {
+        self.max_select
+    }
+    pub fn options(&self) -> Vec<ProductOption> {
+        self.options.clone()
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        Self::validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_options(&mut self, options: Vec<ProductOption>) -> Result<(), FieldError> {
+        Self::validate_options(&options)?;
+        self.options = options;
+        Ok(())
+    }
+    pub fn set_limits(&mut self, min_select: i32, max_select: i32) -> Result<(), FieldError> {
+        Self::validate_limits(min_select, max_select, self.options.len())?;
+        self.min_select = min_select;
+        self.max_select = max_select;
+        Ok(())
+    }
+
+    // Confere as opções escolhidas neste grupo e retorna a cópia que vai para o pedido.
+    pub fn select(&self, option_ids: &[i32]) -> Result<Vec<SelectedOption>, String> {
+        let mut selected = Vec::new();
+
+        for option in self.options.iter() {
+            let option_id = match option.id() {
+                Some(option_id) if option_ids.contains(&option_id) => option_id,
+                _ => continue,
+            };
+            if !option.available() {
+                return Err(format!("Opção '{}' indisponível", option.name()));
+            }
+            selected.push(SelectedOption::new(
+                option_id,
+                self.name(),
+                option.name(),
+                option.price_delta(),
+            ));
+        }
+
+        let count = selected.len() as i32;
+        if count < self.min_select {
+            return Err(match self.min_select {
+                1 => format!("Escolha uma opção de '{}'", self.name),
+                n => format!("Escolha pelo menos {} opções de '{}'", n, self.name),
+            });
+        }
+        if count > self.max_select {
+            return Err(match self.max_select {
+                1 => format!("Escolha apenas uma opção de '{}'", self.name),
+                n => format!("Escolha no máximo {} opções de '{}'", n, self.name),
+            });
+        }
+
+        Ok(selected)
+    }
+
+    // Validate
+    fn validate_name(name: &str) -> Result<(), FieldError> {
+        if name.trim().is_empty() {
+            return Err(FieldError::required("Option group name cannot be empty"));
+        }
+        if name.chars().count() > 50 {
+            return Err(FieldError::too_long(
+                "Option group name cannot exceed 50 characters",
+                50,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_options(options: &[ProductOption]) -> Result<(), FieldError> {
+        if options.is_empty() {
+            return Err(FieldError::required(
+                "Option group must have at least one option",
+            ));
+        }
+        for (i, option) in options.iter().enumerate() {
+            if options[..i].iter().any(|o| o.name() == option.name()) {
+                return Err(FieldError::duplicate(format!(
+                    "Option '{}' is repeated in the group",
+                    option.name()
+                ))
+                .at(&format!("[{}].name", i)));
+            }
+        }
+        Ok(())
+    }
+    fn validate_limits(min_select: i32, max_select: i32, options: usize) -> Result<(), FieldError> {
+        if min_select < 0 || max_select < 1 || min_select > max_select {
+            return Err(FieldError::new(
+                "out_of_range",
+                String::from(
+                    "Option group limits must satisfy 0 <= min_select <= max_select and max_select >= 1",
+                ),
+            )
+            .at("min_select"));
+        }
+        if max_select as usize > options {
+            return Err(FieldError::new(
+                "out_of_range",
+                String::from("Option group max_select cannot exceed the number of options"),
+            )
+            .with_param("max", options)
+            .at("max_select"));
+        }
+        Ok(())
+    }
+}
+
+// Opção escolhida pelo cliente, gravada no item do pedido para a cozinha.
+// O aplicativo envia apenas o 'option_id', os demais campos são preenchidos pelo servidor.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectedOption {
+    option_id: i32,
+    #[serde(default)]
+    group: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    price_delta: Money,
+}
+
+impl SelectedOption {
+    pub fn new(option_id: i32, group: String, name: String, price_delta: Money) -> SelectedOption {
+        SelectedOption {
+            option_id,
+            group,
+            name,
+            price_delta,
+        }
+    }
+
+    // Get
+    pub fn option_id(&self) -> i32 {
+        self.option_id
+    }
+    pub fn price_delta(&self) -> Money {
+        self.price_delta
+    }
+}
+
+// Confere as escolhas em todos os grupos do produto e retorna as opções escolhidas
+pub fn select_options(
+    groups: &[OptionGroup],
+    selected: &[SelectedOption],
+) -> Result<Vec<SelectedOption>, String> {
+    let option_ids: Vec<i32> = selected.iter().map(|o| o.option_id()).collect();
+
+    for (i, option_id) in option_ids.iter().enumerate() {
+        if option_ids[..i].contains(option_id) {
+            return Err(String::from("A mesma opção foi escolhida mais de uma vez"));
+        }
+        let known = groups
+            .iter()
+            .flat_map(|g| g.options.iter())
+            .any(|o| o.id() == Some(*option_id));
+        if !known {
+            return Err(format!("Opção {} não pertence ao produto", option_id));
+        }
+    }
+
+    let mut options = Vec::new();
+    for group in groups {
+        options.extend(group.select(&option_ids)?);
+    }
+
+    Ok(options)
+}
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use super::order_total::OrderTotal;
+use super::product_cart::ProductCart;
+
+// Pedido gravado, com os itens e os valores calculados pelo servidor.
+// Os valores ficam no nível principal do objeto, como na resposta da criação antes dos itens serem incluídos.
+#[derive(Debug, Clone, Serialize)]
+pub struct Order {
+    id_purchase: i32,
+    #[serde(flatten)]
+    total: OrderTotal,
+    items: Vec<ProductCart>,
+}
+
+impl Order {
+    pub fn new(id_purchase: i32, total: OrderTotal, items: Vec<ProductCart>) -> Order {
+        Order {
+            id_purchase,
+            total,
+            items,
+        }
+    }
+
+    // Get
+    pub fn id_purchase(&self) -> i32 {
+        self.id_purchase
+    }
+}
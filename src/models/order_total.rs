@@ -0,0 +1,153 @@
+use serde::Serialize;
+
+use super::money::{Currency, Money};
+
+// Item do pedido com o preço cadastrado do produto
+#[derive(Debug, Clone)]
+pub struct PricedItem {
+    product_id: i32,
+    category_id: Option<i32>,
+    line_total: Money,
+}
+
+impl PricedItem {
+    pub fn new(
+        product_id: i32,
+        category_id: Option<i32>,
+        quantity: i32,
+        unit_price: Money,
+    ) -> Result<PricedItem, String> {
+        Ok(PricedItem {
+            product_id,
+            category_id,
+            line_total: unit_price.times(quantity)?,
+        })
+    }
+
+    // Get
+    pub fn product_id(&self) -> i32 {
+        self.product_id
+    }
+    pub fn category_id(&self) -> Option<i32> {
+        self.category_id
+    }
+    pub fn line_total(&self) -> Money {
+        self.line_total
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AppliedPromotion {
+    promotion_id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<String>,
+    amount: Money,
+}
+
+impl AppliedPromotion {
+    pub fn new(promotion_id: i32, code: Option<String>, amount: Money) -> AppliedPromotion {
+        AppliedPromotion {
+            promotion_id,
+            code,
+            amount,
+        }
+    }
+
+    // Get
+    pub fn promotion_id(&self) -> i32 {
+        self.promotion_id
+    }
+    pub fn code(&self) -> Option<String> {
+        self.code.clone()
+    }
+    pub fn amount(&self) -> Money {
+        self.amount
+    }
+}
+
+// Valores do pedido calculados pelo servidor, independente dos preços enviados pelos aplicativos
+#[derive(Debug, Clone, Serialize)]
+pub struct OrderTotal {
+    subtotal: Money,
+    delivery_fee: Money,
+    discount: Money,
+    loyalty_points: i32,
+    loyalty_discount: Money,
+    total: Money,
+    currency: Currency,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delivery_zone_id: Option<i32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    promotions: Vec<AppliedPromotion>,
+}
+
+impl OrderTotal {
+    pub fn new(subtotal: Money, delivery_fee: Money, delivery_zone_id: Option<i32>) -> OrderTotal {
+        let mut order_total = OrderTotal {
+            subtotal,
+            delivery_fee,
+            discount: Money::zero(),
+            loyalty_points: 0,
+            loyalty_discount: Money::zero(),
+            total: Money::zero(),
+            currency: subtotal.currency(),
+            delivery_zone_id,
+            promotions: Vec::new(),
+        };
+        order_total.update_total();
+        order_total
+    }
+
+    // Get
+    pub fn subtotal(&self) -> Money {
+        self.subtotal
+    }
+    pub fn delivery_fee(&self) -> Money {
+        self.delivery_fee
+    }
+    pub fn discount(&self) -> Money {
+        self.discount
+    }
+    pub fn loyalty_points(&self) -> i32 {
+        self.loyalty_points
+    }
+    pub fn loyalty_discount(&self) -> Money {
+        self.loyalty_discount
+    }
+    pub fn total(&self) -> Money {
+        self.total
+    }
+    pub fn delivery_zone_id(&self) -> Option<i32> {
+        self.delivery_zone_id
+    }
+    pub fn promotions(&self) -> Vec<AppliedPromotion> {
+        self.promotions.clone()
+    }
+
+    // O desconto nunca ultrapassa o valor do pedido com a entrega
+    pub fn add_promotion(&mut self, promotion: AppliedPromotion) {
+        let amount = promotion.amount().min(self.total).max(Money::zero());
+        if !amount.is_positive() {
+            return;
+        }
+
+        self.discount += amount;
+        self.promotions.push(AppliedPromotion::new(
+            promotion.promotion_id(),
+            promotion.code(),
+            amount,
+        ));
+        self.update_total();
+    }
+
+    // Pontos de fidelidade resgatados, aplicados depois das promoções
+    pub fn add_loyalty(&mut self, points: i32, amount: Money) {
+        self.loyalty_points += points;
+        self.loyalty_discount += amount.min(self.total).max(Money::zero());
+        self.update_total();
+    }
+
+    fn update_total(&mut self) {
+        self.total = self.subtotal + self.delivery_fee - self.discount - self.loyalty_discount;
+    }
+}
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+// Página de uma listagem. 'next_cursor' é enviado para obter a página seguinte e é nulo na última página.
+#[derive(Debug, Clone, Serialize)]
+pub struct Page<T> {
+    items: Vec<T>,
+    next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<String>) -> Page<T> {
+        Page { items, next_cursor }
+    }
+}
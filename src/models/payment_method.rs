@@ -0,0 +1,177 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+use super::validation::{FieldError, ValidationErrors};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentMethodKind {
+    Cash,
+    Card,
+    Pix,
+}
+
+impl PaymentMethodKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentMethodKind::Cash => "cash",
+            PaymentMethodKind::Card => "card",
+            PaymentMethodKind::Pix => "pix",
+        }
+    }
+
+    // Converte o texto salvo no banco ou enviado pelos aplicativos no tipo correspondente.
+    // Aceita também os nomes em português usados no campo 'payment_type' dos pedidos.
+    pub fn parse(kind: &str) -> Result<PaymentMethodKind, String> {
+        match kind.trim().to_lowercase().as_str() {
+            "cash" | "dinheiro" => Ok(PaymentMethodKind::Cash),
+            "card" | "cartao" | "cartão" => Ok(PaymentMethodKind::Card),
+            "pix" => Ok(PaymentMethodKind::Pix),
+            _ => Err(format!("Tipo de pagamento '{}' inválido", kind)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentMethod {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    kind: PaymentMethodKind,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    label: String,
+    #[serde(default)]
+    is_default: bool,
+}
+
+impl PaymentMethod {
+    pub fn new(
+        id: Option<i32>,
+        kind: PaymentMethodKind,
+        value: String,
+        label: String,
+        is_default: bool,
+    ) -> Result<PaymentMethod, ValidationErrors> {
+        let mut new_method = PaymentMethod {
+            id,
+            kind,
+            value: String::new(),
+            label: String::new(),
+            is_default: false,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("value", new_method.set_value(value));
+        errors.check("label", new_method.set_label(label));
+        new_method.set_is_default(is_default);
+
+        errors.result(new_method)
+    }
+
+    pub fn from_json(json: web::Json<PaymentMethod>) -> Result<PaymentMethod, ValidationErrors> {
+        PaymentMethod::new(
+            json.id(),
+            json.kind(),
+            json.value(),
+            json.label(),
+            json.is_default(),
+        )
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn kind(&self) -> PaymentMethodKind {
+        self.kind
+    }
+    pub fn value(&self) -> String {
+        self.value.clone()
+    }
+    pub fn label(&self) -> String {
+        self.label.clone()
+    }
+    pub fn is_default(&self) -> bool {
+        self.is_default
+    }
+
+    // Set
+    pub fn set_value(&mut self, value: String) -> Result<(), FieldError> {
+        Self::validate_value(self.kind, &value)?;
+        self.value = value;
+        Ok(())
+    }
+    pub fn set_label(&mut self, label: String) -> Result<(), FieldError> {
+        Self::validate_label(&label)?;
+        self.label = label;
+        Ok(())
+    }
+    pub fn set_is_default(&mut self, is_default: bool) {
+        self.is_default = is_default;
+    }
+
+    // Validate
+    fn validate_value(kind: PaymentMethodKind, value: &str) -> Result<(), FieldError> {
+        match kind {
+            PaymentMethodKind::Cash => {
+                if !value.is_empty() {
+                    return Err(FieldError::invalid_format(
+                        "O campo 'value' deve estar vazio para pagamentos em dinheiro",
+                    ));
+                }
+            }
+            PaymentMethodKind::Card => {
+                if value.is_empty() {
+                    return Err(FieldError::required(
+                        "O campo 'value' deve conter o token do cartão",
+                    ));
+                }
+                if value.len() > 255 {
+                    return Err(FieldError::too_long(
+                        "O campo 'value' deve conter o token do cartão com no máximo 255 caracteres",
+                        255,
+                    ));
+                }
+                // O backend guarda apenas o token gerado pelo gateway, nunca o número do cartão
+                if value.len() >= 13 && value.chars().all(|c| c.is_ascii_digit() || c == ' ') {
+                    return Err(FieldError::invalid_format(
+                        "O campo 'value' deve conter o token do cartão, não o número",
+                    ));
+                }
+            }
+            PaymentMethodKind::Pix => Self::validate_pix_key(value)?,
+        }
+        Ok(())
+    }
+    fn validate_pix_key(key: &str) -> Result<(), FieldError> {
+        let only_digits = !key.is_empty() && key.chars().all(|c| c.is_ascii_digit());
+
+        let is_cpf_or_cnpj = only_digits && (key.len() == 11 || key.len() == 14);
+        let is_email = key.len() <= 77 && key.contains('@') && key.contains('.');
+        let is_phone = key.starts_with("+55")
+            && key[3..].chars().all(|c| c.is_ascii_digit())
+            && (key.len() == 13 || key.len() == 14);
+        let is_random = key.len() == 36
+            && key.char_indices().all(|(i, c)| match i {
+                8 | 13 | 18 | 23 => c == '-',
+                _ => c.is_ascii_hexdigit(),
+            });
+
+        if !(is_cpf_or_cnpj || is_email || is_phone || is_random) {
+            return Err(FieldError::invalid_format(
+                "O campo 'value' deve ser uma chave PIX válida (CPF, CNPJ, e-mail, telefone ou chave aleatória)",
+            ));
+        }
+        Ok(())
+    }
+    fn validate_label(label: &str) -> Result<(), FieldError> {
+        if label.len() > 50 {
+            return Err(FieldError::too_long(
+                "O campo 'label' deve ter no máximo 50 caracteres",
+                50,
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,64 +1,137 @@
 use actix_web::web;
 use serde::{Deserialize, Serialize};
 
+use super::bundle::BundleSlot;
+use super::money::Money;
+use super::nutrition::{validate_allergens, Allergen, NutritionFacts};
+use super::option_group::OptionGroup;
+use super::validation::{FieldError, ValidationErrors};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Product {
     #[serde(skip_serializing_if = "Option::is_none")]
     id: Option<i32>,
+    // Código externo do produto, usado na importação do cardápio. Sem código o produto recebe 'prod-{id}'.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sku: Option<String>,
     name: String,
-    price: f64,
+    price: Money,
     description: String,
     image: String,
     available: bool,
+    #[serde(default)]
+    category_id: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    option_groups: Vec<OptionGroup>,
+    // Partes do combo. Produtos com partes são combos e 'available' considera os componentes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    bundle: Vec<BundleSlot>,
+    // Alergênicos declarados pelos funcionários. Nos combos devem incluir os de todas as partes que podem ser escolhidas.
+    #[serde(default)]
+    allergens: Vec<Allergen>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nutrition: Option<NutritionFacts>,
 }
 
 impl Product {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         id: Option<i32>,
+        sku: Option<String>,
         name: String,
-        price: f64,
+        price: Money,
         description: String,
         image: String,
         available: bool,
-    ) -> Result<Product, String> {
+        category_id: Option<i32>,
+        option_groups: Vec<OptionGroup>,
+        bundle: Vec<BundleSlot>,
+        allergens: Vec<Allergen>,
+        nutrition: Option<NutritionFacts>,
+    ) -> Result<Product, ValidationErrors> {
         let mut new_product = Product {
             id: id,
+            sku: None,
             name: String::new(),
-            price: 0.0,
+            price: Money::zero(),
             description: String::new(),
             image: String::new(),
             available: false,
+            category_id: None,
+            option_groups: Vec::new(),
+            bundle: Vec::new(),
+            allergens: Vec::new(),
+            nutrition: None,
         };
 
-        new_product.set_name(name)?;
-        new_product.set_price(price)?;
-        new_product.set_description(description)?;
-        new_product.set_image(image)?;
-        new_product.set_available(available)?;
+        let mut errors = ValidationErrors::new();
+        errors.check("sku", new_product.set_sku(sku));
+        errors.check("name", new_product.set_name(name));
+        errors.check("price", new_product.set_price(price));
+        errors.check("description", new_product.set_description(description));
+        errors.check("image", new_product.set_image(image));
+        errors.check("available", new_product.set_available(available));
+        new_product.set_category_id(category_id);
+
+        // Os grupos e as partes do combo recebidos no JSON são reconstruídos para validar cada item
+        let mut groups = Vec::new();
+        for (i, group) in option_groups.into_iter().enumerate() {
+            match group.rebuild() {
+                Ok(group) => groups.push(group),
+                Err(e) => {
+                    errors.nested(&format!("option_groups[{}]", i), e);
+                    groups.push(group);
+                }
+            }
+        }
+        errors.check("option_groups", new_product.set_option_groups(groups));
 
-        Ok(new_product)
+        let mut slots = Vec::new();
+        for (i, slot) in bundle.into_iter().enumerate() {
+            match slot.rebuild() {
+                Ok(slot) => slots.push(slot),
+                Err(e) => {
+                    errors.nested(&format!("bundle[{}]", i), e);
+                    slots.push(slot);
+                }
+            }
+        }
+        errors.check("bundle", new_product.set_bundle(slots));
+        errors.check("allergens", new_product.set_allergens(allergens));
+        errors.check("nutrition", new_product.set_nutrition(nutrition));
+
+        errors.result(new_product)
     }
 
-    pub fn from_json(json: web::Json<Product>) -> Result<Product, String> {
-        Ok(Product::new(
+    pub fn from_json(json: web::Json<Product>) -> Result<Product, ValidationErrors> {
+        Product::new(
             json.id(),
+            json.sku(),
             json.name(),
             json.price(),
             json.description(),
             json.image(),
             json.available(),
-        )?)
+            json.category_id(),
+            json.option_groups(),
+            json.bundle(),
+            json.allergens(),
+            json.nutrition(),
+        )
     }
 
     // Get
     pub fn id(&self) -> Option<i32> {
         self.id.clone()
     }
+    pub fn sku(&self) -> Option<String> {
+        self.sku.clone()
+    }
     pub fn name(&self) -> String {
         self.name.clone()
     }
-    pub fn price(&self) -> f64 {
-        self.price.clone()
+    pub fn price(&self) -> Money {
+        self.price
     }
     pub fn description(&self) -> String {
         self.description.clone()
@@ -69,58 +142,265 @@ impl Product {
     pub fn available(&self) -> bool {
         self.available.clone()
     }
+    pub fn category_id(&self) -> Option<i32> {
+        self.category_id
+    }
+    pub fn option_groups(&self) -> Vec<OptionGroup> {
+        self.option_groups.clone()
+    }
+    pub fn bundle(&self) -> Vec<BundleSlot> {
+        self.bundle.clone()
+    }
+    pub fn allergens(&self) -> Vec<Allergen> {
+        self.allergens.clone()
+    }
+    pub fn nutrition(&self) -> Option<NutritionFacts> {
+        self.nutrition
+    }
 
     // Sets
-    pub fn set_name(&mut self, name: String) -> Result<(), String> {
+    pub fn set_sku(&mut self, sku: Option<String>) -> Result<(), FieldError> {
+        if let Some(sku) = &sku {
+            Self::validate_sku(sku)?;
+        }
+        self.sku = sku;
+        Ok(())
+    }
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
         Self::validate_name(&name)?;
         self.name = name;
         Ok(())
     }
-    pub fn set_price(&mut self, price: f64) -> Result<(), String> {
+    pub fn set_price(&mut self, price: Money) -> Result<(), FieldError> {
         Self::validate_price(price)?;
         self.price = price;
         Ok(())
     }
-    pub fn set_description(&mut self, description: String) -> Result<(), String> {
+    pub fn set_description(&mut self, description: String) -> Result<(), FieldError> {
         Self::validate_description(&description)?;
         self.description = description;
         Ok(())
     }
-    pub fn set_image(&mut self, image: String) -> Result<(), String> {
+    pub fn set_image(&mut self, image: String) -> Result<(), FieldError> {
         Self::validate_image(&image)?;
         self.image = image;
         Ok(())
     }
-    pub fn set_available(&mut self, available: bool) -> Result<(), String> {
+    pub fn set_available(&mut self, available: bool) -> Result<(), FieldError> {
         self.available = available;
         Ok(())
     }
+    pub fn set_category_id(&mut self, category_id: Option<i32>) {
+        self.category_id = category_id;
+    }
+    pub fn set_option_groups(&mut self, option_groups: Vec<OptionGroup>) -> Result<(), FieldError> {
+        Self::validate_option_groups(&option_groups)?;
+        self.option_groups = option_groups;
+        Ok(())
+    }
+    pub fn set_bundle(&mut self, bundle: Vec<BundleSlot>) -> Result<(), FieldError> {
+        Self::validate_bundle(&bundle)?;
+        self.bundle = bundle;
+        Ok(())
+    }
+    pub fn set_allergens(&mut self, mut allergens: Vec<Allergen>) -> Result<(), FieldError> {
+        validate_allergens(&allergens)?;
+        allergens.sort();
+        self.allergens = allergens;
+        Ok(())
+    }
+    pub fn set_nutrition(&mut self, nutrition: Option<NutritionFacts>) -> Result<(), FieldError> {
+        if let Some(nutrition) = &nutrition {
+            nutrition.validate()?;
+        }
+        self.nutrition = nutrition;
+        Ok(())
+    }
 
     // Validate
-    fn validate_name(name: &str) -> Result<(), String> {
+    fn validate_sku(sku: &str) -> Result<(), FieldError> {
+        if sku.is_empty() || sku.len() > 64 {
+            return Err(FieldError::new(
+                "length",
+                String::from("Product SKU must have between 1 and 64 characters"),
+            )
+            .with_param("min", 1)
+            .with_param("max", 64));
+        }
+        if !sku
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(FieldError::invalid_format(
+                "Product SKU can only contain letters, digits, '-', '_' and '.'",
+            ));
+        }
+        Ok(())
+    }
+    fn validate_name(name: &str) -> Result<(), FieldError> {
         if name.is_empty() {
-            return Err(String::from("Product name cannot be empty"));
+            return Err(FieldError::required("Product name cannot be empty"));
         }
         Ok(())
     }
-    fn validate_price(price: f64) -> Result<(), String> {
-        if price <= 0.0 {
-            return Err(String::from("Product price must be greater than 0"));
+    fn validate_price(price: Money) -> Result<(), FieldError> {
+        if !price.is_positive() {
+            return Err(FieldError::min(
+                "Product price must be greater than 0",
+                0.01,
+            ));
         }
         Ok(())
     }
 
-    fn validate_description(description: &str) -> Result<(), String> {
+    fn validate_description(description: &str) -> Result<(), FieldError> {
         if description.is_empty() {
-            return Err(String::from("Product description cannot be empty"));
+            return Err(FieldError::required("Product description cannot be empty"));
         }
         Ok(())
     }
 
-    fn validate_image(image: &str) -> Result<(), String> {
+    fn validate_image(image: &str) -> Result<(), FieldError> {
         if image.is_empty() {
-            return Err(String::from("Product image cannot be empty"));
+            return Err(FieldError::required("Product image cannot be empty"));
         }
         Ok(())
     }
+
+    fn validate_option_groups(option_groups: &[OptionGroup]) -> Result<(), FieldError> {
+        for (i, group) in option_groups.iter().enumerate() {
+            if option_groups[..i].iter().any(|g| g.name() == group.name()) {
+                return Err(FieldError::duplicate(format!(
+                    "Option group '{}' is repeated in the product",
+                    group.name()
+                ))
+                .at(&format!("[{}].name", i)));
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_bundle(bundle: &[BundleSlot]) -> Result<(), FieldError> {
+        for (i, slot) in bundle.iter().enumerate() {
+            if bundle[..i].iter().any(|s| s.name() == slot.name()) {
+                return Err(FieldError::duplicate(format!(
+                    "Bundle slot '{}' is repeated in the product",
+                    slot.name()
+                ))
+                .at(&format!("[{}].name", i)));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn product(
+        option_groups: serde_json::Value,
+        bundle: serde_json::Value,
+    ) -> Result<Product, ValidationErrors> {
+        let option_groups: Vec<OptionGroup> = serde_json::from_value(option_groups).unwrap();
+        let bundle: Vec<BundleSlot> = serde_json::from_value(bundle).unwrap();
+        Product::new(
+            None,
+            None,
+            String::from("Pizza"),
+            Money::from_cents(4990),
+            String::from("Pizza de calabresa"),
+            String::from("pizza.png"),
+            true,
+            None,
+            option_groups,
+            bundle,
+            Vec::new(),
+            None,
+        )
+    }
+
+    fn fields(errors: ValidationErrors) -> Vec<String> {
+        errors
+            .errors()
+            .iter()
+            .map(|error| {
+                serde_json::to_value(error).unwrap()["field"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn new_validates_option_groups_received_as_json() {
+        let errors = product(
+            json!([
+                {"name": "Tamanho", "min_select": 1, "max_select": 3, "options": [
+                    {"name": "Média", "available": true},
+                    {"name": " ", "available": true}
+                ]},
+                {"name": "", "min_select": 2, "max_select": 1, "options": []}
+            ]),
+            json!([]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            fields(errors),
+            vec![
+                "option_groups[0].options[1].name",
+                "option_groups[0].max_select",
+                "option_groups[1].name",
+                "option_groups[1].options",
+                "option_groups[1].min_select",
+            ]
+        );
+    }
+
+    #[test]
+    fn new_accepts_valid_option_groups() {
+        let product = product(
+            json!([
+                {"name": "Tamanho", "min_select": 1, "max_select": 1, "options": [
+                    {"name": "Média", "price_delta": 0, "available": true},
+                    {"name": "Grande", "price_delta": 10.5, "available": true}
+                ]}
+            ]),
+            json!([]),
+        )
+        .unwrap();
+        assert_eq!(product.option_groups()[0].options().len(), 2);
+    }
+
+    #[test]
+    fn new_validates_bundle_slots_received_as_json() {
+        let errors = product(
+            json!([]),
+            json!([
+                {"name": "Lanche", "quantity": 0, "product_ids": [1]},
+                {"name": "Bebida", "product_ids": []},
+                {"name": "Acompanhamento", "product_ids": [4, 5, 4]}
+            ]),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            fields(errors),
+            vec![
+                "bundle[0].quantity",
+                "bundle[1].product_ids",
+                "bundle[2].product_ids[2]",
+            ]
+        );
+
+        let product = product(
+            json!([]),
+            json!([{"name": "Bebida", "product_ids": [2, 3]}]),
+        )
+        .unwrap();
+        assert_eq!(product.bundle()[0].quantity(), 1);
+    }
 }
@@ -1,12 +1,24 @@
 use actix_web::web;
 use serde::{Deserialize, Serialize};
 
+use super::bundle::BundleComponent;
+use super::money::Money;
+use super::option_group::SelectedOption;
+use super::validation::{FieldError, ValidationErrors};
+
+// Pedido recebido, na fila da cozinha
+pub const STATUS_RECEIVED: i32 = 0;
+// Pedido cancelado, o estoque baixado pelo pedido é devolvido
+pub const STATUS_CANCELLED: i32 = 5;
+// Pedido agendado, enviado à cozinha pouco antes do horário pedido
+pub const STATUS_SCHEDULED: i32 = 6;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductCart {
     #[serde(skip_serializing_if = "Option::is_none")]
     id: Option<i32>,
     name: String,
-    price: f64,
+    price: Money,
     description: String,
     image: String,
     date: String,
@@ -15,13 +27,17 @@ pub struct ProductCart {
     status: i32,
     payment_type: String,
     id_purchase: i32,
+    #[serde(default)]
+    options: Vec<SelectedOption>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    components: Vec<BundleComponent>,
 }
 
 impl ProductCart {
     pub fn new(
         id: Option<i32>,
         name: String,
-        price: f64,
+        price: Money,
         description: String,
         image: String,
         date: String,
@@ -30,11 +46,13 @@ impl ProductCart {
         status: i32,
         payment_type: String,
         id_purchase: i32,
-    ) -> Result<Self, String> {
+        options: Vec<SelectedOption>,
+        components: Vec<BundleComponent>,
+    ) -> Result<Self, ValidationErrors> {
         let mut new_product_cart = Self {
             id: id,
             name: String::new(),
-            price: 0.0,
+            price: Money::zero(),
             description: String::new(),
             image: String::new(),
             date: String::new(),
@@ -43,25 +61,34 @@ impl ProductCart {
             status: 0,
             payment_type: String::new(),
             id_purchase: id_purchase,
+            options: Vec::new(),
+            components: Vec::new(),
         };
 
-        new_product_cart.set_name(name)?;
-        new_product_cart.set_price(price)?;
-        new_product_cart.set_description(description)?;
-        new_product_cart.set_image(image)?;
-        new_product_cart.set_date(date)?;
-        new_product_cart.set_quantity(quantity)?;
-        new_product_cart.set_observation(observation)?;
-        new_product_cart.set_status(status)?;
-        new_product_cart.set_payment_type(payment_type)?;
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_product_cart.set_name(name));
+        errors.check("price", new_product_cart.set_price(price));
+        errors.check("description", new_product_cart.set_description(description));
+        errors.check("image", new_product_cart.set_image(image));
+        errors.check("date", new_product_cart.set_date(date));
+        errors.check("quantity", new_product_cart.set_quantity(quantity));
+        errors.check("observation", new_product_cart.set_observation(observation));
+        errors.check("status", new_product_cart.set_status(status));
+        errors.check("payment_type", new_product_cart.set_payment_type(payment_type));
+        new_product_cart.set_options(options);
+        new_product_cart.set_components(components);
 
-        Ok(new_product_cart)
+        errors.result(new_product_cart)
     }
 
-    pub fn from_json(json: web::Json<Vec<ProductCart>>) -> Result<Vec<ProductCart>, String> {
+    // Valida todos os itens do pedido; os erros de cada item ficam em "items[i]"
+    pub fn from_json(
+        json: web::Json<Vec<ProductCart>>,
+    ) -> Result<Vec<ProductCart>, ValidationErrors> {
         let mut product_carts = Vec::new();
-        for product_cart in json.iter() {
-            let prod = ProductCart::new(
+        let mut errors = ValidationErrors::new();
+        for (i, product_cart) in json.iter().enumerate() {
+            match ProductCart::new(
                 product_cart.id(),
                 product_cart.name(),
                 product_cart.price(),
@@ -73,10 +100,14 @@ impl ProductCart {
                 product_cart.status(),
                 product_cart.payment_type(),
                 product_cart.id_purchase(),
-            )?;
-            product_carts.push(prod);
+                product_cart.options(),
+                product_cart.components(),
+            ) {
+                Ok(prod) => product_carts.push(prod),
+                Err(e) => errors.nested(&format!("items[{}]", i), e),
+            }
         }
-        Ok(product_carts)
+        errors.result(product_carts)
     }
 
     // Get
@@ -86,7 +117,7 @@ impl ProductCart {
     pub fn name(&self) -> String {
         self.name.clone()
     }
-    pub fn price(&self) -> f64 {
+    pub fn price(&self) -> Money {
         self.price
     }
     pub fn description(&self) -> String {
@@ -113,112 +144,127 @@ impl ProductCart {
     pub fn id_purchase(&self) -> i32 {
         self.id_purchase.clone()
     }
+    pub fn options(&self) -> Vec<SelectedOption> {
+        self.options.clone()
+    }
+    pub fn components(&self) -> Vec<BundleComponent> {
+        self.components.clone()
+    }
 
     // Set
-    pub fn set_name(&mut self, name: String) -> Result<(), String> {
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
         Self::validate_name(&name)?;
         self.name = name;
         Ok(())
     }
-    pub fn set_price(&mut self, price: f64) -> Result<(), String> {
+    pub fn set_price(&mut self, price: Money) -> Result<(), FieldError> {
         Self::validate_price(price)?;
         self.price = price;
         Ok(())
     }
-    pub fn set_description(&mut self, description: String) -> Result<(), String> {
+    pub fn set_description(&mut self, description: String) -> Result<(), FieldError> {
         Self::validate_description(&description)?;
         self.description = description;
         Ok(())
     }
-    pub fn set_image(&mut self, image: String) -> Result<(), String> {
+    pub fn set_image(&mut self, image: String) -> Result<(), FieldError> {
         Self::validate_image(&image)?;
         self.image = image;
         Ok(())
     }
-    pub fn set_date(&mut self, date: String) -> Result<(), String> {
+    pub fn set_date(&mut self, date: String) -> Result<(), FieldError> {
         Self::validate_date(&date)?;
         self.date = date;
         Ok(())
     }
-    pub fn set_quantity(&mut self, quantity: i32) -> Result<(), String> {
+    pub fn set_quantity(&mut self, quantity: i32) -> Result<(), FieldError> {
         Self::validate_quantity(quantity)?;
         self.quantity = quantity;
         Ok(())
     }
-    pub fn set_observation(&mut self, observation: String) -> Result<(), String> {
+    pub fn set_observation(&mut self, observation: String) -> Result<(), FieldError> {
         Self::validate_observation(&observation)?;
         self.observation = observation;
         Ok(())
     }
-    pub fn set_status(&mut self, status: i32) -> Result<(), String> {
+    pub fn set_status(&mut self, status: i32) -> Result<(), FieldError> {
         Self::validate_status(status)?;
         self.status = status;
         Ok(())
     }
-    pub fn set_payment_type(&mut self, payment_type: String) -> Result<(), String> {
+    pub fn set_payment_type(&mut self, payment_type: String) -> Result<(), FieldError> {
         Self::validate_payment_type(&payment_type)?;
         self.payment_type = payment_type;
         Ok(())
     }
+    pub fn set_options(&mut self, options: Vec<SelectedOption>) {
+        self.options = options;
+    }
+    pub fn set_components(&mut self, components: Vec<BundleComponent>) {
+        self.components = components;
+    }
 
     // Validate
-    fn validate_name(name: &str) -> Result<(), String> {
+    fn validate_name(name: &str) -> Result<(), FieldError> {
         if name.is_empty() {
-            return Err(String::from("Name cannot be empty"));
+            return Err(FieldError::required("Name cannot be empty"));
         }
         if name.len() > 255 {
-            return Err(String::from("Name is too long"));
+            return Err(FieldError::too_long("Name is too long", 255));
         }
         Ok(())
     }
-    fn validate_price(price: f64) -> Result<(), String> {
-        if price <= 0.0 {
-            return Err(String::from("Price must be greater than zero"));
+    fn validate_price(price: Money) -> Result<(), FieldError> {
+        if !price.is_positive() {
+            return Err(FieldError::min("Price must be greater than zero", 0.01));
         }
         Ok(())
     }
-    fn validate_description(description: &str) -> Result<(), String> {
+    fn validate_description(description: &str) -> Result<(), FieldError> {
         if description.is_empty() {
-            return Err(String::from("Description cannot be empty"));
+            return Err(FieldError::required("Description cannot be empty"));
         }
         Ok(())
     }
-    fn validate_image(image: &str) -> Result<(), String> {
+    fn validate_image(image: &str) -> Result<(), FieldError> {
         if image.is_empty() {
-            return Err(String::from("Image cannot be empty"));
+            return Err(FieldError::required("Image cannot be empty"));
         }
         Ok(())
     }
-    fn validate_date(date: &str) -> Result<(), String> {
+    fn validate_date(date: &str) -> Result<(), FieldError> {
         if date.is_empty() {
-            return Err(String::from("Date cannot be empty"));
+            return Err(FieldError::required("Date cannot be empty"));
         }
         Ok(())
     }
-    fn validate_quantity(quantity: i32) -> Result<(), String> {
+    fn validate_quantity(quantity: i32) -> Result<(), FieldError> {
         if quantity <= 0 {
-            return Err(String::from("Quantity must be greater than zero"));
+            return Err(FieldError::min("Quantity must be greater than zero", 1));
         }
         Ok(())
     }
-    fn validate_observation(observation: &str) -> Result<(), String> {
+    fn validate_observation(observation: &str) -> Result<(), FieldError> {
         if observation.len() > 255 {
-            return Err(String::from("Observation is too long"));
+            return Err(FieldError::too_long("Observation is too long", 255));
         }
         Ok(())
     }
-    fn validate_status(status: i32) -> Result<(), String> {
-        if status != 0 && status != 1 && status != 2 && status != 3 && status != 4 {
-            return Err(String::from(
-                "Status must be '0' or '1' or '2' or '3' or '4'",
-            ));
+    fn validate_status(status: i32) -> Result<(), FieldError> {
+        if status != 0 && status != 1 && status != 2 && status != 3 && status != 4 && status != 5 && status != 6 {
+            return Err(FieldError::new(
+                "out_of_range",
+                String::from("Status must be '0' or '1' or '2' or '3' or '4' or '5' or '6'"),
+            )
+            .with_param("min", 0)
+            .with_param("max", 6));
         }
         Ok(())
     }
 
-    fn validate_payment_type(payment_type: &str) -> Result<(), String> {
+    fn validate_payment_type(payment_type: &str) -> Result<(), FieldError> {
         if payment_type.is_empty() {
-            return Err(String::from("Payment tpe cannot be empty"));
+            return Err(FieldError::required("Payment tpe cannot be empty"));
         }
         Ok(())
     }
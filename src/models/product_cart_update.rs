@@ -1,6 +1,8 @@
 use actix_web::web;
 use serde::{Deserialize, Serialize};
 
+use super::validation::{FieldError, ValidationErrors};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProductCartUpdate {
     status: i32,
@@ -9,25 +11,26 @@ pub struct ProductCartUpdate {
 }
 
 impl ProductCartUpdate {
-    pub fn new(status: i32, payment_type: String, id_purchase: i32) -> Result<Self, String> {
+    pub fn new(status: i32, payment_type: String, id_purchase: i32) -> Result<Self, ValidationErrors> {
         let mut new_product_cart = Self {
             status: 0,
             payment_type: String::new(),
             id_purchase: id_purchase,
         };
 
-        new_product_cart.set_status(status)?;
-        new_product_cart.set_payment_type(payment_type)?;
+        let mut errors = ValidationErrors::new();
+        errors.check("status", new_product_cart.set_status(status));
+        errors.check("payment_type", new_product_cart.set_payment_type(payment_type));
 
-        Ok(new_product_cart)
+        errors.result(new_product_cart)
     }
 
-    pub fn from_json(json: web::Json<ProductCartUpdate>) -> Result<ProductCartUpdate, String> {
-        Ok(ProductCartUpdate::new(
+    pub fn from_json(json: web::Json<ProductCartUpdate>) -> Result<ProductCartUpdate, ValidationErrors> {
+        ProductCartUpdate::new(
             json.status(),
             json.payment_type(),
             json.id_purchase,
-        )?)
+        )
     }
 
     // Get
@@ -42,29 +45,32 @@ impl ProductCartUpdate {
     }
 
     // Set
-    pub fn set_status(&mut self, status: i32) -> Result<(), String> {
+    pub fn set_status(&mut self, status: i32) -> Result<(), FieldError> {
         Self::validate_status(status)?;
         self.status = status;
         Ok(())
     }
-    pub fn set_payment_type(&mut self, payment_type: String) -> Result<(), String> {
+    pub fn set_payment_type(&mut self, payment_type: String) -> Result<(), FieldError> {
         Self::validate_payment_type(&payment_type)?;
         self.payment_type = payment_type;
         Ok(())
     }
 
     // Validate
-    fn validate_status(status: i32) -> Result<(), String> {
-        if status != 0 && status != 1 && status != 2 && status != 3 && status != 4 {
-            return Err(String::from(
-                "Status must be '0' or '1' or '2' or '3' or '4'",
-            ));
+    fn validate_status(status: i32) -> Result<(), FieldError> {
+        if status != 0 && status != 1 && status != 2 && status != 3 && status != 4 && status != 5 && status != 6 {
+            return Err(FieldError::new(
+                "out_of_range",
+                String::from("Status must be '0' or '1' or '2' or '3' or '4' or '5' or '6'"),
+            )
+            .with_param("min", 0)
+            .with_param("max", 6));
         }
         Ok(())
     }
-    fn validate_payment_type(payment_type: &str) -> Result<(), String> {
+    fn validate_payment_type(payment_type: &str) -> Result<(), FieldError> {
         if payment_type.is_empty() {
-            return Err(String::from("Payment tpe cannot be empty"));
+            return Err(FieldError::required("Payment tpe cannot be empty"));
         }
         Ok(())
     }
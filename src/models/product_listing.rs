@@ -0,0 +1,247 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+use super::nutrition::Allergen;
+
+pub const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProductSort {
+    Name,
+    Price,
+    Newest,
+}
+
+impl ProductSort {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProductSort::Name => "name",
+            ProductSort::Price => "price",
+            ProductSort::Newest => "newest",
+        }
+    }
+
+    pub fn parse(sort: &str) -> Result<ProductSort, String> {
+        match sort {
+            "name" => Ok(ProductSort::Name),
+            "price" => Ok(ProductSort::Price),
+            "newest" => Ok(ProductSort::Newest),
+            _ => Err(format!("Invalid product sort '{}'", sort)),
+        }
+    }
+}
+
+// Posição do último produto de uma página: o id e o valor do campo de ordenação.
+// É enviado ao cliente codificado, sem significado para ele.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductCursor {
+    sort: ProductSort,
+    id: i32,
+    key: String,
+}
+
+impl ProductCursor {
+    pub fn new(sort: ProductSort, id: i32, key: String) -> ProductCursor {
+        ProductCursor { sort, id, key }
+    }
+
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(format!("{}|{}|{}", self.sort.as_str(), self.id, self.key))
+    }
+
+    pub fn decode(cursor: &str) -> Result<ProductCursor, String> {
+        let invalid = || String::from("Invalid cursor");
+
+        let decoded = URL_SAFE_NO_PAD.decode(cursor).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+        let mut parts = decoded.splitn(3, '|');
+
+        let sort = ProductSort::parse(parts.next().unwrap_or_default()).map_err(|_| invalid())?;
+        let id = parts
+            .next()
+            .and_then(|id| id.parse::<i32>().ok())
+            .ok_or_else(invalid)?;
+        let key = parts.next().ok_or_else(invalid)?.to_string();
+
+        Ok(ProductCursor::new(sort, id, key))
+    }
+
+    // Get
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+    pub fn key(&self) -> String {
+        self.key.clone()
+    }
+}
+
+// Filtros e ordenação da listagem de produtos
+#[derive(Debug, Clone)]
+pub struct ProductListing {
+    sort: ProductSort,
+    category_id: Option<i32>,
+    min_price: Option<Money>,
+    max_price: Option<Money>,
+    only_available: bool,
+    // Produtos que contêm algum destes alergênicos ficam fora da listagem
+    exclude_allergens: Vec<Allergen>,
+    limit: i64,
+    cursor: Option<ProductCursor>,
+}
+
+impl ProductListing {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        sort: ProductSort,
+        category_id: Option<i32>,
+        min_price: Option<Money>,
+        max_price: Option<Money>,
+        only_available: bool,
+        exclude_allergens: Vec<Allergen>,
+        limit: i64,
+        cursor: Option<String>,
+    ) -> Result<ProductListing, String> {
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(format!("Listing limit must be between 1 and {}", MAX_LIMIT));
+        }
+        if min_price.is_some_and(|price| price.is_negative())
+            || max_price.is_some_and(|price| price.is_negative())
+        {
+            return Err(String::from("Price filter cannot be negative"));
+        }
+        if let (Some(min_price), Some(max_price)) = (min_price, max_price) {
+            if min_price > max_price {
+                return Err(String::from(
+                    "Minimum price cannot be greater than maximum price",
+                ));
+            }
+        }
+
+        // O cursor só vale para a mesma ordenação em que foi gerado
+        let cursor = match cursor {
+            Some(cursor) => {
+                let cursor = ProductCursor::decode(&cursor)?;
+                if cursor.sort != sort {
+                    return Err(String::from("Cursor does not match the requested sort"));
+                }
+                Some(cursor)
+            }
+            None => None,
+        };
+
+        Ok(ProductListing {
+            sort,
+            category_id,
+            min_price,
+            max_price,
+            only_available,
+            exclude_allergens,
+            limit,
+            cursor,
+        })
+    }
+
+    // Get
+    pub fn sort(&self) -> ProductSort {
+        self.sort
+    }
+    pub fn category_id(&self) -> Option<i32> {
+        self.category_id
+    }
+    pub fn min_price(&self) -> Option<Money> {
+        self.min_price
+    }
+    pub fn max_price(&self) -> Option<Money> {
+        self.max_price
+    }
+    pub fn only_available(&self) -> bool {
+        self.only_available
+    }
+    pub fn exclude_allergens(&self) -> Vec<Allergen> {
+        self.exclude_allergens.clone()
+    }
+    pub fn limit(&self) -> i64 {
+        self.limit
+    }
+    pub fn cursor(&self) -> Option<ProductCursor> {
+        self.cursor.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn listing(sort: ProductSort, cursor: Option<String>) -> Result<ProductListing, String> {
+        ProductListing::new(
+            sort,
+            None,
+            None,
+            None,
+            false,
+            Vec::new(),
+            DEFAULT_LIMIT,
+            cursor,
+        )
+    }
+
+    #[test]
+    fn cursor_round_trips() {
+        for cursor in [
+            ProductCursor::new(ProductSort::Name, 7, String::from("Feijão | tropeiro")),
+            ProductCursor::new(ProductSort::Price, 42, String::from("12.50")),
+            ProductCursor::new(ProductSort::Newest, 1, String::new()),
+        ] {
+            let encoded = cursor.encode();
+            assert!(encoded
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+            assert_eq!(ProductCursor::decode(&encoded), Ok(cursor));
+        }
+    }
+
+    #[test]
+    fn decode_rejects_tampered_cursors() {
+        let encode = |raw: &str| URL_SAFE_NO_PAD.encode(raw);
+        assert!(ProductCursor::decode("not base64!").is_err());
+        assert!(ProductCursor::decode(&URL_SAFE_NO_PAD.encode([0xff, 0xfe])).is_err());
+        assert!(ProductCursor::decode(&encode("rating|1|x")).is_err());
+        assert!(ProductCursor::decode(&encode("name|abc|x")).is_err());
+        assert!(ProductCursor::decode(&encode("name|1")).is_err());
+        assert!(ProductCursor::decode("").is_err());
+    }
+
+    #[test]
+    fn listing_rejects_a_cursor_from_another_sort() {
+        let cursor = ProductCursor::new(ProductSort::Price, 3, String::from("9.90")).encode();
+        assert!(listing(ProductSort::Name, Some(cursor.clone())).is_err());
+
+        let listing = listing(ProductSort::Price, Some(cursor)).unwrap();
+        assert_eq!(listing.cursor().map(|cursor| cursor.id()), Some(3));
+    }
+
+    #[test]
+    fn listing_validates_the_price_range_and_limit() {
+        let price = |cents| Some(Money::from_cents(cents));
+        let new = |min, max, limit| {
+            ProductListing::new(
+                ProductSort::Name,
+                None,
+                min,
+                max,
+                false,
+                Vec::new(),
+                limit,
+                None,
+            )
+        };
+        assert!(new(price(1000), price(500), DEFAULT_LIMIT).is_err());
+        assert!(new(price(-1), None, DEFAULT_LIMIT).is_err());
+        assert!(new(price(500), price(500), DEFAULT_LIMIT).is_ok());
+        assert!(new(None, None, 0).is_err());
+        assert!(new(None, None, MAX_LIMIT + 1).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use serde::Serialize;
+
+use super::product::Product;
+
+// Maior número de palavras consideradas na busca
+const MAX_TERMS: usize = 10;
+const MAX_QUERY_LENGTH: usize = 100;
+pub const DEFAULT_LIMIT: i64 = 20;
+const MAX_LIMIT: i64 = 100;
+
+// Busca textual de produtos pelo nome e pela descrição
+#[derive(Debug, Clone)]
+pub struct ProductSearch {
+    terms: Vec<String>,
+    prefix: bool,
+    category_id: Option<i32>,
+    only_available: bool,
+    limit: i64,
+    offset: i64,
+}
+
+impl ProductSearch {
+    pub fn new(
+        query: String,
+        category_id: Option<i32>,
+        only_available: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<ProductSearch, String> {
+        if query.chars().count() > MAX_QUERY_LENGTH {
+            return Err(format!(
+                "Search query cannot exceed {} characters",
+                MAX_QUERY_LENGTH
+            ));
+        }
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(format!("Search limit must be between 1 and {}", MAX_LIMIT));
+        }
+        if offset < 0 {
+            return Err(String::from("Search offset cannot be negative"));
+        }
+
+        // Somente letras e números entram na consulta, o que evita erros de sintaxe no 'to_tsquery'
+        let terms: Vec<String> = query
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|term| !term.is_empty())
+            .take(MAX_TERMS)
+            .map(|term| term.to_lowercase())
+            .collect();
+        if terms.is_empty() {
+            return Err(String::from("Search query must contain letters or numbers"));
+        }
+
+        Ok(ProductSearch {
+            terms,
+            // A última palavra ainda está sendo digitada, a menos que a busca termine com um espaço
+            prefix: !query.ends_with(char::is_whitespace),
+            category_id,
+            only_available,
+            limit,
+            offset,
+        })
+    }
+
+    // Get
+    pub fn category_id(&self) -> Option<i32> {
+        self.category_id
+    }
+    pub fn only_available(&self) -> bool {
+        self.only_available
+    }
+    pub fn limit(&self) -> i64 {
+        self.limit
+    }
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    // Consulta no formato do 'to_tsquery', com todas as palavras obrigatórias e a última como prefixo.
+    // Ex.: "feijao tro" => "feijao & tro:*"
+    pub fn tsquery(&self) -> String {
+        let mut tsquery = self.terms.join(" & ");
+        if self.prefix {
+            tsquery.push_str(":*");
+        }
+        tsquery
+    }
+}
+
+// Produto encontrado na busca. 'name_highlight' e 'snippet' marcam as palavras encontradas com <mark></mark>.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProductSearchResult {
+    product: Product,
+    rank: f32,
+    name_highlight: String,
+    snippet: String,
+}
+
+impl ProductSearchResult {
+    pub fn new(
+        product: Product,
+        rank: f32,
+        name_highlight: String,
+        snippet: String,
+    ) -> ProductSearchResult {
+        ProductSearchResult {
+            product,
+            rank,
+            name_highlight,
+            snippet,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tsquery(query: &str) -> String {
+        ProductSearch::new(query.to_string(), None, false, DEFAULT_LIMIT, 0)
+            .unwrap()
+            .tsquery()
+    }
+
+    #[test]
+    fn tsquery_requires_every_term_and_prefixes_the_last() {
+        assert_eq!(tsquery("feijao tro"), "feijao & tro:*");
+        assert_eq!(tsquery("Feijão Tropeiro "), "feijão & tropeiro");
+        assert_eq!(tsquery("pão"), "pão:*");
+    }
+
+    #[test]
+    fn tsquery_drops_operators_and_punctuation() {
+        assert_eq!(tsquery("x-burger & (bacon)!"), "x & burger & bacon:*");
+        assert_eq!(tsquery("a:* | b' "), "a & b");
+        assert_eq!(tsquery("coca 2l"), "coca & 2l:*");
+    }
+
+    #[test]
+    fn tsquery_keeps_at_most_max_terms() {
+        let query = (1..=15)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        assert_eq!(tsquery(&query), "1 & 2 & 3 & 4 & 5 & 6 & 7 & 8 & 9 & 10:*");
+    }
+
+    #[test]
+    fn new_rejects_queries_without_terms_and_invalid_pages() {
+        assert!(ProductSearch::new(String::from(" &! "), None, false, 20, 0).is_err());
+        assert!(ProductSearch::new("a".repeat(MAX_QUERY_LENGTH + 1), None, false, 20, 0).is_err());
+        assert!(ProductSearch::new(String::from("pizza"), None, false, 0, 0).is_err());
+        assert!(ProductSearch::new(String::from("pizza"), None, false, MAX_LIMIT + 1, 0).is_err());
+        assert!(ProductSearch::new(String::from("pizza"), None, false, 20, -1).is_err());
+    }
+}
@@ -0,0 +1,332 @@
+use actix_web::web;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use super::money::Money;
+use super::order_total::PricedItem;
+use super::validation::{FieldError, ValidationErrors};
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PromotionKind {
+    Percentage { percent: f64 },
+    Fixed { amount: Money },
+    FreeDelivery,
+}
+
+// Promoções sem código são aplicadas automaticamente a um produto ou a uma categoria, as com código são cupons.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Promotion {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    #[serde(default)]
+    code: Option<String>,
+    description: String,
+    kind: PromotionKind,
+    #[serde(default)]
+    product_id: Option<i32>,
+    #[serde(default)]
+    category_id: Option<i32>,
+    #[serde(default)]
+    min_order: Money,
+    #[serde(default)]
+    valid_from: Option<String>,
+    #[serde(default)]
+    valid_until: Option<String>,
+    #[serde(default)]
+    max_uses: Option<i32>,
+    #[serde(default)]
+    max_uses_per_user: Option<i32>,
+    active: bool,
+}
+
+impl Promotion {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<i32>,
+        code: Option<String>,
+        description: String,
+        kind: PromotionKind,
+        product_id: Option<i32>,
+        category_id: Option<i32>,
+        min_order: Money,
+        valid_from: Option<String>,
+        valid_until: Option<String>,
+        max_uses: Option<i32>,
+        max_uses_per_user: Option<i32>,
+        active: bool,
+    ) -> Result<Promotion, ValidationErrors> {
+        let mut new_promotion = Promotion {
+            id,
+            code: None,
+            description: String::new(),
+            kind: PromotionKind::FreeDelivery,
+            product_id: None,
+            category_id: None,
+            min_order: Money::zero(),
+            valid_from: None,
+            valid_until: None,
+            max_uses: None,
+            max_uses_per_user: None,
+            active: false,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("code", new_promotion.set_code(code));
+        errors.check("description", new_promotion.set_description(description));
+        errors.check("kind", new_promotion.set_kind(kind));
+        // As regras com mais de um campo informam o campo no próprio erro
+        errors.check("", new_promotion.set_scope(product_id, category_id));
+        errors.check("min_order", new_promotion.set_min_order(min_order));
+        errors.check("", new_promotion.set_validity(valid_from, valid_until));
+        errors.check("", new_promotion.set_limits(max_uses, max_uses_per_user));
+        new_promotion.set_active(active);
+
+        errors.result(new_promotion)
+    }
+
+    pub fn from_json(json: web::Json<Promotion>) -> Result<Promotion, ValidationErrors> {
+        Promotion::new(
+            json.id(),
+            json.code(),
+            json.description(),
+            json.kind(),
+            json.product_id(),
+            json.category_id(),
+            json.min_order(),
+            json.valid_from(),
+            json.valid_until(),
+            json.max_uses(),
+            json.max_uses_per_user(),
+            json.active(),
+        )
+    }
+
+    // Calcula o desconto da promoção sobre os itens do pedido e a taxa de entrega
+    pub fn discount(&self, items: &[PricedItem], subtotal: Money, delivery_fee: Money) -> Money {
+        if subtotal < self.min_order {
+            return Money::zero();
+        }
+
+        let base: Money = match (self.product_id, self.category_id) {
+            (Some(product_id), _) => items
+                .iter()
+                .filter(|item| item.product_id() == product_id)
+                .map(|item| item.line_total())
+                .sum(),
+            (None, Some(category_id)) => items
+                .iter()
+                .filter(|item| item.category_id() == Some(category_id))
+                .map(|item| item.line_total())
+                .sum(),
+            (None, None) => subtotal,
+        };
+
+        match self.kind {
+            PromotionKind::Percentage { percent } => base.percent(percent),
+            PromotionKind::Fixed { amount } => amount.min(base),
+            PromotionKind::FreeDelivery => delivery_fee,
+        }
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn code(&self) -> Option<String> {
+        self.code.clone()
+    }
+    pub fn description(&self) -> String {
+        self.description.clone()
+    }
+    pub fn kind(&self) -> PromotionKind {
+        self.kind.clone()
+    }
+    pub fn product_id(&self) -> Option<i32> {
+        self.product_id
+    }
+    pub fn category_id(&self) -> Option<i32> {
+        self.category_id
+    }
+    pub fn min_order(&self) -> Money {
+        self.min_order
+    }
+    pub fn valid_from(&self) -> Option<String> {
+        self.valid_from.clone()
+    }
+    pub fn valid_until(&self) -> Option<String> {
+        self.valid_until.clone()
+    }
+    pub fn max_uses(&self) -> Option<i32> {
+        self.max_uses
+    }
+    pub fn max_uses_per_user(&self) -> Option<i32> {
+        self.max_uses_per_user
+    }
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    // Set
+    // Os códigos de cupom são guardados em maiúsculas
+    pub fn set_code(&mut self, code: Option<String>) -> Result<(), FieldError> {
+        let code = code.map(|c| c.trim().to_uppercase());
+        Self::validate_code(&code)?;
+        self.code = code;
+        Ok(())
+    }
+    pub fn set_description(&mut self, description: String) -> Result<(), FieldError> {
+        Self::validate_description(&description)?;
+        self.description = description;
+        Ok(())
+    }
+    pub fn set_kind(&mut self, kind: PromotionKind) -> Result<(), FieldError> {
+        Self::validate_kind(&kind)?;
+        self.kind = kind;
+        Ok(())
+    }
+    pub fn set_scope(
+        &mut self,
+        product_id: Option<i32>,
+        category_id: Option<i32>,
+    ) -> Result<(), FieldError> {
+        Self::validate_scope(&self.code, product_id, category_id)?;
+        self.product_id = product_id;
+        self.category_id = category_id;
+        Ok(())
+    }
+    pub fn set_min_order(&mut self, min_order: Money) -> Result<(), FieldError> {
+        Self::validate_min_order(min_order)?;
+        self.min_order = min_order;
+        Ok(())
+    }
+    pub fn set_validity(
+        &mut self,
+        valid_from: Option<String>,
+        valid_until: Option<String>,
+    ) -> Result<(), FieldError> {
+        Self::validate_validity(&valid_from, &valid_until)?;
+        self.valid_from = valid_from;
+        self.valid_until = valid_until;
+        Ok(())
+    }
+    pub fn set_limits(
+        &mut self,
+        max_uses: Option<i32>,
+        max_uses_per_user: Option<i32>,
+    ) -> Result<(), FieldError> {
+        Self::validate_limits(max_uses, max_uses_per_user)?;
+        self.max_uses = max_uses;
+        self.max_uses_per_user = max_uses_per_user;
+        Ok(())
+    }
+    pub fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+
+    // Validate
+    fn validate_code(code: &Option<String>) -> Result<(), FieldError> {
+        if let Some(code) = code {
+            if code.len() < 3 || code.len() > 20 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(FieldError::new(
+                    "length",
+                    String::from("Coupon code must have 3 to 20 letters or digits"),
+                )
+                .with_param("min", 3)
+                .with_param("max", 20));
+            }
+        }
+        Ok(())
+    }
+    fn validate_description(description: &str) -> Result<(), FieldError> {
+        if description.is_empty() {
+            return Err(FieldError::required("Promotion description cannot be empty"));
+        }
+        Ok(())
+    }
+    fn validate_kind(kind: &PromotionKind) -> Result<(), FieldError> {
+        match kind {
+            PromotionKind::Percentage { percent } if *percent <= 0.0 || *percent > 100.0 => Err(
+                FieldError::new(
+                    "out_of_range",
+                    String::from("Promotion percent must be greater than 0 and at most 100"),
+                )
+                .with_param("min", 0)
+                .with_param("max", 100)
+                .at("percent"),
+            ),
+            PromotionKind::Fixed { amount } if !amount.is_positive() => {
+                Err(FieldError::min("Promotion amount must be greater than 0", 0.01).at("amount"))
+            }
+            _ => Ok(()),
+        }
+    }
+    fn validate_scope(
+        code: &Option<String>,
+        product_id: Option<i32>,
+        category_id: Option<i32>,
+    ) -> Result<(), FieldError> {
+        if product_id.is_some() && category_id.is_some() {
+            return Err(FieldError::invalid_format(
+                "Promotion must target either a product or a category, not both",
+            )
+            .at("category_id"));
+        }
+        if code.is_none() && product_id.is_none() && category_id.is_none() {
+            return Err(FieldError::required(
+                "Promotions without a coupon code must target a product or a category",
+            )
+            .at("product_id"));
+        }
+        Ok(())
+    }
+    fn validate_min_order(min_order: Money) -> Result<(), FieldError> {
+        if min_order.is_negative() {
+            return Err(FieldError::min("Promotion minimum order cannot be negative", 0));
+        }
+        Ok(())
+    }
+    fn validate_validity(
+        valid_from: &Option<String>,
+        valid_until: &Option<String>,
+    ) -> Result<(), FieldError> {
+        let parse = |field: &str, date: &Option<String>| -> Result<Option<NaiveDateTime>, FieldError> {
+            match date {
+                Some(date) => NaiveDateTime::parse_from_str(date, DATE_FORMAT)
+                    .map(Some)
+                    .map_err(|_| {
+                        FieldError::new(
+                            "invalid_format",
+                            format!("Date '{}' must be in the format YYYY-MM-DD HH:MM:SS", date),
+                        )
+                        .at(field)
+                    }),
+                None => Ok(None),
+            }
+        };
+
+        let from = parse("valid_from", valid_from)?;
+        let until = parse("valid_until", valid_until)?;
+        if let (Some(from), Some(until)) = (from, until) {
+            if until <= from {
+                return Err(FieldError::invalid_format(
+                    "Promotion 'valid_until' must be after 'valid_from'",
+                )
+                .at("valid_until"));
+            }
+        }
+        Ok(())
+    }
+    fn validate_limits(max_uses: Option<i32>, max_uses_per_user: Option<i32>) -> Result<(), FieldError> {
+        if max_uses.is_some_and(|n| n <= 0) {
+            return Err(FieldError::min("Promotion usage limits must be greater than 0", 1).at("max_uses"));
+        }
+        if max_uses_per_user.is_some_and(|n| n <= 0) {
+            return Err(FieldError::min("Promotion usage limits must be greater than 0", 1)
+                .at("max_uses_per_user"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use actix_web::web;
+use chrono::{NaiveDate, NaiveTime};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use std::env;
+
+pub const TIME_FORMAT: &str = "%H:%M";
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+lazy_static! {
+    // Fuso horário do restaurante, usado para avaliar os horários do cardápio.
+    // O nome entra direto nas consultas SQL, por isso só são aceitos nomes da base IANA.
+    static ref RESTAURANT_TIMEZONE: String = env::var("RESTAURANT_TIMEZONE")
+        .ok()
+        .filter(|tz| {
+            !tz.is_empty()
+                && tz
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '-' | '+'))
+        })
+        .unwrap_or_else(|| String::from("America/Sao_Paulo"));
+}
+
+pub fn restaurant_timezone() -> &'static str {
+    RESTAURANT_TIMEZONE.as_str()
+}
+
+// Intervalo em que o item é servido. 'weekday' vai de 0 (domingo) a 6 (sábado).
+// Quando 'end' é menor ou igual a 'start' o intervalo passa da meia-noite e termina no dia seguinte.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRange {
+    weekday: i16,
+    start: String,
+    end: String,
+}
+
+impl ScheduleRange {
+    pub fn new(weekday: i16, start: String, end: String) -> Result<ScheduleRange, String> {
+        if !(0..=6).contains(&weekday) {
+            return Err(String::from(
+                "Schedule weekday must be between 0 (Sunday) and 6 (Saturday)",
+            ));
+        }
+        Self::validate_time(&start)?;
+        Self::validate_time(&end)?;
+
+        Ok(ScheduleRange {
+            weekday,
+            start,
+            end,
+        })
+    }
+
+    // Get
+    pub fn weekday(&self) -> i16 {
+        self.weekday
+    }
+    pub fn start(&self) -> String {
+        self.start.clone()
+    }
+    pub fn end(&self) -> String {
+        self.end.clone()
+    }
+    // Horários já validados no 'new'
+    pub fn start_time(&self) -> NaiveTime {
+        NaiveTime::parse_from_str(&self.start, TIME_FORMAT).unwrap_or_default()
+    }
+    pub fn end_time(&self) -> NaiveTime {
+        NaiveTime::parse_from_str(&self.end, TIME_FORMAT).unwrap_or_default()
+    }
+    pub fn overnight(&self) -> bool {
+        self.end_time() <= self.start_time()
+    }
+
+    // Validate
+    fn validate_time(time: &str) -> Result<(), String> {
+        NaiveTime::parse_from_str(time, TIME_FORMAT)
+            .map(|_| ())
+            .map_err(|_| format!("Time '{}' must be in the format HH:MM", time))
+    }
+}
+
+// Exceção para uma data, que substitui os intervalos da semana: indisponível num feriado
+// ou disponível num dia em que normalmente não é servido.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleException {
+    date: String,
+    available: bool,
+}
+
+impl ScheduleException {
+    pub fn new(date: String, available: bool) -> Result<ScheduleException, String> {
+        NaiveDate::parse_from_str(&date, DATE_FORMAT)
+            .map_err(|_| format!("Date '{}' must be in the format YYYY-MM-DD", date))?;
+
+        Ok(ScheduleException { date, available })
+    }
+
+    // Get
+    pub fn date(&self) -> String {
+        self.date.clone()
+    }
+    pub fn available(&self) -> bool {
+        self.available
+    }
+}
+
+// Horários de um produto ou categoria. Sem intervalos o item é servido a qualquer hora.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Schedule {
+    #[serde(default)]
+    ranges: Vec<ScheduleRange>,
+    #[serde(default)]
+    exceptions: Vec<ScheduleException>,
+}
+
+impl Schedule {
+    pub fn new(
+        ranges: Vec<ScheduleRange>,
+        exceptions: Vec<ScheduleException>,
+    ) -> Result<Schedule, String> {
+        for (i, exception) in exceptions.iter().enumerate() {
+            if exceptions[..i].iter().any(|e| e.date == exception.date) {
+                return Err(format!(
+                    "Date {} is repeated in the schedule exceptions",
+                    exception.date
+                ));
+            }
+        }
+
+        Ok(Schedule { ranges, exceptions })
+    }
+
+    pub fn from_json(json: web::Json<Schedule>) -> Result<Schedule, String> {
+        let ranges = json
+            .ranges
+            .iter()
+            .map(|r| ScheduleRange::new(r.weekday(), r.start(), r.end()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let exceptions = json
+            .exceptions
+            .iter()
+            .map(|e| ScheduleException::new(e.date(), e.available()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Schedule::new(ranges, exceptions)
+    }
+
+    // Get
+    pub fn ranges(&self) -> Vec<ScheduleRange> {
+        self.ranges.clone()
+    }
+    pub fn exceptions(&self) -> Vec<ScheduleException> {
+        self.exceptions.clone()
+    }
+}
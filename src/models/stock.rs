@@ -0,0 +1,483 @@
+use actix_web::web;
+use serde::{Deserialize, Serialize};
+
+use super::validation::{FieldError, ValidationErrors};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdjustmentReason {
+    Restock,
+    Waste,
+    Correction,
+    Order,
+    Cancellation,
+}
+
+impl AdjustmentReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AdjustmentReason::Restock => "restock",
+            AdjustmentReason::Waste => "waste",
+            AdjustmentReason::Correction => "correction",
+            AdjustmentReason::Order => "order",
+            AdjustmentReason::Cancellation => "cancellation",
+        }
+    }
+
+    pub fn parse(reason: &str) -> Result<AdjustmentReason, String> {
+        match reason {
+            "restock" => Ok(AdjustmentReason::Restock),
+            "waste" => Ok(AdjustmentReason::Waste),
+            "correction" => Ok(AdjustmentReason::Correction),
+            "order" => Ok(AdjustmentReason::Order),
+            "cancellation" => Ok(AdjustmentReason::Cancellation),
+            _ => Err(format!("Invalid stock adjustment reason '{}'", reason)),
+        }
+    }
+}
+
+// Controle de estoque de um produto. Sem controle o produto nunca fica sem estoque.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockSettings {
+    tracked: bool,
+    #[serde(default)]
+    low_stock_threshold: i32,
+}
+
+impl StockSettings {
+    pub fn new(tracked: bool, low_stock_threshold: i32) -> Result<StockSettings, String> {
+        if low_stock_threshold < 0 {
+            return Err(String::from("Low stock threshold cannot be negative"));
+        }
+
+        Ok(StockSettings {
+            tracked,
+            low_stock_threshold,
+        })
+    }
+
+    pub fn from_json(json: web::Json<StockSettings>) -> Result<StockSettings, String> {
+        StockSettings::new(json.tracked(), json.low_stock_threshold())
+    }
+
+    // Get
+    pub fn tracked(&self) -> bool {
+        self.tracked
+    }
+    pub fn low_stock_threshold(&self) -> i32 {
+        self.low_stock_threshold
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ingredient {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<i32>,
+    name: String,
+    // Unidade de medida do estoque e das receitas, como 'kg', 'l' ou 'un'
+    unit: String,
+    // Alterado apenas por ajustes de estoque e pedidos
+    #[serde(default)]
+    stock: f64,
+    #[serde(default)]
+    low_stock_threshold: f64,
+}
+
+impl Ingredient {
+    pub fn new(
+        id: Option<i32>,
+        name: String,
+        unit: String,
+        stock: f64,
+        low_stock_threshold: f64,
+    ) -> Result<Ingredient, ValidationErrors> {
+        let mut new_ingredient = Ingredient {
+            id,
+            name: String::new(),
+            unit: String::new(),
+            stock,
+            low_stock_threshold: 0.0,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_ingredient.set_name(name));
+        errors.check("unit", new_ingredient.set_unit(unit));
+        errors.check(
+            "low_stock_threshold",
+            new_ingredient.set_low_stock_threshold(low_stock_threshold),
+        );
+
+        errors.result(new_ingredient)
+    }
+
+    pub fn from_json(json: web::Json<Ingredient>) -> Result<Ingredient, ValidationErrors> {
+        Ingredient::new(
+            json.id(),
+            json.name(),
+            json.unit(),
+            json.stock(),
+            json.low_stock_threshold(),
+        )
+    }
+
+    // Get
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn unit(&self) -> String {
+        self.unit.clone()
+    }
+    pub fn stock(&self) -> f64 {
+        self.stock
+    }
+    pub fn low_stock_threshold(&self) -> f64 {
+        self.low_stock_threshold
+    }
+
+    // Set
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        Self::validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_unit(&mut self, unit: String) -> Result<(), FieldError> {
+        Self::validate_unit(&unit)?;
+        self.unit = unit;
+        Ok(())
+    }
+    pub fn set_low_stock_threshold(&mut self, low_stock_threshold: f64) -> Result<(), FieldError> {
+        Self::validate_low_stock_threshold(low_stock_threshold)?;
+        self.low_stock_threshold = low_stock_threshold;
+        Ok(())
+    }
+
+    // Validate
+    fn validate_name(name: &str) -> Result<(), FieldError> {
+        if name.trim().is_empty() {
+            return Err(FieldError::required("Ingredient name cannot be empty"));
+        }
+        if name.chars().count() > 50 {
+            return Err(FieldError::too_long(
+                "Ingredient name cannot exceed 50 characters",
+                50,
+            ));
+        }
+        Ok(())
+    }
+    fn validate_unit(unit: &str) -> Result<(), FieldError> {
+        if unit.trim().is_empty() || unit.chars().count() > 10 {
+            return Err(FieldError::new(
+                "length",
+                String::from("Ingredient unit must have 1 to 10 characters"),
+            )
+            .with_param("min", 1)
+            .with_param("max", 10));
+        }
+        Ok(())
+    }
+    fn validate_low_stock_threshold(low_stock_threshold: f64) -> Result<(), FieldError> {
+        if low_stock_threshold < 0.0 || !low_stock_threshold.is_finite() {
+            return Err(FieldError::min("Low stock threshold cannot be negative", 0));
+        }
+        Ok(())
+    }
+}
+
+// Quantidade de um ingrediente usada em uma unidade do produto
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecipeItem {
+    ingredient_id: i32,
+    amount: f64,
+}
+
+impl RecipeItem {
+    pub fn new(ingredient_id: i32, amount: f64) -> Result<RecipeItem, String> {
+        if amount <= 0.0 || !amount.is_finite() {
+            return Err(String::from("Recipe amount must be greater than 0"));
+        }
+
+        Ok(RecipeItem {
+            ingredient_id,
+            amount,
+        })
+    }
+
+    pub fn from_json(json: web::Json<Vec<RecipeItem>>) -> Result<Vec<RecipeItem>, String> {
+        let mut recipe: Vec<RecipeItem> = Vec::new();
+        for item in json.iter() {
+            if recipe.iter().any(|r| r.ingredient_id() == item.ingredient_id()) {
+                return Err(format!(
+                    "Ingredient {} is repeated in the recipe",
+                    item.ingredient_id()
+                ));
+            }
+            recipe.push(RecipeItem::new(item.ingredient_id(), item.amount())?);
+        }
+        Ok(recipe)
+    }
+
+    // Get
+    pub fn ingredient_id(&self) -> i32 {
+        self.ingredient_id
+    }
+    pub fn amount(&self) -> f64 {
+        self.amount
+    }
+}
+
+// Ajuste manual de estoque de um produto ou de um ingrediente
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StockAdjustment {
+    #[serde(default)]
+    product_id: Option<i32>,
+    #[serde(default)]
+    ingredient_id: Option<i32>,
+    delta: f64,
+    reason: AdjustmentReason,
+    #[serde(default)]
+    note: String,
+}
+
+impl StockAdjustment {
+    pub fn new(
+        product_id: Option<i32>,
+        ingredient_id: Option<i32>,
+        delta: f64,
+        reason: AdjustmentReason,
+        note: String,
+    ) -> Result<StockAdjustment, ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if product_id.is_some() == ingredient_id.is_some() {
+            errors.check(
+                "",
+                Err(FieldError::invalid_format(
+                    "Stock adjustment must target either a product or an ingredient",
+                )),
+            );
+        }
+        errors.check("delta", Self::validate_delta(product_id.is_some(), delta));
+        errors.check("reason", Self::validate_reason(reason));
+        errors.check("note", Self::validate_note(&note));
+
+        errors.result(StockAdjustment {
+            product_id,
+            ingredient_id,
+            delta,
+            reason,
+            note,
+        })
+    }
+
+    pub fn from_json(json: web::Json<StockAdjustment>) -> Result<StockAdjustment, ValidationErrors> {
+        StockAdjustment::new(
+            json.product_id(),
+            json.ingredient_id(),
+            json.delta(),
+            json.reason(),
+            json.note(),
+        )
+    }
+
+    // Get
+    pub fn product_id(&self) -> Option<i32> {
+        self.product_id
+    }
+    pub fn ingredient_id(&self) -> Option<i32> {
+        self.ingredient_id
+    }
+    pub fn delta(&self) -> f64 {
+        self.delta
+    }
+    pub fn reason(&self) -> AdjustmentReason {
+        self.reason
+    }
+    pub fn note(&self) -> String {
+        self.note.clone()
+    }
+
+    // Validate
+    // O estoque dos produtos é contado em unidades inteiras (INTEGER no banco)
+    fn validate_delta(is_product: bool, delta: f64) -> Result<(), FieldError> {
+        if delta == 0.0 || !delta.is_finite() {
+            return Err(FieldError::invalid_format("Stock adjustment delta cannot be 0"));
+        }
+        if is_product && delta.fract() != 0.0 {
+            return Err(FieldError::invalid_format(
+                "Product stock adjustments must be whole units",
+            ));
+        }
+        if is_product && (delta < i32::MIN as f64 || delta > i32::MAX as f64) {
+            return Err(FieldError::new(
+                "out_of_range",
+                format!(
+                    "Product stock adjustments must be between {} and {}",
+                    i32::MIN,
+                    i32::MAX
+                ),
+            )
+            .with_param("min", i32::MIN)
+            .with_param("max", i32::MAX));
+        }
+        Ok(())
+    }
+    // Pedidos e cancelamentos são registrados pelo servidor
+    fn validate_reason(reason: AdjustmentReason) -> Result<(), FieldError> {
+        if matches!(
+            reason,
+            AdjustmentReason::Order | AdjustmentReason::Cancellation
+        ) {
+            return Err(FieldError::invalid_format(
+                "Stock adjustment reason must be 'restock', 'waste' or 'correction'",
+            ));
+        }
+        Ok(())
+    }
+    fn validate_note(note: &str) -> Result<(), FieldError> {
+        if note.chars().count() > 255 {
+            return Err(FieldError::too_long(
+                "Stock adjustment note cannot exceed 255 characters",
+                255,
+            ));
+        }
+        Ok(())
+    }
+}
+
+// Registro de uma alteração de estoque
+#[derive(Debug, Clone, Serialize)]
+pub struct StockMovement {
+    id: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingredient_id: Option<i32>,
+    name: String,
+    delta: f64,
+    reason: AdjustmentReason,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    note: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id_purchase: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_id: Option<i32>,
+    created_at: String,
+}
+
+impl StockMovement {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: i32,
+        product_id: Option<i32>,
+        ingredient_id: Option<i32>,
+        name: String,
+        delta: f64,
+        reason: AdjustmentReason,
+        note: String,
+        id_purchase: Option<i32>,
+        user_id: Option<i32>,
+        created_at: String,
+    ) -> StockMovement {
+        StockMovement {
+            id,
+            product_id,
+            ingredient_id,
+            name,
+            delta,
+            reason,
+            note,
+            id_purchase,
+            user_id,
+            created_at,
+        }
+    }
+}
+
+// Nível de estoque de um produto ou ingrediente controlado
+#[derive(Debug, Clone, Serialize)]
+pub struct StockLevel {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    product_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ingredient_id: Option<i32>,
+    name: String,
+    stock: f64,
+    low_stock_threshold: f64,
+    low: bool,
+}
+
+impl StockLevel {
+    pub fn new(
+        product_id: Option<i32>,
+        ingredient_id: Option<i32>,
+        name: String,
+        stock: f64,
+        low_stock_threshold: f64,
+    ) -> StockLevel {
+        StockLevel {
+            product_id,
+            ingredient_id,
+            name,
+            stock,
+            low_stock_threshold,
+            low: stock <= low_stock_threshold,
+        }
+    }
+
+    pub fn low(&self) -> bool {
+        self.low
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(errors: ValidationErrors) -> Vec<String> {
+        errors
+            .errors()
+            .iter()
+            .map(|error| {
+                error
+                    .to_string()
+                    .split(':')
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn product_adjustment_rejects_fractional_and_out_of_range_deltas() {
+        for delta in [1.5, 3_000_000_000.0, -3_000_000_000.0] {
+            let errors = StockAdjustment::new(
+                Some(1),
+                None,
+                delta,
+                AdjustmentReason::Restock,
+                String::new(),
+            )
+            .unwrap_err();
+            assert_eq!(fields(errors), vec!["delta"]);
+        }
+    }
+
+    #[test]
+    fn ingredient_adjustment_accepts_fractional_deltas() {
+        let adjustment =
+            StockAdjustment::new(None, Some(1), 0.25, AdjustmentReason::Waste, String::new())
+                .unwrap();
+        assert_eq!(adjustment.delta(), 0.25);
+    }
+
+    #[test]
+    fn adjustment_reports_every_invalid_field() {
+        let errors =
+            StockAdjustment::new(Some(1), None, 0.0, AdjustmentReason::Order, "a".repeat(256))
+                .unwrap_err();
+        assert_eq!(fields(errors), vec!["delta", "reason", "note"]);
+    }
+}
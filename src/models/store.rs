@@ -0,0 +1,386 @@
+use actix_web::web;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use super::schedule::{ScheduleRange, DATE_FORMAT};
+
+pub const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+// Dias procurados pela próxima abertura
+const NEXT_OPENING_DAYS: i64 = 60;
+
+// Feriado ou outra data em que o restaurante não abre
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    date: String,
+    #[serde(default)]
+    description: String,
+}
+
+impl Holiday {
+    pub fn new(date: String, description: String) -> Result<Holiday, String> {
+        NaiveDate::parse_from_str(&date, DATE_FORMAT)
+            .map_err(|_| format!("Date '{}' must be in the format YYYY-MM-DD", date))?;
+        if description.chars().count() > 100 {
+            return Err(String::from(
+                "Holiday description cannot exceed 100 characters",
+            ));
+        }
+
+        Ok(Holiday {
+            date,
+            description,
+        })
+    }
+
+    // Get
+    pub fn date(&self) -> String {
+        self.date.clone()
+    }
+    pub fn description(&self) -> String {
+        self.description.clone()
+    }
+}
+
+// Horário de funcionamento do restaurante. Sem intervalos cadastrados o restaurante está sempre aberto.
+// Um intervalo que passa da meia-noite pertence ao dia em que começa, inclusive para os feriados.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreHours {
+    #[serde(default)]
+    opening_hours: Vec<ScheduleRange>,
+    #[serde(default)]
+    holidays: Vec<Holiday>,
+}
+
+impl StoreHours {
+    pub fn new(
+        opening_hours: Vec<ScheduleRange>,
+        holidays: Vec<Holiday>,
+    ) -> Result<StoreHours, String> {
+        for (i, holiday) in holidays.iter().enumerate() {
+            if holidays[..i].iter().any(|h| h.date == holiday.date) {
+                return Err(format!("Date {} is repeated in the holidays", holiday.date));
+            }
+        }
+
+        Ok(StoreHours {
+            opening_hours,
+            holidays,
+        })
+    }
+
+    pub fn from_json(json: web::Json<StoreHours>) -> Result<StoreHours, String> {
+        let opening_hours = json
+            .opening_hours
+            .iter()
+            .map(|r| ScheduleRange::new(r.weekday(), r.start(), r.end()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let holidays = json
+            .holidays
+            .iter()
+            .map(|h| Holiday::new(h.date(), h.description()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        StoreHours::new(opening_hours, holidays)
+    }
+
+    // Verifica se o restaurante está aberto no horário local informado.
+    pub fn is_open_at(&self, time: NaiveDateTime) -> bool {
+        if self.opening_hours.is_empty() {
+            return true;
+        }
+
+        let date = time.date();
+        let previous = date - Duration::days(1);
+
+        self.opening_hours.iter().any(|range| {
+            let starts_today = Self::weekday(date) == range.weekday()
+                && !self.is_holiday(date)
+                && time.time() >= range.start_time()
+                && (range.overnight() || time.time() < range.end_time());
+            let started_yesterday = range.overnight()
+                && Self::weekday(previous) == range.weekday()
+                && !self.is_holiday(previous)
+                && time.time() < range.end_time();
+
+            starts_today || started_yesterday
+        })
+    }
+
+    // Próximo horário em que o restaurante abre a partir do horário informado, inclusive.
+    // Retorna None se não houver abertura nos próximos dias.
+    pub fn next_opening(&self, from: NaiveDateTime) -> Option<NaiveDateTime> {
+        if self.is_open_at(from) {
+            return Some(from);
+        }
+
+        (0..=NEXT_OPENING_DAYS)
+            .map(|offset| from.date() + Duration::days(offset))
+            .filter(|date| !self.is_holiday(*date))
+            .flat_map(|date| {
+                self.opening_hours
+                    .iter()
+                    .filter(move |range| range.weekday() == Self::weekday(date))
+                    .map(move |range| date.and_time(range.start_time()))
+            })
+            .filter(|opening| *opening > from)
+            .min()
+    }
+
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        let date = date.format(DATE_FORMAT).to_string();
+        self.holidays.iter().any(|h| h.date == date)
+    }
+
+    fn weekday(date: NaiveDate) -> i16 {
+        date.weekday().num_days_from_sunday() as i16
+    }
+
+    // Get
+    pub fn opening_hours(&self) -> Vec<ScheduleRange> {
+        self.opening_hours.clone()
+    }
+    pub fn holidays(&self) -> Vec<Holiday> {
+        self.holidays.clone()
+    }
+}
+
+// Pausa manual dos pedidos. Sem 'resume_at' a pausa dura até ser encerrada por um funcionário.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorePause {
+    #[serde(default)]
+    reason: String,
+    #[serde(default)]
+    resume_at: Option<String>,
+}
+
+impl StorePause {
+    pub fn new(reason: String, resume_at: Option<String>) -> Result<StorePause, String> {
+        if reason.chars().count() > 255 {
+            return Err(String::from("Pause reason cannot exceed 255 characters"));
+        }
+        if let Some(resume_at) = &resume_at {
+            NaiveDateTime::parse_from_str(resume_at, DATETIME_FORMAT).map_err(|_| {
+                format!(
+                    "Date '{}' must be in the format YYYY-MM-DD HH:MM:SS",
+                    resume_at
+                )
+            })?;
+        }
+
+        Ok(StorePause {
+            reason,
+            resume_at,
+        })
+    }
+
+    pub fn from_json(json: web::Json<StorePause>) -> Result<StorePause, String> {
+        StorePause::new(json.reason(), json.resume_at())
+    }
+
+    // Get
+    pub fn reason(&self) -> String {
+        self.reason.clone()
+    }
+    pub fn resume_at(&self) -> Option<String> {
+        self.resume_at.clone()
+    }
+}
+
+// Situação do restaurante informada aos aplicativos
+#[derive(Debug, Clone, Serialize)]
+pub struct StoreStatus {
+    open: bool,
+    paused: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pause_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    next_opening: Option<String>,
+}
+
+impl StoreStatus {
+    // Calcula a situação no horário local 'now', considerando a pausa em vigor, se houver.
+    pub fn new(hours: &StoreHours, now: NaiveDateTime, pause: Option<StorePause>) -> StoreStatus {
+        let format = |time: NaiveDateTime| time.format(DATETIME_FORMAT).to_string();
+
+        match pause {
+            Some(pause) => {
+                let resume_at = pause
+                    .resume_at()
+                    .and_then(|t| NaiveDateTime::parse_from_str(&t, DATETIME_FORMAT).ok());
+                StoreStatus {
+                    open: false,
+                    paused: true,
+                    pause_reason: Some(pause.reason()).filter(|r| !r.is_empty()),
+                    next_opening: resume_at.and_then(|t| hours.next_opening(t)).map(format),
+                }
+            }
+            None if hours.is_open_at(now) => StoreStatus {
+                open: true,
+                paused: false,
+                pause_reason: None,
+                next_opening: None,
+            },
+            None => StoreStatus {
+                open: false,
+                paused: false,
+                pause_reason: None,
+                next_opening: hours.next_opening(now).map(format),
+            },
+        }
+    }
+
+    // Get
+    pub fn open(&self) -> bool {
+        self.open
+    }
+
+    // Mensagem de recusa de pedidos enquanto o restaurante está fechado
+    pub fn closed_message(&self) -> String {
+        let mut message = match (&self.pause_reason, self.paused) {
+            (Some(reason), _) => format!("Pedidos pausados: {}", reason),
+            (None, true) => String::from("Pedidos pausados"),
+            (None, false) => String::from("Restaurante fechado"),
+        };
+        if let Some(next_opening) = &self.next_opening {
+            message.push_str(&format!(". Abre em {}", next_opening));
+        }
+        message
+    }
+}
+
+// Regras dos pedidos agendados. A cozinha atende até 'slot_capacity' pedidos em cada intervalo de
+// 'slot_minutes' e recebe o pedido 'lead_minutes' antes do horário pedido.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderScheduleSettings {
+    slot_minutes: i32,
+    slot_capacity: i32,
+    lead_minutes: i32,
+    // Antecedência mínima do agendamento
+    min_advance_minutes: i32,
+    max_days_ahead: i32,
+}
+
+impl OrderScheduleSettings {
+    pub fn new(
+        slot_minutes: i32,
+        slot_capacity: i32,
+        lead_minutes: i32,
+        min_advance_minutes: i32,
+        max_days_ahead: i32,
+    ) -> Result<OrderScheduleSettings, String> {
+        if slot_minutes <= 0 || 1440 % slot_minutes != 0 {
+            return Err(String::from(
+                "Slot minutes must be greater than 0 and divide a day evenly",
+            ));
+        }
+        if slot_capacity <= 0 {
+            return Err(String::from("Slot capacity must be greater than 0"));
+        }
+        if lead_minutes < 0 || min_advance_minutes < 0 {
+            return Err(String::from(
+                "Lead and advance minutes cannot be negative",
+            ));
+        }
+        if max_days_ahead <= 0 {
+            return Err(String::from("Max days ahead must be greater than 0"));
+        }
+
+        Ok(OrderScheduleSettings {
+            slot_minutes,
+            slot_capacity,
+            lead_minutes,
+            min_advance_minutes,
+            max_days_ahead,
+        })
+    }
+
+    pub fn from_json(
+        json: web::Json<OrderScheduleSettings>,
+    ) -> Result<OrderScheduleSettings, String> {
+        OrderScheduleSettings::new(
+            json.slot_minutes(),
+            json.slot_capacity(),
+            json.lead_minutes(),
+            json.min_advance_minutes(),
+            json.max_days_ahead(),
+        )
+    }
+
+    // Início do intervalo de capacidade que contém o horário informado
+    pub fn slot_start(&self, time: NaiveDateTime) -> NaiveDateTime {
+        let minutes = time.time().num_seconds_from_midnight() as i64 / 60;
+        let start = minutes - minutes % self.slot_minutes as i64;
+        time.date().and_time(NaiveTime::MIN) + Duration::minutes(start)
+    }
+
+    // Intervalos de um dia em que o restaurante está aberto, a partir do mínimo de antecedência
+    pub fn slots(&self, hours: &StoreHours, date: NaiveDate, now: NaiveDateTime) -> Vec<NaiveDateTime> {
+        let earliest = now + Duration::minutes(self.min_advance_minutes as i64);
+
+        (0..1440 / self.slot_minutes)
+            .map(|i| date.and_time(NaiveTime::MIN) + Duration::minutes((i * self.slot_minutes) as i64))
+            .filter(|slot| *slot >= earliest && hours.is_open_at(*slot))
+            .collect()
+    }
+
+    // Confere se o horário pedido respeita a antecedência e o horário de funcionamento.
+    pub fn validate_time(
+        &self,
+        hours: &StoreHours,
+        requested: NaiveDateTime,
+        now: NaiveDateTime,
+    ) -> Result<(), String> {
+        if requested < now + Duration::minutes(self.min_advance_minutes as i64) {
+            return Err(format!(
+                "Pedidos agendados precisam de pelo menos {} minutos de antecedência",
+                self.min_advance_minutes
+            ));
+        }
+        if requested > now + Duration::days(self.max_days_ahead as i64) {
+            return Err(format!(
+                "Pedidos podem ser agendados com até {} dias de antecedência",
+                self.max_days_ahead
+            ));
+        }
+        if !hours.is_open_at(requested) {
+            return Err(String::from(
+                "O restaurante está fechado no horário escolhido",
+            ));
+        }
+        Ok(())
+    }
+
+    // Get
+    pub fn slot_minutes(&self) -> i32 {
+        self.slot_minutes
+    }
+    pub fn slot_capacity(&self) -> i32 {
+        self.slot_capacity
+    }
+    pub fn lead_minutes(&self) -> i32 {
+        self.lead_minutes
+    }
+    pub fn min_advance_minutes(&self) -> i32 {
+        self.min_advance_minutes
+    }
+    pub fn max_days_ahead(&self) -> i32 {
+        self.max_days_ahead
+    }
+}
+
+// Intervalo disponível para agendamento com as vagas restantes
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduleSlot {
+    start: String,
+    remaining: i32,
+}
+
+impl ScheduleSlot {
+    pub fn new(start: NaiveDateTime, remaining: i32) -> ScheduleSlot {
+        ScheduleSlot {
+            start: start.format(DATETIME_FORMAT).to_string(),
+            remaining,
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use actix_web::{http::header::ACCEPT_LANGUAGE, web, HttpRequest};
+use serde::{Deserialize, Serialize};
+
+use super::validation::{FieldError, ValidationErrors};
+
+// Idioma do conteúdo cadastrado no próprio produto ou categoria. As traduções são para os demais idiomas.
+pub const DEFAULT_LOCALE: &str = "pt-br";
+
+// Maior número de idiomas considerados do cabeçalho Accept-Language
+const MAX_ACCEPTED: usize = 10;
+
+// Nome e descrição de um produto ou categoria em outro idioma.
+// Sem descrição traduzida é exibida a descrição original.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Translation {
+    locale: String,
+    name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+impl Translation {
+    pub fn new(
+        locale: String,
+        name: String,
+        description: Option<String>,
+    ) -> Result<Translation, ValidationErrors> {
+        let mut new_translation = Translation {
+            locale: String::new(),
+            name: String::new(),
+            description: None,
+        };
+
+        let mut errors = ValidationErrors::new();
+        errors.check("locale", new_translation.set_locale(locale));
+        errors.check("name", new_translation.set_name(name));
+        errors.check("description", new_translation.set_description(description));
+
+        errors.result(new_translation)
+    }
+
+    // Valida as traduções recebidas; cada idioma aparece uma vez
+    pub fn from_json(
+        json: web::Json<Vec<Translation>>,
+    ) -> Result<Vec<Translation>, ValidationErrors> {
+        let mut translations: Vec<Translation> = Vec::new();
+        let mut errors = ValidationErrors::new();
+
+        for (i, translation) in json.into_inner().into_iter().enumerate() {
+            let translation = match Translation::new(
+                translation.locale,
+                translation.name,
+                translation.description,
+            ) {
+                Ok(translation) => translation,
+                Err(e) => {
+                    errors.nested(&format!("[{}]", i), e);
+                    continue;
+                }
+            };
+            if translations.iter().any(|t| t.locale == translation.locale) {
+                errors.check(
+                    &format!("[{}].locale", i),
+                    Err(FieldError::duplicate(format!(
+                        "Language '{}' is repeated in the translations",
+                        translation.locale
+                    ))),
+                );
+            }
+            translations.push(translation);
+        }
+
+        errors.result(translations)
+    }
+
+    // Get
+    pub fn locale(&self) -> String {
+        self.locale.clone()
+    }
+    pub fn name(&self) -> String {
+        self.name.clone()
+    }
+    pub fn description(&self) -> Option<String> {
+        self.description.clone()
+    }
+
+    // Set
+    pub fn set_locale(&mut self, locale: String) -> Result<(), FieldError> {
+        let locale = normalize_locale(&locale).map_err(|e| FieldError::new("invalid_format", e))?;
+        if primary_language(&locale) == primary_language(DEFAULT_LOCALE) {
+            return Err(FieldError::new(
+                "invalid",
+                format!(
+                    "Content in '{}' is edited in the item itself, not as a translation",
+                    locale
+                ),
+            ));
+        }
+        self.locale = locale;
+        Ok(())
+    }
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
+        if name.trim().is_empty() {
+            return Err(FieldError::required("Translated name cannot be empty"));
+        }
+        self.name = name;
+        Ok(())
+    }
+    pub fn set_description(&mut self, description: Option<String>) -> Result<(), FieldError> {
+        if description.as_ref().is_some_and(|d| d.trim().is_empty()) {
+            return Err(FieldError::required(
+                "Translated description cannot be empty",
+            ));
+        }
+        self.description = description;
+        Ok(())
+    }
+}
+
+// Parâmetro '?lang=' das leituras do cardápio, que tem prioridade sobre o cabeçalho Accept-Language
+#[derive(Debug, Deserialize)]
+pub struct LanguageQuery {
+    lang: Option<String>,
+}
+
+impl LanguageQuery {
+    pub fn lang(&self) -> Option<String> {
+        self.lang.clone()
+    }
+}
+
+// Idiomas pedidos na requisição, do preferido ao menos preferido, usados como cadeia de fallback
+pub fn requested_locales(req: &HttpRequest, lang: Option<String>) -> Result<Vec<String>, String> {
+    let accept_language = req
+        .headers()
+        .get(ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok());
+
+    locale_chain(lang.as_deref(), accept_language)
+}
+
+// Monta a cadeia de idiomas: cada idioma pedido seguido da sua língua sem a região ("en-us" => "en-us", "en").
+// A cadeia termina no português, cujo conteúdo é o do próprio item; uma cadeia vazia usa só o conteúdo original.
+pub fn locale_chain(
+    lang: Option<&str>,
+    accept_language: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let requested = match lang {
+        Some(lang) => vec![normalize_locale(lang)?],
+        None => parse_accept_language(accept_language.unwrap_or_default()),
+    };
+
+    let mut chain: Vec<String> = Vec::new();
+    for locale in requested {
+        for candidate in [locale.clone(), primary_language(&locale).to_string()] {
+            if primary_language(&candidate) == primary_language(DEFAULT_LOCALE) {
+                return Ok(chain);
+            }
+            if !chain.contains(&candidate) {
+                chain.push(candidate);
+            }
+        }
+    }
+
+    Ok(chain)
+}
+
+// Idiomas do cabeçalho Accept-Language ordenados pelo peso 'q'. Valores inválidos e '*' são ignorados.
+fn parse_accept_language(header: &str) -> Vec<String> {
+    let mut accepted: Vec<(String, f32)> = header
+        .split(',')
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let locale = normalize_locale(parts.next()?).ok()?;
+            let quality = match parts.find_map(|param| param.trim().strip_prefix("q=")) {
+                Some(quality) => quality.trim().parse::<f32>().ok()?,
+                None => 1.0,
+            };
+            (quality > 0.0).then_some((locale, quality))
+        })
+        .take(MAX_ACCEPTED)
+        .collect();
+
+    // A ordenação é estável, então idiomas com o mesmo peso mantêm a ordem do cabeçalho
+    accepted.sort_by(|a, b| b.1.total_cmp(&a.1));
+    accepted.into_iter().map(|(locale, _)| locale).collect()
+}
+
+// Identificador de idioma em minúsculas, como "en" ou "pt-br"
+pub fn normalize_locale(locale: &str) -> Result<String, String> {
+    let normalized = locale.trim().to_lowercase().replace('_', "-");
+    let mut parts = normalized.split('-');
+
+    let valid = normalized.len() <= 35
+        && parts.next().is_some_and(|language| {
+            (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase())
+        })
+        && parts.all(|part| {
+            (1..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+
+    match valid {
+        true => Ok(normalized),
+        false => Err(format!("Invalid language '{}'", locale.trim())),
+    }
+}
+
+fn primary_language(locale: &str) -> &str {
+    locale.split('-').next().unwrap_or(locale)
+}
@@ -1,17 +1,24 @@
 use actix_web::web;
 use serde::{Deserialize, Serialize};
 
+use super::payment_method::PaymentMethod;
+use super::validation::{FieldError, ValidationErrors};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     #[serde(skip_serializing_if = "Option::is_none")]
     id: Option<i32>,
     name: String,
     username: String,
+    // Recebida no cadastro e na atualização, mas nunca enviada nas respostas
+    #[serde(skip_serializing)]
     password: String,
     email: String,
     phone: String,
     address: String,
-    payment_methods: Vec<String>,
+    // Apenas leitura: os métodos de pagamento são cadastrados pelas rotas '/payment_method'
+    #[serde(default, skip_deserializing)]
+    payment_methods: Vec<PaymentMethod>,
     #[serde(skip_serializing_if = "Option::is_none")]
     acess: Option<i32>,
 }
@@ -25,9 +32,9 @@ impl User {
         email: String,
         phone: String,
         address: String,
-        payment_methods: Vec<String>,
+        payment_methods: Vec<PaymentMethod>,
         acess: Option<i32>
-    ) -> Result<User, String> {
+    ) -> Result<User, ValidationErrors> {
         let mut new_user = User {
             id: id,
             name: String::new(),
@@ -40,20 +47,21 @@ impl User {
             acess: Some(0),
         };
 
-        new_user.set_name(name)?;
-        new_user.set_username(username)?;
-        new_user.set_password(password)?;
-        new_user.set_email(email)?;
-        new_user.set_phone(phone)?;
-        new_user.set_address(address)?;
-        new_user.set_payment_methods(payment_methods)?;
+        let mut errors = ValidationErrors::new();
+        errors.check("name", new_user.set_name(name));
+        errors.check("username", new_user.set_username(username));
+        errors.check("password", new_user.set_password(password));
+        errors.check("email", new_user.set_email(email));
+        errors.check("phone", new_user.set_phone(phone));
+        errors.check("address", new_user.set_address(address));
+        errors.check("payment_methods", new_user.set_payment_methods(payment_methods));
         new_user.set_acess(acess);
 
-        Ok(new_user)
+        errors.result(new_user)
     }
 
-    pub fn from_json(json: web::Json<User>) -> Result<User, String> {
-        Ok(User::new(
+    pub fn from_json(json: web::Json<User>) -> Result<User, ValidationErrors> {
+        User::new(
             json.id(),
             json.name(),
             json.username(),
@@ -63,7 +71,7 @@ impl User {
             json.address(),
             json.payment_methods(),
             json.acess(),
-        )?)
+        )
     }
 
     // Get
@@ -88,7 +96,7 @@ impl User {
     pub fn address(&self) -> String {
         self.address.clone()
     }
-    pub fn payment_methods(&self) -> Vec<String> {
+    pub fn payment_methods(&self) -> Vec<PaymentMethod> {
         self.payment_methods.clone()
     }
     pub fn acess(&self) -> Option<i32> {
@@ -96,41 +104,39 @@ impl User {
     }
 
     // Set
-    pub fn set_name(&mut self, name: String) -> Result<(), String> {
+    pub fn set_name(&mut self, name: String) -> Result<(), FieldError> {
         Self::validate_name(&name)?;
         self.name = name;
         Ok(())
     }
-    pub fn set_username(&mut self, username: String) -> Result<(), String> {
+    pub fn set_username(&mut self, username: String) -> Result<(), FieldError> {
         Self::validate_username(&username)?;
         self.username = username;
         Ok(())
     }
-    pub fn set_password(&mut self, password: String) -> Result<(), String> {
+    pub fn set_password(&mut self, password: String) -> Result<(), FieldError> {
         Self::validate_password(&password)?;
         self.password = password;
         Ok(())
     }
-    pub fn set_email(&mut self, email: String) -> Result<(), String> {
+    pub fn set_email(&mut self, email: String) -> Result<(), FieldError> {
         Self::validate_email(&email)?;
         self.email = email;
         Ok(())
     }
-    pub fn set_phone(&mut self, phone: String) -> Result<(), String> {
+    pub fn set_phone(&mut self, phone: String) -> Result<(), FieldError> {
         Self::validate_phone(&phone)?;
         self.phone = phone;
         Ok(())
     }
-    pub fn set_address(&mut self, address: String) -> Result<(), String> {
+    pub fn set_address(&mut self, address: String) -> Result<(), FieldError> {
         Self::validate_address(&address)?;
         self.address = address;
         Ok(())
     }
-    pub fn set_payment_methods(&mut self, payment_methods: Vec<String>) -> Result<(), String> {
+    pub fn set_payment_methods(&mut self, payment_methods: Vec<PaymentMethod>) -> Result<(), FieldError> {
         Self::validate_payment_methods(&payment_methods)?;
-        let mut aux = payment_methods.clone();
-        aux.dedup();
-        self.payment_methods = aux;
+        self.payment_methods = payment_methods;
         Ok(())
     }
     pub fn set_acess(&mut self, acess: Option<i32>) {
@@ -141,55 +147,68 @@ impl User {
     }
 
     // Validate
-    fn validate_name(name: &str) -> Result<(), String> {
+    fn validate_name(name: &str) -> Result<(), FieldError> {
         if name.is_empty() {
-            return Err(String::from("O campo 'nome' não pode estar vazio"));
+            return Err(FieldError::required("O campo 'nome' não pode estar vazio"));
         }
         Ok(())
     }
-    fn validate_username(username: &str) -> Result<(), String> {
+    fn validate_username(username: &str) -> Result<(), FieldError> {
         if username.len() > 50 {
-            return Err(String::from(
+            return Err(FieldError::too_long(
                 "O campo 'username' deve ter no máximo 50 caracteres",
+                50,
             ));
         }
         Ok(())
     }
-    fn validate_password(password: &str) -> Result<(), String> {
+    fn validate_password(password: &str) -> Result<(), FieldError> {
         if password.len() < 4 {
-            return Err(String::from(
+            return Err(FieldError::too_short(
                 "O campo 'password' deve ter pelo menos 8 caracteres",
+                4,
             ));
         }
         Ok(())
     }
-    fn validate_email(email: &str) -> Result<(), String> {
+    fn validate_email(email: &str) -> Result<(), FieldError> {
         if !email.contains('@') || !email.contains('.') {
-            return Err(String::from(
+            return Err(FieldError::invalid_format(
                 "O campo 'email' deve ser um endereço de email válido",
             ));
         }
         Ok(())
     }
-    fn validate_phone(phone: &str) -> Result<(), String> {
+    fn validate_phone(phone: &str) -> Result<(), FieldError> {
         if phone.chars().any(|c| !c.is_numeric()) {
-            return Err(String::from("O campo 'phone' deve conter apenas números"));
+            return Err(FieldError::invalid_format("O campo 'phone' deve conter apenas números"));
         }
         Ok(())
     }
-    fn validate_address(address: &str) -> Result<(), String> {
+    fn validate_address(address: &str) -> Result<(), FieldError> {
         if address.is_empty() {
-            return Err(String::from("O campo 'address' não pode estar vazio"));
+            return Err(FieldError::required("O campo 'address' não pode estar vazio"));
         }
         Ok(())
     }
-    fn validate_payment_methods(payment_methods: &[String]) -> Result<(), String> {
-        let mut aux = payment_methods.to_vec();
-        aux.dedup();
-        if aux.len() != payment_methods.len() {
-            return Err(String::from(
-                "A lista de 'payment_methods' contém elementos duplicados",
-            ));
+    fn validate_payment_methods(payment_methods: &[PaymentMethod]) -> Result<(), FieldError> {
+        for (i, method) in payment_methods.iter().enumerate() {
+            if payment_methods[..i]
+                .iter()
+                .any(|m| m.kind() == method.kind() && m.value() == method.value())
+            {
+                return Err(FieldError::duplicate(String::from(
+                    "A lista de 'payment_methods' contém elementos duplicados",
+                ))
+                .at(&format!("[{}]", i)));
+            }
+        }
+        if payment_methods.iter().filter(|m| m.is_default()).count() > 1 {
+            return Err(FieldError::new(
+                "max_default",
+                String::from("A lista de 'payment_methods' deve ter no máximo um método padrão"),
+            )
+            .with_param("max", 1));
         }
         Ok(())
     }
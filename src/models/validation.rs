@@ -0,0 +1,209 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+
+// Erro de validação de um campo. 'field' é o caminho do campo nos dados enviados, como
+// "items[2].quantity", 'code' identifica a regra violada para os aplicativos e 'params' traz os
+// limites da regra (ex.: {"max": 50}), para que os formulários montem as próprias mensagens.
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    field: String,
+    code: &'static str,
+    message: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    params: BTreeMap<&'static str, Value>,
+}
+
+impl FieldError {
+    pub fn new(code: &'static str, message: String) -> FieldError {
+        FieldError {
+            field: String::new(),
+            code,
+            message,
+            params: BTreeMap::new(),
+        }
+    }
+
+    // Campo obrigatório vazio
+    pub fn required(message: &str) -> FieldError {
+        FieldError::new("required", message.to_string())
+    }
+    // Texto com mais caracteres que o permitido
+    pub fn too_long(message: &str, max: usize) -> FieldError {
+        FieldError::new("too_long", message.to_string()).with_param("max", max)
+    }
+    // Texto com menos caracteres que o permitido
+    pub fn too_short(message: &str, min: usize) -> FieldError {
+        FieldError::new("too_short", message.to_string()).with_param("min", min)
+    }
+    // Número abaixo do mínimo
+    pub fn min(message: &str, min: impl Into<Value>) -> FieldError {
+        FieldError::new("min", message.to_string()).with_param("min", min)
+    }
+    // Valor fora do formato esperado
+    pub fn invalid_format(message: &str) -> FieldError {
+        FieldError::new("invalid_format", message.to_string())
+    }
+    // Item repetido em uma lista
+    pub fn duplicate(message: String) -> FieldError {
+        FieldError::new("duplicate", message)
+    }
+
+    pub fn with_param(mut self, name: &'static str, value: impl Into<Value>) -> FieldError {
+        self.params.insert(name, value.into());
+        self
+    }
+
+    // Coloca o erro dentro de 'path': "quantity" em "items[2]" fica "items[2].quantity".
+    // Com 'path' vazio o erro mantém o próprio campo.
+    pub fn at(mut self, path: &str) -> FieldError {
+        self.field = match (self.field.is_empty(), self.field.starts_with('[')) {
+            _ if path.is_empty() => self.field,
+            (true, _) => path.to_string(),
+            (false, true) => format!("{}{}", path, self.field),
+            (false, false) => format!("{}.{}", path, self.field),
+        };
+        self
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.field.is_empty() {
+            true => write!(f, "{}", self.message),
+            false => write!(f, "{}: {}", self.field, self.message),
+        }
+    }
+}
+
+// Todos os erros encontrados na validação de um modelo, em vez de apenas o primeiro
+#[derive(Debug, Clone, Default)]
+pub struct ValidationErrors {
+    errors: Vec<FieldError>,
+}
+
+impl ValidationErrors {
+    pub fn new() -> ValidationErrors {
+        ValidationErrors { errors: Vec::new() }
+    }
+
+    // Registra o erro de 'field', se houver
+    pub fn check<E: Into<FieldError>>(&mut self, field: &str, result: Result<(), E>) {
+        if let Err(error) = result {
+            self.errors.push(error.into().at(field));
+        }
+    }
+
+    // Registra os erros de um modelo contido em 'path', como um item de uma lista ("items[2]")
+    pub fn nested(&mut self, path: &str, errors: ValidationErrors) {
+        self.errors
+            .extend(errors.errors.into_iter().map(|error| error.at(path)));
+    }
+
+    // Retorna 'value' se não houve erros
+    pub fn result<T>(self, value: T) -> Result<T, ValidationErrors> {
+        match self.errors.is_empty() {
+            true => Ok(value),
+            false => Err(self),
+        }
+    }
+
+    // Get
+    pub fn errors(&self) -> Vec<FieldError> {
+        self.errors.clone()
+    }
+}
+
+impl fmt::Display for ValidationErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<String> = self.errors.iter().map(|error| error.to_string()).collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl From<ValidationErrors> for String {
+    fn from(errors: ValidationErrors) -> String {
+        errors.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(error: &FieldError) -> &str {
+        &error.field
+    }
+
+    #[test]
+    fn at_joins_paths_with_dots_and_indexes() {
+        let error = FieldError::required("Quantity cannot be empty");
+        assert_eq!(field(&error.clone().at("quantity")), "quantity");
+        assert_eq!(
+            field(&error.clone().at("quantity").at("items[2]")),
+            "items[2].quantity"
+        );
+        assert_eq!(field(&error.clone().at("[0]").at("options")), "options[0]");
+        assert_eq!(
+            field(&error.at("quantity").at("[1]").at("items")),
+            "items[1].quantity"
+        );
+    }
+
+    #[test]
+    fn at_keeps_the_field_for_an_empty_path() {
+        let error = FieldError::invalid_format("Invalid code").at("code");
+        assert_eq!(field(&error.clone().at("")), "code");
+        assert_eq!(field(&FieldError::invalid_format("Invalid").at("")), "");
+    }
+
+    #[test]
+    fn nested_errors_are_collected_under_the_item_path() {
+        let mut item = ValidationErrors::new();
+        item.check(
+            "quantity",
+            Err(FieldError::min("Quantity must be positive", 1)),
+        );
+        item.check("name", Ok::<(), FieldError>(()));
+        item.check(
+            "",
+            Err(FieldError::too_long("Note is too long", 50).at("note")),
+        );
+
+        let mut errors = ValidationErrors::new();
+        errors.nested("items[2]", item);
+        let fields: Vec<String> = errors
+            .errors()
+            .iter()
+            .map(|e| field(e).to_string())
+            .collect();
+        assert_eq!(fields, vec!["items[2].quantity", "items[2].note"]);
+        assert_eq!(
+            errors.to_string(),
+            "items[2].quantity: Quantity must be positive; items[2].note: Note is too long"
+        );
+        assert!(errors.result(()).is_err());
+        assert!(ValidationErrors::new().result(()).is_ok());
+    }
+
+    #[test]
+    fn params_are_serialized_with_the_error() {
+        let error = FieldError::too_long("Name is too long", 50).at("name");
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "field": "name",
+                "code": "too_long",
+                "message": "Name is too long",
+                "params": {"max": 50}
+            })
+        );
+        let error = FieldError::new("invalid", String::from("Invalid order"));
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({"code": "invalid", "message": "Invalid order"})
+        );
+    }
+}
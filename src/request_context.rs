@@ -0,0 +1,103 @@
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    web, Error,
+};
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use std::future::Future;
+
+use crate::models::translation::{requested_locales, LanguageQuery};
+
+// Cabeçalho com o identificador da requisição, enviado pelo cliente ou gerado pelo servidor
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static CONTEXT: RequestContext;
+}
+
+// Dados da requisição usados nas respostas de erro: o identificador, que também aparece no log,
+// e o idioma das mensagens
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    request_id: String,
+    english: bool,
+}
+
+impl RequestContext {
+    fn from_request(req: &ServiceRequest) -> RequestContext {
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| {
+                (1..=64).contains(&id.len())
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            })
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| {
+                thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(16)
+                    .map(char::from)
+                    .collect()
+            });
+
+        let lang = web::Query::<LanguageQuery>::from_query(req.query_string())
+            .ok()
+            .and_then(|query| query.lang());
+        let english = requested_locales(req.request(), lang)
+            .ok()
+            .and_then(|locales| locales.first().cloned())
+            .is_some_and(|locale| locale.starts_with("en"));
+
+        RequestContext {
+            request_id,
+            english,
+        }
+    }
+
+    // Contexto da requisição em andamento. Fora de uma requisição retorna um contexto vazio.
+    pub fn current() -> RequestContext {
+        CONTEXT
+            .try_with(|context| context.clone())
+            .unwrap_or(RequestContext {
+                request_id: String::new(),
+                english: false,
+            })
+    }
+
+    // Get
+    pub fn request_id(&self) -> String {
+        self.request_id.clone()
+    }
+    pub fn english(&self) -> bool {
+        self.english
+    }
+}
+
+// Middleware que guarda o contexto durante o atendimento da requisição e devolve o identificador no cabeçalho
+pub fn middleware<S>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse, Error = Error>,
+{
+    let context = RequestContext::from_request(&req);
+    let request_id = context.request_id();
+
+    let response = CONTEXT.sync_scope(context.clone(), || srv.call(req));
+
+    CONTEXT.scope(context, async move {
+        let mut response = response.await?;
+        if let Ok(value) = HeaderValue::from_str(&request_id) {
+            response
+                .headers_mut()
+                .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+        }
+        Ok(response)
+    })
+}
@@ -0,0 +1,98 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::address::DbAddress;
+use crate::models::{api_error::ApiError, address::Address, tokens::find_user_id_by_token};
+
+// Cadastra um novo endereço de entrega para o usuário identificado pelo user_id e user_token.
+//
+// Retorna o código 201 (Created) se o endereço foi criado com sucesso.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar o endereço.
+#[post("/api/v1/address/create/{user_id}/{user_token}")]
+async fn create(
+    path: web::Path<(i32, String)>,
+    address: web::Json<Address>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match Address::from_json(address) {
+            Ok(new_address) => match DbAddress::create(user_id, new_address).await {
+                Ok(_) => HttpResponse::Created().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os endereços de entrega do usuário, com o endereço padrão primeiro.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os endereços se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os endereços.
+#[get("/api/v1/address/read/{user_id}/{user_token}")]
+async fn read(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbAddress::read(user_id).await {
+                Ok(addresses) => HttpResponse::Ok().json(addresses),
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza um endereço do usuário. Marcar como padrão desmarca os demais.
+//
+// Retorna o código 200 (OK) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 404 (Not Found) se o endereço não pertence ao usuário ou não existe.
+#[put("/api/v1/address/update/{id}/{user_id}/{user_token}")]
+async fn update(
+    path: web::Path<(i32, i32, String)>,
+    address: web::Json<Address>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match Address::from_json(address) {
+            Ok(new_address) => match DbAddress::update(id, user_id, new_address).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Remove um endereço do usuário. Pedidos antigos mantêm a cópia do endereço usado.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 404 (Not Found) se o endereço não for encontrado.
+#[delete("/api/v1/address/delete/{id}/{user_id}/{user_token}")]
+async fn delete(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbAddress::delete(id, user_id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,98 @@
+use actix_web::{get, post, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{archive::DbArchive, product::DbProduct, user::DbUser};
+use crate::models::{api_error::ApiError, archive::retention_days, tokens::find_user_id_by_token};
+
+// Lista os produtos excluídos com a data em que serão removidos definitivamente.
+// Disponível apenas para administradores.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os produtos se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é administrador.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os produtos.
+#[get("/api/v1/product/deleted/{user_id}/{user_token}")]
+async fn read_products(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_admin(user_id).await {
+            Ok(true) => match DbArchive::read_products(retention_days()).await {
+                Ok(products) => HttpResponse::Ok().json(products),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Restaura um produto excluído, que volta ao cardápio. Disponível apenas para administradores.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é administrador.
+// Retorna o código 404 (Not Found) se o produto não existir, não estiver excluído ou já tiver sido removido.
+#[post("/api/v1/product/restore/{id}/{user_id}/{user_token}")]
+async fn restore_product(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_admin(user_id).await {
+            Ok(true) => match DbProduct::restore(id, user_id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os usuários excluídos que ainda podem ser restaurados, com a data em que os dados serão apagados.
+// Disponível apenas para administradores.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os usuários se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é administrador.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os usuários.
+#[get("/api/v1/user/deleted/{user_id}/{user_token}")]
+async fn read_users(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_admin(user_id).await {
+            Ok(true) => match DbArchive::read_users(retention_days()).await {
+                Ok(users) => HttpResponse::Ok().json(users),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Restaura um usuário excluído, que volta a conseguir entrar. Disponível apenas para administradores.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é administrador.
+// Retorna o código 404 (Not Found) se o usuário não existir, não estiver excluído ou já tiver os dados apagados.
+#[post("/api/v1/user/restore/{id}/{user_id}/{user_token}")]
+async fn restore_user(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_admin(user_id).await {
+            Ok(true) => match DbUser::restore(id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -1,7 +1,8 @@
-use actix_web::{delete, get, post, web, HttpResponse, Responder};
+use actix_web::{delete, get, post, web, HttpResponse, Responder, ResponseError};
 
-use crate::data::user::DbUser;
+use crate::data::{error::DataError, user::DbUser};
 use crate::models::{
+    api_error::ApiError,
     login_response::LoginResponse,
     credentials::Credentials,
     tokens::{find_user_id_by_token, generate_token, remove_token},
@@ -12,6 +13,7 @@ use crate::models::{
 // Retorna o codigo 200 (OK) com um token de autenticação se as credenciais estiverem corretas.
 // Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
 // Retorna o código 401 (Unauthorized) se as credenciais estiverem incorretas ou não existirem.
+// Retorna o código 500 (Internal Server Error) se houve problema ao consultar o banco de dados.
 #[post("/api/v1/authenticate/login")]
 async fn login(credentials: web::Json<Credentials>) -> impl Responder {
     match Credentials::from_json(credentials) {
@@ -19,12 +21,13 @@ async fn login(credentials: web::Json<Credentials>) -> impl Responder {
             match DbUser::autenticate(credentials.username(), credentials.password()).await {
                 Ok(id) => match generate_token(id) {
                     Ok(token) => HttpResponse::Ok().json(LoginResponse::new(id, token)),
-                    Err(e) => HttpResponse::BadRequest().body(e),
+                    Err(e) => ApiError::validation(e).error_response(),
                 },
-                Err(_) => HttpResponse::Unauthorized().finish(),
+                Err(e @ DataError::Db(_)) => ApiError::from(e).error_response(),
+                Err(_) => ApiError::Unauthorized.error_response(),
             }
         }
-        Err(e) => HttpResponse::BadRequest().body(e),
+        Err(e) => ApiError::from(e).error_response(),
     }
 }
 
@@ -41,8 +44,8 @@ pub async fn logout(path: web::Path<(String, String)>) -> impl Responder {
             remove_token(token);
             HttpResponse::Ok().finish()
         }
-        Some(_) => HttpResponse::Unauthorized().finish(),
-        None => HttpResponse::Unauthorized().finish(),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
 }
 
@@ -58,7 +61,7 @@ async fn check(path: web::Path<(String, String)>) -> impl Responder {
         Some(user_id) if user_id.to_string() == *id => {
             HttpResponse::Ok().finish()
         }
-        Some(_) => HttpResponse::Unauthorized().finish(),
-        None => HttpResponse::Unauthorized().finish(),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
 }
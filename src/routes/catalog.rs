@@ -0,0 +1,89 @@
+use actix_web::{get, http::header, post, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{catalog::DbCatalog, error::DataError, user::DbUser};
+use crate::models::{
+    api_error::ApiError,
+    catalog::{Catalog, CatalogQuery},
+    tokens::find_user_id_by_token,
+};
+
+// Exporta o cardápio completo (categorias, produtos, opções e disponibilidade) em CSV ou JSON
+// ('?format=csv', padrão 'json'). Os combos não são exportados. Disponível apenas para administradores.
+//
+// Retorna o código 200 (Ok) e o arquivo do catálogo se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o formato é inválido.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é administrador.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o cardápio.
+#[get("/api/v1/catalog/export/{user_id}/{user_token}")]
+async fn export(path: web::Path<(i32, String)>, query: web::Query<CatalogQuery>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    let format = match query.format() {
+        Ok(format) => format,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_admin(user_id).await {
+            Ok(true) => match DbCatalog::export()
+                .await
+                .and_then(|catalog| catalog.write(format).map_err(DataError::Db))
+            {
+                Ok(file) => HttpResponse::Ok()
+                    .content_type(format.content_type())
+                    .insert_header((
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"catalog.{}\"", format.extension()),
+                    ))
+                    .body(file),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Importa o cardápio enviado no corpo da requisição em CSV ou JSON ('?format=csv', padrão 'json'),
+// no mesmo formato da exportação. Categorias são encontradas pelo nome e produtos pelo SKU.
+// Por padrão apenas simula a importação; com '?dry_run=false' grava tudo em uma única transação,
+// ou nada se alguma linha tiver erro. Disponível apenas para administradores.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as alterações se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com as alterações e os erros de cada linha se o catálogo é inválido.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é administrador.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao gravar o cardápio.
+#[post("/api/v1/catalog/import/{user_id}/{user_token}")]
+async fn import(
+    path: web::Path<(i32, String)>,
+    query: web::Query<CatalogQuery>,
+    body: web::Bytes,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    let (catalog, errors) = match query
+        .format()
+        .and_then(|format| Catalog::parse(format, &body))
+    {
+        Ok(parsed) => parsed,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_admin(user_id).await {
+            Ok(true) => {
+                match DbCatalog::import(&catalog, errors, query.dry_run(), Some(user_id)).await {
+                    Ok(result) if result.has_errors() => HttpResponse::BadRequest().json(result),
+                    Ok(result) => HttpResponse::Ok().json(result),
+                    Err(e) => ApiError::from(e).error_response(),
+                }
+            }
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,209 @@
+use actix_web::{
+    delete, get, http::header, post, put, web, HttpRequest, HttpResponse, Responder, ResponseError,
+};
+
+use crate::data::{category::DbCategory, user::DbUser};
+use crate::models::{
+    api_error::ApiError,
+    category::Category,
+    tokens::find_user_id_by_token,
+    translation::{requested_locales, LanguageQuery},
+};
+
+// Cria uma nova categoria do cardápio. Disponível apenas para funcionários.
+//
+// Retorna o código 201 (Created) e um objeto JSON com a categoria gravada se a categoria foi criada com sucesso,
+// com o endereço da categoria no cabeçalho Location.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar a categoria ou o nome já existir.
+#[post("/api/v1/category/create/{user_id}/{user_token}")]
+async fn create(path: web::Path<(i32, String)>, category: web::Json<Category>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Category::from_json(category) {
+                Ok(new_category) => match DbCategory::create(new_category, user_id).await {
+                    Ok(category) => HttpResponse::Created()
+                        .insert_header((
+                            header::LOCATION,
+                            format!("/api/v1/category/read/{}", category.id().unwrap_or_default()),
+                        ))
+                        .json(category),
+                    Err(e) => {
+                        ApiError::from(e).error_response()
+                    }
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Obtém as informações da categoria correspondente ao id fornecido.
+// O idioma é escolhido pelo parâmetro '?lang=' ou pelo cabeçalho Accept-Language, com o conteúdo original em português
+// quando não há tradução.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as informações da categoria se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o idioma pedido é inválido.
+// Retorna o código 404 (Not Found) se não conseguiu encontrar a categoria.
+#[get("/api/v1/category/read/{id}")]
+async fn read(req: HttpRequest, path: web::Path<i32>, language: web::Query<LanguageQuery>) -> impl Responder {
+    let locales = match requested_locales(&req, language.lang()) {
+        Ok(locales) => locales,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match DbCategory::read(*path, &locales).await {
+        Ok(category) => HttpResponse::Ok().json(category),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Lista as categorias disponíveis na ordem do cardápio.
+// O idioma é escolhido pelo parâmetro '?lang=' ou pelo cabeçalho Accept-Language, com o conteúdo original em português
+// quando não há tradução.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as categorias se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o idioma pedido é inválido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as categorias.
+#[get("/api/v1/category/read")]
+async fn read_available(req: HttpRequest, language: web::Query<LanguageQuery>) -> impl Responder {
+    let locales = match requested_locales(&req, language.lang()) {
+        Ok(locales) => locales,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match DbCategory::read_all(true, &locales).await {
+        Ok(categories) => HttpResponse::Ok().json(categories),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Lista todas as categorias, incluindo as indisponíveis, com o conteúdo original para edição.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as categorias se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as categorias.
+#[get("/api/v1/category/read_all/{user_id}/{user_token}")]
+async fn read_all(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbCategory::read_all(false, &[]).await {
+                Ok(categories) => HttpResponse::Ok().json(categories),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza uma categoria. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (OK) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a categoria não for encontrada.
+#[put("/api/v1/category/update/{id}/{user_id}/{user_token}")]
+async fn update(
+    path: web::Path<(i32, i32, String)>,
+    category: web::Json<Category>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Category::from_json(category) {
+                Ok(new_category) => match DbCategory::update(id, new_category, user_id).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Remove uma categoria. Os produtos da categoria continuam cadastrados, sem categoria.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a categoria não for encontrada.
+#[delete("/api/v1/category/delete/{id}/{user_id}/{user_token}")]
+async fn delete(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbCategory::delete(id, user_id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Retorna o cardápio completo em uma única chamada: as categorias disponíveis em ordem,
+// cada uma com os seus produtos disponíveis.
+// O idioma é escolhido pelo parâmetro '?lang=' ou pelo cabeçalho Accept-Language, com o conteúdo original em português
+// quando não há tradução.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com o cardápio se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o idioma pedido é inválido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o cardápio.
+#[get("/api/v1/menu")]
+async fn menu(req: HttpRequest, language: web::Query<LanguageQuery>) -> impl Responder {
+    let locales = match requested_locales(&req, language.lang()) {
+        Ok(locales) => locales,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match DbCategory::read_menu(&locales).await {
+        Ok(menu) => HttpResponse::Ok().json(menu),
+        Err(e) => {
+            ApiError::from(e).error_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{http::StatusCode, test, App};
+
+    // Precisa do banco de dados de 'Db::connect' com a tabela do sql/tbl_category.sql
+    #[actix_web::test]
+    #[ignore = "requires the database from Db::connect"]
+    async fn read_returns_404_for_an_unknown_id() {
+        let app = test::init_service(App::new().service(read)).await;
+
+        let request = test::TestRequest::get()
+            .uri(&format!("/api/v1/category/read/{}", i32::MAX))
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let body: serde_json::Value = test::read_body_json(response).await;
+        assert_eq!(body["code"], "not_found");
+    }
+}
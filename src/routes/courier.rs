@@ -0,0 +1,145 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{courier::DbCourier, delivery::DbDelivery, user::DbUser};
+use crate::models::{api_error::ApiError, courier::CourierLocation, tokens::find_user_id_by_token};
+
+// Cadastra o usuário identificado por courier_id como entregador. Disponível apenas para funcionários.
+//
+// Retorna o código 201 (Created) se o entregador foi cadastrado com sucesso.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao cadastrar o entregador.
+#[post("/api/v1/courier/create/{courier_id}/{user_id}/{user_token}")]
+async fn create(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (courier_id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbCourier::create(courier_id).await {
+                Ok(_) => HttpResponse::Created().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os entregadores ativos com a última posição conhecida. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os entregadores se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os entregadores.
+#[get("/api/v1/courier/read/{user_id}/{user_token}")]
+async fn read(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbCourier::read_all().await {
+                Ok(couriers) => HttpResponse::Ok().json(couriers),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Desativa um entregador. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o entregador não for encontrado.
+#[delete("/api/v1/courier/delete/{courier_id}/{user_id}/{user_token}")]
+async fn delete(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (courier_id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbCourier::delete(courier_id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Altera a disponibilidade do entregador autenticado para receber novos pedidos.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 404 (Not Found) se o usuário não é um entregador ativo.
+#[put("/api/v1/courier/available/{available}/{user_id}/{user_token}")]
+async fn update_available(path: web::Path<(bool, i32, String)>) -> impl Responder {
+    let (available, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbCourier::update_available(user_id, available).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza a posição atual do entregador autenticado.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se as coordenadas são inválidas.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 404 (Not Found) se o usuário não é um entregador ativo.
+#[put("/api/v1/courier/location/{user_id}/{user_token}")]
+async fn update_location(
+    path: web::Path<(i32, String)>,
+    location: web::Json<CourierLocation>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match CourierLocation::from_json(location) {
+                Ok(location) => match DbCourier::update_location(user_id, location).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista as entregas em andamento do entregador autenticado.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as entregas se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é entregador.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as entregas.
+#[get("/api/v1/courier/deliveries/{user_id}/{user_token}")]
+async fn read_deliveries(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbCourier::is_courier(user_id).await {
+            Ok(true) => match DbDelivery::read_by_courier(user_id).await {
+                Ok(deliveries) => HttpResponse::Ok().json(deliveries),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,124 @@
+use actix_web::{get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{courier::DbCourier, delivery::DbDelivery, error::DataError, user::DbUser};
+use crate::models::{api_error::ApiError, delivery::DeliveryConfirmation, tokens::find_user_id_by_token};
+
+// Atribui um pedido a um entregador escolhido. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 422 (Unprocessable Entity) com uma mensagem de erro se o pedido ou o entregador não puderem ser usados,
+// como um pedido cancelado, entregue ou agendado que ainda não foi enviado à cozinha.
+#[put("/api/v1/delivery/assign/{id_purchase}/{courier_id}/{user_id}/{user_token}")]
+async fn assign(path: web::Path<(i32, i32, i32, String)>) -> impl Responder {
+    let (id_purchase, courier_id, user_id, user_token) = (path.0, path.1, path.2, &path.3);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbDelivery::assign(id_purchase, courier_id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(DataError::Rule(message)) | Err(DataError::NotFound(message)) => {
+                    ApiError::Unprocessable(message).error_response()
+                }
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atribui um pedido automaticamente ao próximo entregador disponível (rodízio). Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com a entrega criada se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 422 (Unprocessable Entity) com uma mensagem de erro se não houver entregador disponível ou o pedido
+// não puder ser entregue.
+#[put("/api/v1/delivery/auto_assign/{id_purchase}/{user_id}/{user_token}")]
+async fn auto_assign(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id_purchase, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbDelivery::auto_assign(id_purchase).await {
+                Ok(_) => match DbDelivery::read(id_purchase).await {
+                    Ok((_, delivery)) => HttpResponse::Ok().json(delivery),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(DataError::Rule(message)) | Err(DataError::NotFound(message)) => {
+                    ApiError::Unprocessable(message).error_response()
+                }
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Confirma a entrega de um pedido pelo entregador responsável, com o código mostrado ao cliente.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é entregador.
+// Retorna o código 422 (Unprocessable Entity) com uma mensagem de erro se o código não confere, a entrega não é do entregador
+// ou o pedido foi cancelado.
+#[post("/api/v1/delivery/confirm/{id_purchase}/{user_id}/{user_token}")]
+async fn confirm(
+    path: web::Path<(i32, i32, String)>,
+    confirmation: web::Json<DeliveryConfirmation>,
+) -> impl Responder {
+    let (id_purchase, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbCourier::is_courier(user_id).await {
+            Ok(true) => match DeliveryConfirmation::from_json(confirmation) {
+                Ok(confirmation) => {
+                    match DbDelivery::confirm(id_purchase, user_id, confirmation).await {
+                        Ok(_) => HttpResponse::Ok().finish(),
+                        Err(DataError::Rule(message)) | Err(DataError::NotFound(message)) => {
+                            ApiError::Unprocessable(message).error_response()
+                        }
+                        Err(e) => ApiError::from(e).error_response(),
+                    }
+                }
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Acompanha a entrega de um pedido, com o entregador e sua última posição conhecida.
+// Disponível para o dono do pedido e para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com a entrega se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não tem acesso ao pedido.
+// Retorna o código 404 (Not Found) se o pedido ainda não foi atribuído a um entregador.
+#[get("/api/v1/delivery/track/{id_purchase}/{user_id}/{user_token}")]
+async fn track(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id_purchase, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbDelivery::read(id_purchase).await {
+                Ok((owner_id, delivery)) if owner_id == user_id => HttpResponse::Ok().json(delivery),
+                Ok((_, delivery)) => match DbUser::is_staff(user_id).await {
+                    Ok(true) => HttpResponse::Ok().json(delivery),
+                    Ok(false) => ApiError::Unauthorized.error_response(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
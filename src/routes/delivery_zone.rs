@@ -0,0 +1,140 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{delivery_zone::DbDeliveryZone, error::DataError, user::DbUser};
+use crate::models::{api_error::ApiError, delivery_zone::DeliveryZone, tokens::find_user_id_by_token};
+
+// Cria uma nova zona de entrega. Disponível apenas para funcionários.
+//
+// Retorna o código 201 (Created) se a zona foi criada com sucesso.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar a zona.
+#[post("/api/v1/delivery_zone/create/{user_id}/{user_token}")]
+async fn create(
+    path: web::Path<(i32, String)>,
+    zone: web::Json<DeliveryZone>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DeliveryZone::from_json(zone) {
+                Ok(new_zone) => match DbDeliveryZone::create(new_zone).await {
+                    Ok(_) => HttpResponse::Created().finish(),
+                    Err(e) => {
+                        ApiError::from(e).error_response()
+                    }
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista as zonas de entrega ativas.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as zonas se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as zonas.
+#[get("/api/v1/delivery_zone/read")]
+async fn read() -> impl Responder {
+    match DbDeliveryZone::read_all(true).await {
+        Ok(zones) => HttpResponse::Ok().json(zones),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Lista todas as zonas de entrega, incluindo as inativas. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as zonas se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as zonas.
+#[get("/api/v1/delivery_zone/read_all/{user_id}/{user_token}")]
+async fn read_all(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbDeliveryZone::read_all(false).await {
+                Ok(zones) => HttpResponse::Ok().json(zones),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Calcula a taxa, o pedido mínimo e o tempo estimado de entrega para as coordenadas informadas.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com a cotação se o endereço estiver em uma zona de entrega.
+// Retorna o código 422 (Unprocessable Entity) com uma mensagem de erro se o endereço estiver fora da área de entrega.
+// Retorna o código 500 (Internal Server Error) se houve problema ao ler as zonas de entrega.
+#[get("/api/v1/delivery_zone/quote/{latitude}/{longitude}")]
+async fn quote(path: web::Path<(f64, f64)>) -> impl Responder {
+    let (latitude, longitude) = (path.0, path.1);
+
+    match DbDeliveryZone::quote(latitude, longitude).await {
+        Ok(quote) => HttpResponse::Ok().json(quote),
+        Err(DataError::Rule(message)) => ApiError::Unprocessable(message).error_response(),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Atualiza uma zona de entrega. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (OK) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a zona não for encontrada.
+#[put("/api/v1/delivery_zone/update/{id}/{user_id}/{user_token}")]
+async fn update(
+    path: web::Path<(i32, i32, String)>,
+    zone: web::Json<DeliveryZone>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DeliveryZone::from_json(zone) {
+                Ok(new_zone) => match DbDeliveryZone::update(id, new_zone).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Remove uma zona de entrega. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a zona não for encontrada.
+#[delete("/api/v1/delivery_zone/delete/{id}/{user_id}/{user_token}")]
+async fn delete(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbDeliveryZone::delete(id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
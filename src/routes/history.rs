@@ -1,39 +1,152 @@
-use actix_web::{get, post, put, web, HttpResponse, Responder};
+use actix_web::{get, http::header, post, put, web, HttpResponse, Responder, ResponseError};
+use serde::Deserialize;
 
+use crate::data::address::DbAddress;
+use crate::data::delivery_zone::DbDeliveryZone;
+use crate::data::error::DataError;
 use crate::data::historic::DbHistoric;
+use crate::data::loyalty::DbLoyalty;
+use crate::data::payment_method::DbPaymentMethod;
+use crate::data::promotion::DbPromotion;
+use crate::data::store::DbStore;
+use crate::data::user::DbUser;
 use crate::models::{
-    product_cart::ProductCart, product_cart_update::ProductCartUpdate,
-    tokens::find_user_id_by_token,
+    api_error::ApiError,
+    address::Address, money::Money, order::Order, order_total::{OrderTotal, PricedItem}, product_cart::{ProductCart, STATUS_SCHEDULED},
+    product_cart_update::ProductCartUpdate, tokens::find_user_id_by_token,
 };
 
+#[derive(Deserialize)]
+struct Checkout {
+    address_id: Option<i32>,
+    payment_method_id: Option<i32>,
+    coupon: Option<String>,
+    redeem_points: Option<i32>,
+    scheduled_for: Option<String>,
+}
+
+// Credenciais do funcionário na rota anterior da atualização, que não as recebia no caminho
+#[derive(Deserialize)]
+struct StaffQuery {
+    user_id: Option<i32>,
+    user_token: Option<String>,
+}
+
 // Cria um novo histórico de compra do usuário identificado pelo user_id e user_token com os dados fornecidos
+// O endereço de entrega é escolhido pelo parâmetro '?address_id=', usando o endereço padrão se omitido,
+// ou o endereço em texto do perfil se o usuário não tem endereços cadastrados.
+// Todos os itens devem ter o mesmo 'payment_type'; com cartão ou PIX o método salvo é escolhido por '?payment_method_id='.
+// Um cupom de desconto pode ser informado pelo parâmetro '?coupon=' e pontos de fidelidade por '?redeem_points='.
+// O parâmetro '?scheduled_for=' (YYYY-MM-DD HH:MM:SS) agenda o pedido, que fica com o status 6 até ser enviado à cozinha.
+// Os preços dos itens, a taxa de entrega e os descontos são calculados pelo servidor.
 //
-// Retorna o código 200 (OK) se o historico de compras foi criado com sucesso.
+// Retorna o código 201 (Created) e um objeto JSON com o id, os valores e os itens gravados do pedido se o historico de compras
+// foi criado com sucesso. O cabeçalho Location aponta para os valores do pedido sem as credenciais, que o cliente
+// acrescenta ao final ('/{user_id}/{user_token}').
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o pedido não puder ser aceito (restaurante fechado, itens, pagamento, endereço ou área de entrega).
 // Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
 // Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar o histórico de compra.
 #[post("/api/v1/historic/create/{user_id}/{user_token}")]
 async fn create(
     path: web::Path<(i32, String)>,
+    checkout: web::Query<Checkout>,
     purchase: web::Json<Vec<ProductCart>>,
 ) -> impl Responder {
     let (user_id, user_token) = (path.0, &path.1);
 
     match ProductCart::from_json(purchase) {
-        Ok(items) => match find_user_id_by_token(user_token) {
+        Ok(mut items) => match find_user_id_by_token(user_token) {
             Some(user_id_token) if user_id_token == user_id => {
-                match DbHistoric::create(&items, user_id).await {
-                    Ok(_) => HttpResponse::Ok().finish(),
-                    Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+                let (address, total, priced_items, scheduled_for) =
+                    match prepare_order(user_id, checkout.into_inner(), &mut items).await {
+                        Ok(order) => order,
+                        Err(e) => return ApiError::from(e).error_response(),
+                    };
+
+                match DbHistoric::create(
+                    &items,
+                    &priced_items,
+                    user_id,
+                    &address,
+                    &total,
+                    scheduled_for.as_deref(),
+                )
+                .await
+                {
+                    Ok(created) => {
+                        let order = Order::new(
+                            created.first().map(|item| item.id_purchase()).unwrap_or_default(),
+                            total,
+                            created,
+                        );
+                        HttpResponse::Created()
+                            .insert_header((
+                                header::LOCATION,
+                                format!("/api/v1/historic/total/{}", order.id_purchase()),
+                            ))
+                            .json(order)
+                    }
+                    Err(e) => ApiError::from(e).error_response(),
                 }
             }
-            Some(_) => HttpResponse::Unauthorized().finish(),
-            None => HttpResponse::Unauthorized().finish(),
+            Some(_) => ApiError::Unauthorized.error_response(),
+            None => ApiError::Unauthorized.error_response(),
         },
-        Err(e) => {
-            println!("Aqui o Erro: {}", e);
-            return HttpResponse::InternalServerError().body(e.to_string());
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Valida o pedido e calcula seus valores antes de gravá-lo.
+async fn prepare_order(
+    user_id: i32,
+    checkout: Checkout,
+    items: &mut [ProductCart],
+) -> Result<(Address, OrderTotal, Vec<PricedItem>, Option<String>), DataError> {
+    if items.is_empty() {
+        return Err(DataError::Rule("O pedido não possui itens".to_string()));
+    }
+
+    let scheduled_for = DbStore::validate_checkout(checkout.scheduled_for).await?;
+    if scheduled_for.is_some() {
+        for item in items.iter_mut() {
+            item.set_status(STATUS_SCHEDULED).map_err(|e| DataError::Rule(e.to_string()))?;
         }
     }
+
+    let payment_type = items[0].payment_type();
+    if items.iter().any(|item| item.payment_type() != payment_type) {
+        return Err(DataError::Rule(
+            "Todos os itens do pedido devem ter o mesmo tipo de pagamento".to_string(),
+        ));
+    }
+    DbPaymentMethod::validate_checkout(user_id, &payment_type, checkout.payment_method_id).await?;
+
+    // Usuários sem endereços cadastrados continuam pedindo com o endereço em texto do perfil
+    let address = match DbAddress::read_for_checkout(user_id, checkout.address_id).await? {
+        Some(address) => address,
+        None => Address::legacy(DbUser::read(user_id).await?.address()),
+    };
+    let quote = match (address.latitude(), address.longitude()) {
+        (Some(latitude), Some(longitude)) => DbDeliveryZone::quote(latitude, longitude).await?,
+        _ => DbDeliveryZone::quote_without_coordinates().await?,
+    };
+
+    let priced_items = DbHistoric::price_items(items).await?;
+    let subtotal: Money = priced_items.iter().map(|item| item.line_total()).sum();
+    if subtotal < quote.min_order() {
+        return Err(DataError::Rule(format!(
+            "O pedido mínimo para esta região é R$ {}",
+            quote.min_order()
+        )));
+    }
+
+    let mut total = OrderTotal::new(subtotal, quote.fee(), quote.zone_id());
+    DbPromotion::apply(user_id, checkout.coupon, &priced_items, &mut total).await?;
+    if let Some(points) = checkout.redeem_points {
+        DbLoyalty::apply_redemption(user_id, points, &mut total).await?;
+    }
+
+    Ok((address, total, priced_items, scheduled_for))
 }
 
 // Busca o histórico de compras do usuário identificado pelo user_id e user_token
@@ -48,49 +161,120 @@ async fn read(path: web::Path<(i32, String)>) -> HttpResponse {
     match find_user_id_by_token(user_token) {
         Some(user_id_token) if user_id_token == user_id => match DbHistoric::read(user_id).await {
             Ok(historic) => HttpResponse::Ok().json(historic),
-            Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+            Err(e) => ApiError::from(e).error_response(),
         },
-        Some(_) => HttpResponse::Unauthorized().finish(),
-        None => HttpResponse::Unauthorized().finish(),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Busca a cópia do endereço de entrega gravada no pedido. Disponível para o dono do pedido e para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com o endereço se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não tem acesso ao pedido.
+// Retorna o código 404 (Not Found) se o pedido não possui endereço gravado.
+#[get("/api/v1/historic/address/{id_purchase}/{user_id}/{user_token}")]
+async fn read_address(path: web::Path<(i32, i32, String)>) -> HttpResponse {
+    let (id_purchase, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbAddress::read_snapshot(id_purchase).await {
+                Ok((owner_id, address)) if owner_id == user_id => HttpResponse::Ok().json(address),
+                Ok((_, address)) => match DbUser::is_staff(user_id).await {
+                    Ok(true) => HttpResponse::Ok().json(address),
+                    Ok(false) => ApiError::Unauthorized.error_response(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
 }
 
-// Atualiza o status e o tipo de pagamento de uma compra na tabela de histórico para um usuário autenticado.
+// Busca os valores calculados pelo servidor para o pedido. Disponível para o dono do pedido e para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com subtotal, taxa de entrega e total se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não tem acesso ao pedido.
+// Retorna o código 404 (Not Found) se o pedido não for encontrado.
+#[get("/api/v1/historic/total/{id_purchase}/{user_id}/{user_token}")]
+async fn read_total(path: web::Path<(i32, i32, String)>) -> HttpResponse {
+    let (id_purchase, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbHistoric::read_total(id_purchase).await {
+                Ok((owner_id, total)) if owner_id == user_id => HttpResponse::Ok().json(total),
+                Ok((_, total)) => match DbUser::is_staff(user_id).await {
+                    Ok(true) => HttpResponse::Ok().json(total),
+                    Ok(false) => ApiError::Unauthorized.error_response(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza o status e o tipo de pagamento de uma compra na tabela de histórico. Disponível apenas para funcionários.
+// Cancelar o pedido devolve o estoque e os pontos de fidelidade resgatados; os pontos ganhos são creditados apenas na
+// confirmação da entrega.
 //
 // Retorna código de status 200 (OK) se a atualização for bem-sucedida
-// Retorna código de status 401 (Unauthorized) se o usuário não estiver autenticado ou se o token fornecido for inválido
+// Retorna código de status 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos
+// Retorna código de status 401 (Unauthorized) se o token fornecido for inválido ou o usuário não for funcionário
 // Retorna código de status 500 (Internal Server Error) com uma mensagem de erro se houver algum problema ao atualizar o histórico
+#[put("/api/v1/historic/update/{user_id}/{user_token}")]
+async fn update(
+    path: web::Path<(i32, String)>,
+    items_update: web::Json<ProductCartUpdate>,
+) -> HttpResponse {
+    update_status(path.0, &path.1, items_update).await
+}
+
+// Rota anterior da atualização, mantida para os clientes existentes. As credenciais do funcionário vêm nos
+// parâmetros '?user_id=' e '?user_token='.
+//
+// Retorna os mesmos códigos da rota '/api/v1/historic/update/{user_id}/{user_token}'.
 #[put("/api/v1/historic/update/")]
-async fn update(items_update: web::Json<ProductCartUpdate>) -> HttpResponse {
-    match ProductCartUpdate::from_json(items_update) {
-        Ok(items) => {
-            match DbHistoric::update(items.id_purchase(), items.status(), items.payment_type())
-                .await
-            {
-                Ok(_) => HttpResponse::Ok().finish(),
-                Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
-            }
-        }
-        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+async fn update_legacy(
+    staff: web::Query<StaffQuery>,
+    items_update: web::Json<ProductCartUpdate>,
+) -> HttpResponse {
+    match (staff.user_id, &staff.user_token) {
+        (Some(user_id), Some(user_token)) => update_status(user_id, user_token, items_update).await,
+        _ => ApiError::Unauthorized.error_response(),
     }
+}
 
-    /*
-    match ProductCartUpdate::from_json(items_update) {
-        Ok(items) => match find_user_id_by_token(user_token) {
-            Some(user_id_token) if user_id_token == user_id => {
-                match DbHistoric::update(items.id_purchase(), items.status(), items.payment_type())
-                    .await
-                {
-                    Ok(_) => HttpResponse::Ok().finish(),
-                    Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+async fn update_status(
+    user_id: i32,
+    user_token: &str,
+    items_update: web::Json<ProductCartUpdate>,
+) -> HttpResponse {
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match ProductCartUpdate::from_json(items_update) {
+                Ok(items) => {
+                    match DbHistoric::update(items.id_purchase(), items.status(), items.payment_type())
+                        .await
+                    {
+                        Ok(_) => HttpResponse::Ok().finish(),
+                        Err(e) => ApiError::from(e).error_response(),
+                    }
                 }
-            }
-            Some(_) => HttpResponse::Unauthorized().finish(),
-            None => HttpResponse::Unauthorized().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
         },
-        Err(e) => return HttpResponse::InternalServerError().body(e.to_string()),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
-    */
 }
 
 // Busca o histórico de compras dos usuários
@@ -102,6 +286,39 @@ async fn update(items_update: web::Json<ProductCartUpdate>) -> HttpResponse {
 async fn read_all() -> HttpResponse {
     match DbHistoric::read_all().await {
         Ok(historic) => HttpResponse::Ok().json(historic),
-        Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::tokens::generate_token;
+    use actix_web::{http::StatusCode, test, App};
+    use serde_json::json;
+
+    fn body() -> serde_json::Value {
+        json!({"status": 5, "payment_type": "pix", "id_purchase": 1})
+    }
+
+    #[actix_web::test]
+    async fn legacy_update_path_requires_staff_credentials() {
+        let app = test::init_service(App::new().service(update_legacy)).await;
+
+        let request = test::TestRequest::put()
+            .uri("/api/v1/historic/update/")
+            .set_json(body())
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        // Token de outro usuário
+        let token = generate_token(41).unwrap();
+        let request = test::TestRequest::put()
+            .uri(&format!("/api/v1/historic/update/?user_id=42&user_token={}", token))
+            .set_json(body())
+            .to_request();
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
     }
 }
@@ -0,0 +1,160 @@
+use actix_multipart::Multipart;
+use actix_web::{post, web, HttpResponse, Responder, ResponseError};
+use futures_util::StreamExt;
+use serde::Deserialize;
+
+use crate::data::{
+    blob_store::{BlobStore, LocalBlobStore},
+    product::DbProduct,
+    user::DbUser,
+};
+use crate::models::{
+    api_error::ApiError,
+    image::{process_upload, ImageVariant, UploadedImage, MAX_UPLOAD_BYTES},
+    tokens::find_user_id_by_token,
+};
+
+// Variante usada como imagem principal do produto
+const MAIN_VARIANT: &str = "large";
+
+#[derive(Deserialize)]
+struct UploadTarget {
+    product_id: Option<i32>,
+}
+
+// Recebe uma imagem pelo campo 'image' de um formulário multipart e grava as variantes redimensionadas
+// e as miniaturas WebP. Com o parâmetro '?product_id=' a imagem principal passa a ser a imagem do produto.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 201 (Created) e um objeto JSON com o valor de 'image' do produto e os endereços das variantes.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o arquivo não for uma imagem JPEG, PNG ou WebP válida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o produto informado não for encontrado.
+// Retorna o código 413 (Payload Too Large) se o arquivo ultrapassar o tamanho máximo.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao gravar os arquivos.
+#[post("/api/v1/image/upload/{user_id}/{user_token}")]
+async fn upload(
+    path: web::Path<(i32, String)>,
+    target: web::Query<UploadTarget>,
+    payload: Multipart,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => store_upload(payload, target.product_id, user_id).await,
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+async fn store_upload(
+    mut payload: Multipart,
+    product_id: Option<i32>,
+    user_id: i32,
+) -> HttpResponse {
+    let (bytes, content_type) = match read_image_field(&mut payload).await {
+        Ok(Some(field)) => field,
+        Ok(None) => {
+            return ApiError::validation("Missing 'image' field".to_string()).error_response()
+        }
+        Err(response) => return response,
+    };
+
+    let variants = match process_upload(&bytes, &content_type) {
+        Ok(variants) => variants,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    let store = LocalBlobStore::from_env();
+    let folder = format!(
+        "uploads/{:x}{:08x}",
+        chrono::Utc::now().timestamp_millis(),
+        rand::random::<u32>()
+    );
+    let mut stored: Vec<String> = Vec::new();
+    let mut uploaded = Vec::new();
+
+    for variant in variants.iter() {
+        let key = format!("{}/{}.{}", folder, variant.name(), variant.extension());
+        if let Err(e) = store
+            .put(&key, variant.bytes().to_vec(), variant.content_type())
+            .await
+        {
+            discard(&store, &stored).await;
+            return ApiError::from(e).error_response();
+        }
+        uploaded.push(ImageVariant::new(
+            variant.name().to_string(),
+            variant.width(),
+            variant.height(),
+            store.url(&key),
+        ));
+        stored.push(key);
+    }
+
+    let main = stored
+        .iter()
+        .find(|key| key.starts_with(&format!("{}/{}.", folder, MAIN_VARIANT)))
+        .cloned()
+        .unwrap_or_default();
+    let image = UploadedImage::new(main.clone(), store.url(&main), uploaded);
+
+    if let Some(product_id) = product_id {
+        if let Err(e) = DbProduct::update_image(product_id, image.image(), user_id).await {
+            discard(&store, &stored).await;
+            return ApiError::from(e).error_response();
+        }
+    }
+
+    HttpResponse::Created().json(image)
+}
+
+// Lê o campo 'image' respeitando o tamanho máximo. Os demais campos são ignorados.
+async fn read_image_field(
+    payload: &mut Multipart,
+) -> Result<Option<(Vec<u8>, String)>, HttpResponse> {
+    while let Some(field) = payload.next().await {
+        let mut field = field
+            .map_err(|e| ApiError::validation(format!("Invalid upload: {}", e)).error_response())?;
+        if field.content_disposition().get_name() != Some("image") {
+            continue;
+        }
+
+        let content_type = field
+            .content_type()
+            .map(|mime| mime.essence_str().to_string())
+            .unwrap_or_default();
+        let mut bytes = Vec::new();
+
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| {
+                ApiError::validation(format!("Invalid upload: {}", e)).error_response()
+            })?;
+            if bytes.len() + chunk.len() > MAX_UPLOAD_BYTES {
+                return Err(ApiError::PayloadTooLarge(format!(
+                    "Image cannot exceed {} MB",
+                    MAX_UPLOAD_BYTES / (1024 * 1024)
+                ))
+                .error_response());
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+
+        return Ok(Some((bytes, content_type)));
+    }
+
+    Ok(None)
+}
+
+// Remove os arquivos já gravados de um envio que falhou
+async fn discard(store: &impl BlobStore, keys: &[String]) {
+    for key in keys {
+        if let Err(e) = store.delete(key).await {
+            log::warn!("Failed to discard uploaded image '{}': {}", key, e);
+        }
+    }
+}
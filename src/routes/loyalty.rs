@@ -0,0 +1,91 @@
+use actix_web::{get, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{loyalty::DbLoyalty, user::DbUser};
+use crate::models::{api_error::ApiError, loyalty::LoyaltySettings, tokens::find_user_id_by_token};
+
+// Busca o saldo de pontos de fidelidade e o extrato de transações do usuário.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com o saldo e as transações se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o extrato.
+#[get("/api/v1/loyalty/read/{user_id}/{user_token}")]
+async fn read(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbLoyalty::read_statement(user_id).await {
+                Ok(statement) => HttpResponse::Ok().json(statement),
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Busca as regras do programa de fidelidade (pontos por real, valor do ponto, validade e resgate mínimo).
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as regras se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as regras.
+#[get("/api/v1/loyalty/settings")]
+async fn read_settings() -> impl Responder {
+    match DbLoyalty::read_settings().await {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Atualiza as regras do programa de fidelidade. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao atualizar as regras.
+#[put("/api/v1/loyalty/settings/{user_id}/{user_token}")]
+async fn update_settings(
+    path: web::Path<(i32, String)>,
+    settings: web::Json<LoyaltySettings>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match LoyaltySettings::from_json(settings) {
+                Ok(settings) => match DbLoyalty::update_settings(settings).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Confere o livro de pontos: transações desbalanceadas e totais emitidos, resgatados, expirados e em aberto.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com a conferência se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o livro.
+#[get("/api/v1/loyalty/audit/{user_id}/{user_token}")]
+async fn audit(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbLoyalty::audit().await {
+                Ok(audit) => HttpResponse::Ok().json(audit),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,99 @@
+use actix_web::{get, web, HttpResponse, Responder, ResponseError};
+use serde::Deserialize;
+
+use crate::data::{menu_audit::DbMenuAudit, user::DbUser};
+use crate::models::{
+    api_error::ApiError,
+    menu_audit::{parse_moment, MenuEntity},
+    tokens::find_user_id_by_token,
+};
+
+#[derive(Deserialize)]
+struct AuditFilter {
+    entity: Option<MenuEntity>,
+    entity_id: Option<i32>,
+}
+
+#[derive(Deserialize)]
+struct PriceMoment {
+    at: String,
+}
+
+// Lista as últimas alterações do cardápio com o funcionário, o horário e os dados antes e depois.
+// Os parâmetros '?entity=' ('product' ou 'category') e '?entity_id=' filtram as alterações.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as alterações se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as alterações.
+#[get("/api/v1/menu/audit/{user_id}/{user_token}")]
+async fn read(path: web::Path<(i32, String)>, filter: web::Query<AuditFilter>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbMenuAudit::read(filter.entity, filter.entity_id).await {
+                Ok(changes) => HttpResponse::Ok().json(changes),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os preços de um produto, do mais recente ao mais antigo. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os preços se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os preços.
+#[get("/api/v1/product/price_history/{id}/{user_id}/{user_token}")]
+async fn read_prices(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbMenuAudit::read_prices(id).await {
+                Ok(prices) => HttpResponse::Ok().json(prices),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Obtém o preço de um produto em vigor no momento informado por '?at=' (YYYY-MM-DD HH:MM:SS ou YYYY-MM-DD,
+// que considera o fim do dia), para a conferência de pedidos contestados. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com o preço e quando ele passou a valer se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se a data for inválida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se não houver preço registrado para o produto naquele momento.
+#[get("/api/v1/product/price_at/{id}/{user_id}/{user_token}")]
+async fn read_price_at(
+    path: web::Path<(i32, i32, String)>,
+    moment: web::Query<PriceMoment>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match parse_moment(&moment.at) {
+                Ok(moment) => match DbMenuAudit::read_price_at(id, &moment).await {
+                    Ok(price) => HttpResponse::Ok().json(price),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,100 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::payment_method::DbPaymentMethod;
+use crate::models::{
+    api_error::ApiError, payment_method::PaymentMethod, tokens::find_user_id_by_token,
+};
+
+// Cadastra um novo método de pagamento para o usuário identificado pelo user_id e user_token.
+//
+// Retorna o código 201 (Created) se o método de pagamento foi criado com sucesso.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar o método de pagamento.
+#[post("/api/v1/payment_method/create/{user_id}/{user_token}")]
+async fn create(
+    path: web::Path<(i32, String)>,
+    method: web::Json<PaymentMethod>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match PaymentMethod::from_json(method) {
+            Ok(new_method) => match DbPaymentMethod::create(user_id, new_method).await {
+                Ok(_) => HttpResponse::Created().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os métodos de pagamento salvos do usuário, com o método padrão primeiro.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os métodos de pagamento se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os métodos de pagamento.
+#[get("/api/v1/payment_method/read/{user_id}/{user_token}")]
+async fn read(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbPaymentMethod::read(user_id).await {
+                Ok(methods) => HttpResponse::Ok().json(methods),
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza um método de pagamento do usuário. Marcar como padrão desmarca os demais.
+//
+// Retorna o código 200 (OK) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 404 (Not Found) se o método de pagamento não pertence ao usuário ou não existe.
+#[put("/api/v1/payment_method/update/{id}/{user_id}/{user_token}")]
+async fn update(
+    path: web::Path<(i32, i32, String)>,
+    method: web::Json<PaymentMethod>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match PaymentMethod::from_json(method) {
+            Ok(new_method) => match DbPaymentMethod::update(id, user_id, new_method).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Remove um método de pagamento do usuário.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou não corresponde ao ID do usuário fornecido.
+// Retorna o código 404 (Not Found) se o método de pagamento não for encontrado.
+#[delete("/api/v1/payment_method/delete/{id}/{user_id}/{user_token}")]
+async fn delete(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => {
+            match DbPaymentMethod::delete(id, user_id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            }
+        }
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -1,89 +1,276 @@
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{
+    delete, get, http::header, post, put, web, HttpRequest, HttpResponse, Responder, ResponseError,
+};
+use serde::Deserialize;
 
-use crate::data::product::DbProduct;
-use crate::models::product::Product;
+use crate::data::{product::DbProduct, user::DbUser};
+use crate::models::{
+    api_error::ApiError,
+    money::Money,
+    nutrition::Allergen,
+    product::Product,
+    product_listing::{ProductListing, ProductSort, DEFAULT_LIMIT as LISTING_DEFAULT_LIMIT},
+    product_search::{ProductSearch, DEFAULT_LIMIT},
+    tokens::find_user_id_by_token,
+    translation::{requested_locales, LanguageQuery},
+};
+
+// Funcionário que faz a alteração, registrado no histórico do cardápio
+#[derive(Deserialize)]
+struct Actor {
+    user_id: Option<i32>,
+    user_token: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ListingParams {
+    sort: Option<ProductSort>,
+    category_id: Option<i32>,
+    min_price: Option<Money>,
+    max_price: Option<Money>,
+    only_available: Option<bool>,
+    exclude_allergens: Option<String>,
+    limit: Option<i64>,
+    cursor: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SearchParams {
+    q: String,
+    category_id: Option<i32>,
+    only_available: Option<bool>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
 
 // Cria um novo produto com os dados fornecidos.
+// Os parâmetros '?user_id=&user_token=' identificam o funcionário no histórico de alterações do cardápio.
 //
-// Retorna o código 201 (Created) se o produto foi criado com sucesso.
+// Retorna o código 201 (Created) e um objeto JSON com o produto gravado (id, código e disponibilidade calculada)
+// se o produto foi criado com sucesso, com o endereço do produto no cabeçalho Location.
 // Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
 // Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar o produto ou o nome já existir.
 #[post("/api/v1/product/create")]
-pub async fn create(product: web::Json<Product>) -> impl Responder {
+pub async fn create(actor: web::Query<Actor>, product: web::Json<Product>) -> impl Responder {
+    let user_id = match staff_actor(actor.into_inner()).await {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
     match Product::from_json(product) {
-        Ok(new_product) => match DbProduct::create(new_product).await {
-            Ok(_) => HttpResponse::Created().finish(),
+        Ok(new_product) => match DbProduct::create(new_product, user_id).await {
+            Ok(product) => HttpResponse::Created()
+                .insert_header((
+                    header::LOCATION,
+                    format!("/api/v1/product/read/{}", product.id().unwrap_or_default()),
+                ))
+                .json(product),
             Err(e) => {
-                HttpResponse::InternalServerError().body(format!("Failed to create product: {}", e))
+                ApiError::from(e).error_response()
             }
         },
-        Err(e) => HttpResponse::BadRequest().body(e),
+        Err(e) => ApiError::from(e).error_response(),
     }
 }
 
 // Obtém as informações do produto correspondente ao id fornecido.
+// O idioma é escolhido pelo parâmetro '?lang=' ou pelo cabeçalho Accept-Language, com o conteúdo original em português
+// quando não há tradução.
 //
 // Retorna o código 200 (Ok) e um objeto JSON com as informações do produto se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se o idioma pedido é inválido.
 // Retorna o código 404 (Not Found) se não conseguiu encontrar o produto.
 #[get("/api/v1/product/read/{id}")]
-async fn read(path: web::Path<i32>) -> impl Responder {
-    match DbProduct::read(*path).await {
+async fn read(req: HttpRequest, path: web::Path<i32>, language: web::Query<LanguageQuery>) -> impl Responder {
+    let locales = match requested_locales(&req, language.lang()) {
+        Ok(locales) => locales,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match DbProduct::read(*path, &locales).await {
         Ok(product) => HttpResponse::Ok()
             .content_type("text/html; charset=utf-8")
             .json(product),
-        Err(e) => HttpResponse::NotFound().body(e),
+        Err(e) => ApiError::from(e).error_response(),
     }
 }
 
 // Retorna uma lista de produtos dentro de um intervalo especificado.
+// Mantido para os clientes existentes; novas listagens devem usar '/api/v1/product/list'.
+// O idioma é escolhido pelo parâmetro '?lang=' ou pelo cabeçalho Accept-Language, com o conteúdo original em português
+// quando não há tradução.
 //
 // Retorna o código 200 (Ok) e um objeto JSON com as informações dos produtos se a operação for bem sucedida.
 // Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
 // Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os produtos.
 #[get("/api/v1/product/read/{start}/{end}/{only_avaliable}")]
-async fn read_in_range(path: web::Path<(usize, usize, bool)>) -> impl Responder {
+async fn read_in_range(
+    req: HttpRequest,
+    path: web::Path<(usize, usize, bool)>,
+    language: web::Query<LanguageQuery>,
+) -> impl Responder {
     let (start, limit, only_avaliable) = (path.0, path.1, path.2);
+    let locales = match requested_locales(&req, language.lang()) {
+        Ok(locales) => locales,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
 
     match limit < start || limit - start > 100 {
-        true => HttpResponse::BadRequest().body("Invalid range"),
+        true => ApiError::validation("Invalid range".to_string()).error_response(),
         false => {
-            match DbProduct::read_in_range(start.try_into().unwrap(), limit.try_into().unwrap(), only_avaliable).await {
+            match DbProduct::read_in_range(start.try_into().unwrap(), limit.try_into().unwrap(), only_avaliable, &locales).await {
                 Ok(products) => HttpResponse::Ok()
                     .content_type("text/html; charset=utf-8")
                     .json(products),
-                Err(e) => HttpResponse::InternalServerError()
-                    .body(format!("Failed to read products: {}", e)),
+                Err(e) => {
+                    ApiError::from(e).error_response()
+                }
             }
         }
     }
 }
 
+// Lista os produtos página a página. A ordem é escolhida por '?sort=' ('name', 'price' ou 'newest', padrão 'name')
+// e os resultados podem ser filtrados por '?category_id=', '?min_price=', '?max_price=' e '?only_available=true'.
+// '?exclude_allergens=' (ex.: 'gluten,milk') deixa de fora os produtos que contêm algum dos alergênicos informados.
+// '?limit=' define o tamanho da página (padrão 20, máximo 100) e o 'next_cursor' retornado é enviado em '?cursor='
+// para obter a página seguinte, com a mesma ordenação.
+// O idioma é escolhido pelo parâmetro '?lang=' ou pelo cabeçalho Accept-Language, com o conteúdo original em português
+// quando não há tradução.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os produtos e o cursor da próxima página se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os parâmetros ou o cursor são inválidos.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os produtos.
+#[get("/api/v1/product/list")]
+async fn list(
+    req: HttpRequest,
+    params: web::Query<ListingParams>,
+    language: web::Query<LanguageQuery>,
+) -> impl Responder {
+    let params = params.into_inner();
+    let locales = match requested_locales(&req, language.lang()) {
+        Ok(locales) => locales,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    let exclude_allergens = match Allergen::parse_list(&params.exclude_allergens.unwrap_or_default()) {
+        Ok(allergens) => allergens,
+        Err(e) => return ApiError::validation(e).error_response(),
+    };
+
+    match ProductListing::new(
+        params.sort.unwrap_or(ProductSort::Name),
+        params.category_id,
+        params.min_price,
+        params.max_price,
+        params.only_available.unwrap_or(false),
+        exclude_allergens,
+        params.limit.unwrap_or(LISTING_DEFAULT_LIMIT),
+        params.cursor,
+    ) {
+        Ok(listing) => match DbProduct::list(listing, &locales).await {
+            Ok(page) => HttpResponse::Ok().json(page),
+            Err(e) => {
+                ApiError::from(e).error_response()
+            }
+        },
+        Err(e) => ApiError::validation(e).error_response(),
+    }
+}
+
+// Busca produtos pelo nome e pela descrição com o parâmetro '?q=', sem diferenciar acentos e considerando
+// as variações das palavras em português. A última palavra é buscada como prefixo, para a busca enquanto se digita.
+// Os parâmetros '?category_id=' e '?only_available=true' filtram os resultados e '?limit=' (padrão 20, máximo 100)
+// e '?offset=' paginam. Os resultados vêm ordenados pela relevância, com o nome e um trecho da descrição destacados.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os produtos encontrados se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os parâmetros são inválidos.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao buscar os produtos.
+#[get("/api/v1/product/search")]
+async fn search(params: web::Query<SearchParams>) -> impl Responder {
+    let params = params.into_inner();
+
+    match ProductSearch::new(
+        params.q,
+        params.category_id,
+        params.only_available.unwrap_or(false),
+        params.limit.unwrap_or(DEFAULT_LIMIT),
+        params.offset.unwrap_or(0),
+    ) {
+        Ok(search) => match DbProduct::search(search).await {
+            Ok(results) => HttpResponse::Ok().json(results),
+            Err(e) => {
+                ApiError::from(e).error_response()
+            }
+        },
+        Err(e) => ApiError::validation(e).error_response(),
+    }
+}
+
 // Atualiza as informações de um produto existente se o id fornecido for valido.
+// Os parâmetros '?user_id=&user_token=' identificam o funcionário no histórico de alterações do cardápio.
 //
 // Retorna o código 200 (OK) se a operação for bem sucedida.
 // Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
 // Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao atualizar o produto.
 #[put("/api/v1/product/update/{id}")]
-async fn update(path: web::Path<i32>, product: web::Json<Product>) -> impl Responder {
+async fn update(
+    path: web::Path<i32>,
+    actor: web::Query<Actor>,
+    product: web::Json<Product>,
+) -> impl Responder {
+    let user_id = match staff_actor(actor.into_inner()).await {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
     match Product::from_json(product) {
-        Ok(new_product) => match DbProduct::update(*path, new_product).await {
+        Ok(new_product) => match DbProduct::update(*path, new_product, user_id).await {
             Ok(_) => HttpResponse::Ok().finish(),
             Err(e) => {
-                HttpResponse::InternalServerError().body(format!("Failed to update product: {}", e))
+                ApiError::from(e).error_response()
             }
         },
-        Err(e) => HttpResponse::BadRequest().body(e),
+        Err(e) => ApiError::from(e).error_response(),
     }
 }
 
 // Remove um produto com o id fornecido
+// Os parâmetros '?user_id=&user_token=' identificam o funcionário no histórico de alterações do cardápio.
 //
 // Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
 // Retorna o código 404 (Not Found) se o produto não for encontrado.
 #[delete("/api/v1/product/delete/{id}")]
-async fn delete(path: web::Path<i32>) -> impl Responder {
-    match DbProduct::delete(*path).await {
+async fn delete(path: web::Path<i32>, actor: web::Query<Actor>) -> impl Responder {
+    let user_id = match staff_actor(actor.into_inner()).await {
+        Ok(user_id) => user_id,
+        Err(response) => return response,
+    };
+
+    match DbProduct::delete(*path, user_id).await {
         Ok(_) => HttpResponse::Ok().finish(),
-        Err(_) => HttpResponse::NotFound().finish(),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Confere o funcionário informado. Sem 'user_id' e 'user_token' a alteração fica registrada sem autor.
+async fn staff_actor(actor: Actor) -> Result<Option<i32>, HttpResponse> {
+    let (user_id, user_token) = match (actor.user_id, actor.user_token) {
+        (None, None) => return Ok(None),
+        (Some(user_id), Some(user_token)) => (user_id, user_token),
+        _ => return Err(ApiError::Unauthorized.error_response()),
+    };
+
+    match find_user_id_by_token(&user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => Ok(Some(user_id)),
+            Ok(false) => Err(ApiError::Unauthorized.error_response()),
+            Err(e) => Err(ApiError::from(e).error_response()),
+        },
+        Some(_) => Err(ApiError::Unauthorized.error_response()),
+        None => Err(ApiError::Unauthorized.error_response()),
     }
 }
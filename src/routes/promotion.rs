@@ -0,0 +1,124 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{promotion::DbPromotion, user::DbUser};
+use crate::models::{api_error::ApiError, promotion::Promotion, tokens::find_user_id_by_token};
+
+// Cria uma nova promoção ou cupom. Disponível apenas para funcionários.
+//
+// Retorna o código 201 (Created) se a promoção foi criada com sucesso.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar a promoção ou o código já existir.
+#[post("/api/v1/promotion/create/{user_id}/{user_token}")]
+async fn create(
+    path: web::Path<(i32, String)>,
+    promotion: web::Json<Promotion>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Promotion::from_json(promotion) {
+                Ok(new_promotion) => match DbPromotion::create(new_promotion).await {
+                    Ok(_) => HttpResponse::Created().finish(),
+                    Err(e) => {
+                        ApiError::from(e).error_response()
+                    }
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista as promoções automáticas em vigor. Cupons não são listados.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as promoções se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as promoções.
+#[get("/api/v1/promotion/read")]
+async fn read() -> impl Responder {
+    match DbPromotion::read_active().await {
+        Ok(promotions) => HttpResponse::Ok().json(promotions),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Lista todas as promoções e cupons. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as promoções se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as promoções.
+#[get("/api/v1/promotion/read_all/{user_id}/{user_token}")]
+async fn read_all(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbPromotion::read_all().await {
+                Ok(promotions) => HttpResponse::Ok().json(promotions),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza uma promoção. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (OK) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a promoção não for encontrada.
+#[put("/api/v1/promotion/update/{id}/{user_id}/{user_token}")]
+async fn update(
+    path: web::Path<(i32, i32, String)>,
+    promotion: web::Json<Promotion>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Promotion::from_json(promotion) {
+                Ok(new_promotion) => match DbPromotion::update(id, new_promotion).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Desativa uma promoção. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a promoção não for encontrada.
+#[delete("/api/v1/promotion/delete/{id}/{user_id}/{user_token}")]
+async fn delete(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbPromotion::delete(id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
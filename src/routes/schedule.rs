@@ -0,0 +1,83 @@
+use actix_web::{get, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{
+    schedule::{DbSchedule, ScheduleOwner},
+    user::DbUser,
+};
+use crate::models::{api_error::ApiError, schedule::Schedule, tokens::find_user_id_by_token};
+
+// Obtém os horários de um produto: os intervalos da semana e as exceções a partir de hoje.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os horários se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os horários.
+#[get("/api/v1/schedule/product/{id}")]
+async fn read_product(path: web::Path<i32>) -> impl Responder {
+    match DbSchedule::read(ScheduleOwner::Product, *path).await {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Obtém os horários de uma categoria: os intervalos da semana e as exceções a partir de hoje.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os horários se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os horários.
+#[get("/api/v1/schedule/category/{id}")]
+async fn read_category(path: web::Path<i32>) -> impl Responder {
+    match DbSchedule::read(ScheduleOwner::Category, *path).await {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Substitui os horários de um produto. Sem intervalos o produto é servido a qualquer hora.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o produto não for encontrado.
+#[put("/api/v1/schedule/product/{id}/{user_id}/{user_token}")]
+async fn update_product(
+    path: web::Path<(i32, i32, String)>,
+    schedule: web::Json<Schedule>,
+) -> impl Responder {
+    update(ScheduleOwner::Product, path.into_inner(), schedule).await
+}
+
+// Substitui os horários de uma categoria, que valem para todos os seus produtos.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a categoria não for encontrada.
+#[put("/api/v1/schedule/category/{id}/{user_id}/{user_token}")]
+async fn update_category(
+    path: web::Path<(i32, i32, String)>,
+    schedule: web::Json<Schedule>,
+) -> impl Responder {
+    update(ScheduleOwner::Category, path.into_inner(), schedule).await
+}
+
+async fn update(
+    owner: ScheduleOwner,
+    (id, user_id, user_token): (i32, i32, String),
+    schedule: web::Json<Schedule>,
+) -> HttpResponse {
+    match find_user_id_by_token(&user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Schedule::from_json(schedule) {
+                Ok(schedule) => match DbSchedule::replace(owner, id, schedule).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,289 @@
+use actix_web::{delete, get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{stock::DbStock, user::DbUser};
+use crate::models::{
+    api_error::ApiError,
+    stock::{Ingredient, RecipeItem, StockAdjustment, StockSettings},
+    tokens::find_user_id_by_token,
+};
+
+// Quantidade de registros retornados pelo histórico de estoque
+const LOG_LIMIT: i64 = 200;
+
+// Ativa ou desativa o controle de estoque de um produto e define o limite de estoque baixo.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o produto não for encontrado.
+#[put("/api/v1/stock/product/{product_id}/{user_id}/{user_token}")]
+async fn update_settings(
+    path: web::Path<(i32, i32, String)>,
+    settings: web::Json<StockSettings>,
+) -> impl Responder {
+    let (product_id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match StockSettings::from_json(settings) {
+                Ok(settings) => match DbStock::update_settings(product_id, settings).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista o estoque dos produtos controlados e dos ingredientes. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os níveis de estoque se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o estoque.
+#[get("/api/v1/stock/read/{user_id}/{user_token}")]
+async fn read(path: web::Path<(i32, String)>) -> impl Responder {
+    read_levels(path.into_inner(), false).await
+}
+
+// Lista os produtos e ingredientes com estoque baixo. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os níveis de estoque se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o estoque.
+#[get("/api/v1/stock/alerts/{user_id}/{user_token}")]
+async fn alerts(path: web::Path<(i32, String)>) -> impl Responder {
+    read_levels(path.into_inner(), true).await
+}
+
+async fn read_levels((user_id, user_token): (i32, String), only_low: bool) -> HttpResponse {
+    match find_user_id_by_token(&user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbStock::read_levels(only_low).await {
+                Ok(levels) => HttpResponse::Ok().json(levels),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Ajusta o estoque de um produto ou ingrediente, registrando o motivo. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos ou o estoque ficaria negativo.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+#[post("/api/v1/stock/adjust/{user_id}/{user_token}")]
+async fn adjust(
+    path: web::Path<(i32, String)>,
+    adjustment: web::Json<StockAdjustment>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match StockAdjustment::from_json(adjustment) {
+                Ok(adjustment) => match DbStock::adjust(user_id, adjustment).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista as últimas alterações de estoque: ajustes, pedidos e cancelamentos. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as alterações se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler o histórico.
+#[get("/api/v1/stock/log/{user_id}/{user_token}")]
+async fn read_log(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbStock::read_log(LOG_LIMIT).await {
+                Ok(movements) => HttpResponse::Ok().json(movements),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Busca a receita de um produto. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os ingredientes da receita se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler a receita.
+#[get("/api/v1/stock/recipe/{product_id}/{user_id}/{user_token}")]
+async fn read_recipe(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (product_id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbStock::read_recipe(product_id).await {
+                Ok(recipe) => HttpResponse::Ok().json(recipe),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Substitui a receita de um produto. Uma lista vazia remove a receita. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao gravar a receita.
+#[put("/api/v1/stock/recipe/{product_id}/{user_id}/{user_token}")]
+async fn update_recipe(
+    path: web::Path<(i32, i32, String)>,
+    recipe: web::Json<Vec<RecipeItem>>,
+) -> impl Responder {
+    let (product_id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match RecipeItem::from_json(recipe) {
+                Ok(recipe) => match DbStock::replace_recipe(product_id, recipe).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Cadastra um ingrediente. O estoque começa em zero e é alterado por ajustes.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 201 (Created) se o ingrediente foi criado com sucesso.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar o ingrediente ou o nome já existir.
+#[post("/api/v1/stock/ingredient/create/{user_id}/{user_token}")]
+async fn create_ingredient(
+    path: web::Path<(i32, String)>,
+    ingredient: web::Json<Ingredient>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Ingredient::from_json(ingredient) {
+                Ok(new_ingredient) => match DbStock::create_ingredient(new_ingredient).await {
+                    Ok(_) => HttpResponse::Created().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os ingredientes com o estoque atual. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os ingredientes se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os ingredientes.
+#[get("/api/v1/stock/ingredient/read_all/{user_id}/{user_token}")]
+async fn read_ingredients(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbStock::read_ingredients().await {
+                Ok(ingredients) => HttpResponse::Ok().json(ingredients),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Atualiza o nome, a unidade e o limite de estoque baixo de um ingrediente. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (OK) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o ingrediente não for encontrado.
+#[put("/api/v1/stock/ingredient/update/{id}/{user_id}/{user_token}")]
+async fn update_ingredient(
+    path: web::Path<(i32, i32, String)>,
+    ingredient: web::Json<Ingredient>,
+) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Ingredient::from_json(ingredient) {
+                Ok(new_ingredient) => match DbStock::update_ingredient(id, new_ingredient).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Remove um ingrediente e o retira das receitas. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o ingrediente não for encontrado.
+#[delete("/api/v1/stock/ingredient/delete/{id}/{user_id}/{user_token}")]
+async fn delete_ingredient(path: web::Path<(i32, i32, String)>) -> impl Responder {
+    let (id, user_id, user_token) = (path.0, path.1, &path.2);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbStock::delete_ingredient(id).await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
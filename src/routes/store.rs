@@ -0,0 +1,168 @@
+use actix_web::{get, post, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{store::DbStore, user::DbUser};
+use crate::models::{
+    api_error::ApiError,
+    store::{OrderScheduleSettings, StoreHours, StorePause},
+    tokens::find_user_id_by_token,
+};
+
+// Informa se o restaurante está aberto, se os pedidos estão pausados e quando abre novamente.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com a situação do restaurante se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler a situação.
+#[get("/api/v1/store/status")]
+async fn status() -> impl Responder {
+    match DbStore::read_status().await {
+        Ok(status) => HttpResponse::Ok().json(status),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Obtém o horário de funcionamento e os próximos feriados.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os horários se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler os horários.
+#[get("/api/v1/store/hours")]
+async fn read_hours() -> impl Responder {
+    match DbStore::read_hours().await {
+        Ok(hours) => HttpResponse::Ok().json(hours),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Substitui o horário de funcionamento e os feriados. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao gravar os horários.
+#[put("/api/v1/store/hours/{user_id}/{user_token}")]
+async fn update_hours(
+    path: web::Path<(i32, String)>,
+    hours: web::Json<StoreHours>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match StoreHours::from_json(hours) {
+                Ok(hours) => match DbStore::update_hours(hours).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Pausa o recebimento de pedidos com um motivo e, opcionalmente, o horário de retorno.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao pausar os pedidos.
+#[post("/api/v1/store/pause/{user_id}/{user_token}")]
+async fn pause(path: web::Path<(i32, String)>, pause: web::Json<StorePause>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match StorePause::from_json(pause) {
+                Ok(pause) => match DbStore::pause(pause).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Retoma o recebimento de pedidos. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao retomar os pedidos.
+#[post("/api/v1/store/resume/{user_id}/{user_token}")]
+async fn resume(path: web::Path<(i32, String)>) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match DbStore::resume().await {
+                Ok(_) => HttpResponse::Ok().finish(),
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
+
+// Lista os horários de uma data (YYYY-MM-DD) disponíveis para pedidos agendados, com as vagas restantes.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com os horários se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se a data for inválida ou houver problema ao ler os horários.
+#[get("/api/v1/store/slots/{date}")]
+async fn read_slots(path: web::Path<String>) -> impl Responder {
+    match DbStore::read_slots(path.into_inner()).await {
+        Ok(slots) => HttpResponse::Ok().json(slots),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Obtém as regras dos pedidos agendados.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as regras se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as regras.
+#[get("/api/v1/store/schedule_settings")]
+async fn read_schedule_settings() -> impl Responder {
+    match DbStore::read_schedule_settings().await {
+        Ok(settings) => HttpResponse::Ok().json(settings),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Atualiza as regras dos pedidos agendados: tamanho e capacidade dos intervalos, antecedência de envio
+// à cozinha e limites de agendamento. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao gravar as regras.
+#[put("/api/v1/store/schedule_settings/{user_id}/{user_token}")]
+async fn update_schedule_settings(
+    path: web::Path<(i32, String)>,
+    settings: web::Json<OrderScheduleSettings>,
+) -> impl Responder {
+    let (user_id, user_token) = (path.0, &path.1);
+
+    match find_user_id_by_token(user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match OrderScheduleSettings::from_json(settings) {
+                Ok(settings) => match DbStore::update_schedule_settings(settings).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::validation(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -0,0 +1,83 @@
+use actix_web::{get, put, web, HttpResponse, Responder, ResponseError};
+
+use crate::data::{
+    translation::{DbTranslation, TranslationOwner},
+    user::DbUser,
+};
+use crate::models::{api_error::ApiError, tokens::find_user_id_by_token, translation::Translation};
+
+// Lista as traduções do nome e da descrição de um produto.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as traduções se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as traduções.
+#[get("/api/v1/translation/product/{id}")]
+async fn read_product(path: web::Path<i32>) -> impl Responder {
+    match DbTranslation::read(TranslationOwner::Product, *path).await {
+        Ok(translations) => HttpResponse::Ok().json(translations),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Lista as traduções do nome e da descrição de uma categoria.
+//
+// Retorna o código 200 (Ok) e um objeto JSON com as traduções se a operação for bem sucedida.
+// Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houver problema ao ler as traduções.
+#[get("/api/v1/translation/category/{id}")]
+async fn read_category(path: web::Path<i32>) -> impl Responder {
+    match DbTranslation::read(TranslationOwner::Category, *path).await {
+        Ok(translations) => HttpResponse::Ok().json(translations),
+        Err(e) => ApiError::from(e).error_response(),
+    }
+}
+
+// Substitui as traduções de um produto, uma por idioma (ex.: [{"locale": "en", "name": "Meatballs"}]).
+// O conteúdo em português é o do próprio produto. Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se o produto não for encontrado.
+#[put("/api/v1/translation/product/{id}/{user_id}/{user_token}")]
+async fn update_product(
+    path: web::Path<(i32, i32, String)>,
+    translations: web::Json<Vec<Translation>>,
+) -> impl Responder {
+    update(TranslationOwner::Product, path.into_inner(), translations).await
+}
+
+// Substitui as traduções de uma categoria, uma por idioma. O conteúdo em português é o da própria categoria.
+// Disponível apenas para funcionários.
+//
+// Retorna o código 200 (Ok) se a operação for bem sucedida.
+// Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados são inválidos.
+// Retorna o código 401 (Unauthorized) se o token fornecido é inválido ou o usuário não é funcionário.
+// Retorna o código 404 (Not Found) se a categoria não for encontrada.
+#[put("/api/v1/translation/category/{id}/{user_id}/{user_token}")]
+async fn update_category(
+    path: web::Path<(i32, i32, String)>,
+    translations: web::Json<Vec<Translation>>,
+) -> impl Responder {
+    update(TranslationOwner::Category, path.into_inner(), translations).await
+}
+
+async fn update(
+    owner: TranslationOwner,
+    (id, user_id, user_token): (i32, i32, String),
+    translations: web::Json<Vec<Translation>>,
+) -> HttpResponse {
+    match find_user_id_by_token(&user_token) {
+        Some(user_id_token) if user_id_token == user_id => match DbUser::is_staff(user_id).await {
+            Ok(true) => match Translation::from_json(translations) {
+                Ok(translations) => match DbTranslation::replace(owner, id, translations).await {
+                    Ok(_) => HttpResponse::Ok().finish(),
+                    Err(e) => ApiError::from(e).error_response(),
+                },
+                Err(e) => ApiError::from(e).error_response(),
+            },
+            Ok(false) => ApiError::Unauthorized.error_response(),
+            Err(e) => ApiError::from(e).error_response(),
+        },
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
+    }
+}
@@ -1,4 +1,6 @@
-use actix_web::{delete, get, post, put, web, HttpResponse, Responder};
+use actix_web::{
+    delete, get, http::header, post, put, web, HttpResponse, Responder, ResponseError,
+};
 
 use lettre::transport::smtp::authentication::Credentials;
 use lettre::{Message, SmtpTransport, Transport};
@@ -50,31 +52,31 @@ struct Email {
 
 use crate::data::user::DbUser;
 use crate::models::{
+    api_error::ApiError,
     tokens::{find_user_id_by_token, remove_token},
     user::User,
 };
 
 // Cria um novo usuário com os dados fornecidos.
 //
-// Retorna o código 201 (Created) se o usuário foi criado com sucesso.
+// Retorna o código 201 (Created) e um objeto JSON com o usuário gravado, sem a senha, se o usuário foi criado com sucesso.
+// O cabeçalho Location aponta para a leitura do usuário sem o token, que o cliente acrescenta ao final ('/{token}').
 // Retorna o código 400 (Bad Request) com uma mensagem de erro se os dados fornecidos são inválidos.
-// Retorna o código 422 (Unprocessable Entity) com uma mensagem de erro se o username já existe.
+// Retorna o código 409 (Conflict) com uma mensagem de erro se o username já existe.
 // Retorna o código 500 (Internal Server Error) com uma mensagem de erro se houve problema ao criar o usuário.
 #[post("/api/v1/user/create")]
 pub async fn create(user: web::Json<User>) -> impl Responder {
     match User::from_json(user) {
         Ok(new_user) => match DbUser::create(new_user).await {
-            Ok(_) => HttpResponse::Created().finish(),
-            Err(e) => {
-                if e.contains("Username already exists.") {
-                    HttpResponse::UnprocessableEntity().body(e)
-                } else {
-                    HttpResponse::InternalServerError()
-                        .body(format!("Failed to create user: {}", e))
-                }
-            }
+            Ok(user) => HttpResponse::Created()
+                .insert_header((
+                    header::LOCATION,
+                    format!("/api/v1/user/read/{}", user.id().unwrap_or_default()),
+                ))
+                .json(user),
+            Err(e) => ApiError::from(e).error_response(),
         },
-        Err(e) => HttpResponse::BadRequest().body(e),
+        Err(e) => ApiError::from(e).error_response(),
     }
 }
 
@@ -92,10 +94,10 @@ pub async fn read(path: web::Path<(i32, String)>) -> impl Responder {
             Ok(user) => HttpResponse::Ok()
                 .content_type("text/html; charset=utf-8")
                 .json(user),
-            Err(e) => HttpResponse::NotFound().body(e),
+            Err(e) => ApiError::from(e).error_response(),
         },
-        Some(_) => HttpResponse::Unauthorized().finish(),
-        None => HttpResponse::Unauthorized().finish(),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
 }
 
@@ -115,17 +117,16 @@ pub async fn update(path: web::Path<(i32, String)>, user: web::Json<User>) -> im
                 Ok(acess) if Some(acess) >= new_user.acess() => {
                     match DbUser::update(new_user).await {
                         Ok(_) => HttpResponse::Ok().finish(),
-                        Err(e) => HttpResponse::InternalServerError()
-                            .body(format!("Failed to update user: {}", e)),
+                        Err(e) => ApiError::from(e).error_response(),
                     }
                 }
-                Ok(_) => HttpResponse::Unauthorized().finish(),
-                Err(e) => HttpResponse::BadRequest().body(e),
+                Ok(_) => ApiError::Unauthorized.error_response(),
+                Err(e) => ApiError::from(e).error_response(),
             },
-            Err(e) => HttpResponse::BadRequest().body(e),
+            Err(e) => ApiError::from(e).error_response(),
         },
-        Some(_) => HttpResponse::Unauthorized().finish(),
-        None => HttpResponse::Unauthorized().finish(),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
 }
 
@@ -144,10 +145,10 @@ pub async fn delete(path: web::Path<(i32, String)>) -> impl Responder {
                 remove_token(token);
                 HttpResponse::Ok().finish()
             }
-            Err(_) => HttpResponse::NotFound().finish(),
+            Err(e) => ApiError::from(e).error_response(),
         },
-        Some(_) => HttpResponse::Unauthorized().finish(),
-        None => HttpResponse::Unauthorized().finish(),
+        Some(_) => ApiError::Unauthorized.error_response(),
+        None => ApiError::Unauthorized.error_response(),
     }
 }
 
@@ -162,7 +163,7 @@ pub async fn get_admin() -> impl Responder {
         Ok(users) => HttpResponse::Ok()
             .content_type("text/html; charset=utf-8")
             .json(users),
-        Err(e) => HttpResponse::BadRequest().body(e),
+        Err(e) => ApiError::from(e).error_response(),
     }
 }
 
@@ -210,8 +211,7 @@ async fn send_recovery_code(
             HttpResponse::Ok().json("Código de recuperação enviado com sucesso.")
         }
         Err(e) => {
-            eprintln!("Erro ao enviar e-mail: {:?}", e);
-            HttpResponse::InternalServerError().json("Erro ao enviar o e-mail.")
+            ApiError::Internal(format!("Failed to send recovery e-mail: {:?}", e)).error_response()
         }
     }
 }
@@ -231,10 +231,10 @@ async fn change_password(
     if is_valid {
         recovery_codes.remove_code(&email);
         match DbUser::update_password(&email, password).await {
-            Ok(_) => return HttpResponse::Ok().json("Senha alterada com sucesso."),
-            Err(e) => return HttpResponse::BadRequest().body(e),
-        };
+            Ok(_) => HttpResponse::Ok().json("Senha alterada com sucesso."),
+            Err(e) => ApiError::from(e).error_response(),
+        }
     } else {
-        return HttpResponse::BadRequest().json("Código de recuperação inválido.");
+        ApiError::validation("Código de recuperação inválido.".to_string()).error_response()
     }
 }